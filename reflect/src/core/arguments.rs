@@ -0,0 +1,330 @@
+//! Arrangement of arguments given by position and by name
+//! - arguments given by name are placed by the parameter names of a function
+//! - parameters without an argument take their default value, if any
+//! - unlike calls with positional arguments only, the resulting conversions are not cached
+//! - resolution of the function called and the conversion of its arguments, shared by `TypeInfo`
+//!   and `Conversions::resolve` (see `resolve`)
+//!
+
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+
+use lazy_static::lazy_static;
+
+use crate::{CTorParser, Conversions, CtorExpr, Function, ReflectError};
+use crate::core::conversions::is_exact_mode;
+use crate::core::plans::{ConversionPlan, PlanCache, PlanKind};
+
+
+// Default value expressions of parameters, as parsed (by expression)
+lazy_static! {
+    static ref DEFAULTS: RwLock<HashMap<String, Arc<CtorExpr>>> = RwLock::new(HashMap::new());
+}
+
+
+/// Argument of a call, as given by the caller or produced (a default value or conversion)
+pub(crate) enum Argument<'a> {
+    Given(&'a Box<dyn Any>),
+    Produced(Box<dyn Any>),
+}
+
+
+impl Argument<'_> {
+
+    /// Value of the argument
+    pub fn value (&self) -> &Box<dyn Any> {
+        match self {
+            Argument::Given(value) => value,
+            Argument::Produced(value) => value,
+        }
+    }
+}
+
+
+/// Arguments as passed to a function
+pub(crate) fn argv<'a> (arguments: &'a [Argument<'_>]) -> Vec<&'a dyn Any> {
+    arguments.iter().map(|arg| arg.value().as_ref()).collect()
+}
+
+
+/// Arguments given by the caller, with those converted by a conversion plan replaced (see
+/// `Conversions::argv`)
+pub(crate) fn converted (args: &[Box<dyn Any>], converted: Vec<Option<Box<dyn Any>>>) -> Vec<Argument<'_>> {
+    args.iter().zip(converted).map(|(arg, converted)| match converted {
+        Some(value) => Argument::Produced(value),
+        None => Argument::Given(arg)
+    }).collect()
+}
+
+
+/// Arguments given by the caller, owned, with those converted by a conversion plan replaced (see
+/// `Conversions::argv`), followed by those produced for parameters without an argument
+pub(crate) fn converted_owned (args: Vec<Box<dyn Any>>, converted: Vec<Option<Box<dyn Any>>>) -> Vec<Box<dyn Any>> {
+    let mut args = args.into_iter();
+    converted.into_iter().filter_map(|converted| {
+        let arg = args.next();
+        converted.or(arg)
+    }).collect()
+}
+
+
+/// Arguments produced by resolution (converted, or default values), `None` where passed as given
+pub(crate) fn produced (arguments: Vec<Argument<'_>>) -> Vec<Option<Box<dyn Any>>> {
+    arguments.into_iter().map(|arg| match arg {
+        Argument::Given(_) => None,
+        Argument::Produced(value) => Some(value)
+    }).collect()
+}
+
+
+/// Function chosen by resolution, with its arguments converted as required
+pub(crate) type Resolved<'a, 'b, F> = (&'a F, Vec<Argument<'b>>);
+
+
+/// Resolve the best of the candidates for positional arguments, converting the arguments as
+/// required
+/// - the candidate and the conversions to apply are planned (see `ConversionPlan`), the plan
+///   being cached if given a cache, with the kind and name of the candidates
+/// - where overloads take the same parameters, the one boxing `returning` (if given, see
+///   `Function::boxed_type`) is chosen, otherwise the first registered
+/// - if none takes the arguments given, those with defaults for the remaining parameters are
+///   considered, with the same preference (see `find_best_arranged`)
+///
+/// # Arguments
+/// * `plans`: cache of plans, with the kind and name of the candidates, if any
+/// * `candidates`: candidate functions
+/// * `args`: positional arguments
+/// * `returning`: type of the result preferred, if any
+///
+/// # Returns
+/// * the best candidate, with its arguments converted to the parameter types, `None` if no
+///   candidate can be called with the arguments, or an error evaluating a default value or
+///   converting an argument
+pub(crate) fn resolve<'a, 'b, F: ?Sized + Function> (plans: Option<(&PlanCache, PlanKind, &str)>, candidates: &'a [Box<F>], args: &'b [Box<dyn Any>], returning: Option<TypeId>)
+    -> Result<Option<Resolved<'a, 'b, F>>, ReflectError> {
+    let plan = match plans {
+        Some((plans, kind, name)) => plans.find(kind, name, candidates, args),
+        None => ConversionPlan::new(candidates, args)
+    };
+    let plan = match plan {
+        Some(plan) => plan,
+        None => return find_best_arranged (candidates, args, &[], returning)
+    };
+
+    let mut function = candidates[plan.index].as_ref();
+    if let Some(returning) = returning {
+        let preferred = candidates.iter()
+            .find(|c| c.boxed_type() == returning && c.arg_types() == function.arg_types());
+        if let Some(preferred) = preferred {
+            function = preferred.as_ref();
+        }
+    }
+
+    let newargs = plan.convert(function.arg_types(), args)?;
+    Ok(Some((function, converted(args, newargs))))
+}
+
+
+/// Find the best of the candidates for positional arguments followed by arguments by name
+/// - candidates are ranked as by `Conversions::find_best_match`, given the types of their
+///   arguments in order of their parameters, a parameter taking its default value being of the
+///   parameter type (see `select`)
+/// - only the default values of the candidate chosen are evaluated (see `arrange`)
+///
+/// # Arguments
+/// * `candidates`: candidate functions
+/// * `args`: positional arguments
+/// * `kwargs`: arguments by parameter name
+/// * `returning`: type of the result preferred, if any
+///
+/// # Returns
+/// * the best candidate, with its arguments converted to the parameter types, `None` if no
+///   candidate can be called with the arguments, or an error evaluating a default value or
+///   converting an argument
+pub(crate) fn find_best_arranged<'a, 'b, F: ?Sized + Function> (candidates: &'a [Box<F>], args: &'b [Box<dyn Any>], kwargs: &'b [(String, Box<dyn Any>)], returning: Option<TypeId>)
+    -> Result<Option<(&'a F, Vec<Argument<'b>>)>, ReflectError> {
+    let arg_types: Vec<TypeId> = args.iter().map(|arg| (**arg).type_id()).collect();
+    let kwarg_types: Vec<(String, TypeId)> = kwargs.iter().map(|(name, arg)| (name.clone(), (**arg).type_id())).collect();
+    let function = match select (candidates, &arg_types, &kwarg_types, returning) {
+        Some(function) => function,
+        None => return Ok(None)
+    };
+    let mut arranged = match arrange (function, args, kwargs)? {
+        Some(arranged) => arranged,
+        None => return Ok(None)
+    };
+
+    // arguments not of the parameter type (including default values) are converted
+    for (to_type, arg) in function.arg_types().iter().zip(arranged.iter_mut()) {
+        let arg_type = (**arg.value()).type_id();
+        if arg_type == *to_type {
+            continue;
+        }
+        let conversion = Conversions::find(arg_type, *to_type);
+        if conversion.as_ref().is_some_and(|conversion| conversion.is_pass_through()) {
+            continue;
+        }
+        match conversion.and_then(|conversion| conversion.convert(arg.value())) {
+            Some(value) => *arg = Argument::Produced(value),
+            None => return Err(ReflectError::ConversionFailed {
+                from: Conversions::type_name(arg_type),
+                to: Conversions::type_name(*to_type)
+            })
+        }
+    }
+    Ok(Some((function, arranged)))
+}
+
+
+/// Select the best of the candidates for arguments of the given types, without converting or
+/// evaluating any argument
+/// - arguments are arranged by `arrange_types`, a parameter taking its default value being of
+///   the parameter type, and ranked by `Conversions::rank`, ties broken by signature
+/// - when dispatching on exact matches only (see `set_exact_mode`), candidates requiring a
+///   default value or the conversion of an argument (other than to an equivalent type) are
+///   skipped
+/// - where overloads take the same parameters, the one boxing `returning` (if given, see
+///   `Function::boxed_type`) is chosen
+fn select<'a, F: ?Sized + Function> (candidates: &'a [Box<F>], arg_types: &[TypeId], kwarg_types: &[(String, TypeId)], returning: Option<TypeId>) -> Option<&'a F> {
+    let mut best: Option<(&'a F, (i32, usize, i32))> = None;
+    for candidate in candidates {
+        let params = candidate.arg_types();
+        if is_exact_mode() && arg_types.len() + kwarg_types.len() != params.len() {
+            continue;
+        }
+        let arranged = match arrange_types (candidate.as_ref(), arg_types, kwarg_types) {
+            Some(arranged) => arranged,
+            None => continue
+        };
+        if is_exact_mode() && !exactly (params, &arranged) {
+            continue;
+        }
+        let rank = match Conversions::rank (params, &arranged) {
+            Some(rank) if rank.0 > 0 => rank,
+            _ => continue
+        };
+
+        let better = match &best {
+            Some((best, best_rank)) => rank > *best_rank ||
+                (rank == *best_rank && candidate.arg_type_names() < best.arg_type_names()),
+            None => true
+        };
+        if better {
+            best = Some((candidate.as_ref(), rank));
+        }
+    }
+
+    let (best, _) = best?;
+    let preferred = returning.and_then(|returning| candidates.iter().find(|c| c.boxed_type() == returning &&
+        c.arg_types() == best.arg_types() && arrange_types (c.as_ref(), arg_types, kwarg_types).is_some()));
+    Some(preferred.map_or(best, |preferred| preferred.as_ref()))
+}
+
+
+// Determine whether arguments of the given types are passed as is (or as an equivalent type),
+// as by `Function::matching`
+fn exactly (parameters: &[TypeId], arg_types: &[TypeId]) -> bool {
+    parameters.iter().zip(arg_types).all(|(param_type, arg_type)| {
+        arg_type == param_type ||
+            Conversions::find(*arg_type, *param_type).is_some_and(|conversion| conversion.is_equivalent())
+    })
+}
+
+
+/// Arrange positional arguments followed by arguments by name in order of the parameters of a
+/// function, filling parameters without an argument with their default values
+///
+/// # Returns
+/// * arguments in order of the parameters, `None` if an argument names no parameter or a
+///   parameter given positionally, or a parameter has neither argument nor default, or an error
+///   evaluating a default value
+fn arrange<'b, F: ?Sized + Function> (function: &F, args: &'b [Box<dyn Any>], kwargs: &'b [(String, Box<dyn Any>)])
+    -> Result<Option<Vec<Argument<'b>>>, ReflectError> {
+    let arity = function.arg_types().len();
+    if args.len() + kwargs.len() > arity {
+        return Ok(None);
+    }
+
+    let mut arranged: Vec<Option<Argument>> = args.iter().map(|arg| Some(Argument::Given(arg))).collect();
+    arranged.resize_with(arity, || None);
+    for (name, value) in kwargs {
+        match function.arg_names().iter().position(|n| n == name) {
+            Some(index) if arranged[index].is_none() => arranged[index] = Some(Argument::Given(value)),
+            _ => return Ok(None)
+        }
+    }
+
+    // parameters without an argument take their default value
+    let defaults = function.arg_defaults();
+    let mut filled = Vec::with_capacity(arity);
+    for (index, arg) in arranged.into_iter().enumerate() {
+        match (arg, defaults.get(index).copied().flatten()) {
+            (Some(arg), _) => filled.push(arg),
+            (None, Some(expr)) => filled.push(Argument::Produced(default_value (expr)?)),
+            (None, None) => return Ok(None)
+        }
+    }
+    Ok(Some(filled))
+}
+
+
+/// Find the best of the candidates for arguments of the given types, without converting or
+/// evaluating any argument
+/// - candidates are selected as by `find_best_arranged` (see `select`), including when
+///   dispatching on exact matches only
+///
+/// # Arguments
+/// * `candidates`: candidate functions
+/// * `arg_types`: types of the positional arguments
+/// * `kwarg_types`: types of the arguments by parameter name
+///
+/// # Returns
+/// * the best candidate, or `None` if no candidate can be called with arguments of the types
+pub(crate) fn find_viable<'a, F: ?Sized + Function> (candidates: &'a [Box<F>], arg_types: &[TypeId], kwarg_types: &[(String, TypeId)]) -> Option<&'a F> {
+    select (candidates, arg_types, kwarg_types, None)
+}
+
+
+// Arrange the types of positional arguments followed by arguments by name in order of the
+// parameters of a function, as `arrange` does the arguments themselves
+fn arrange_types<F: ?Sized + Function> (function: &F, arg_types: &[TypeId], kwarg_types: &[(String, TypeId)]) -> Option<Vec<TypeId>> {
+    let params = function.arg_types();
+    if arg_types.len() + kwarg_types.len() > params.len() {
+        return None;
+    }
+
+    let mut arranged: Vec<Option<TypeId>> = arg_types.iter().map(|arg_type| Some(*arg_type)).collect();
+    arranged.resize(params.len(), None);
+    for (name, arg_type) in kwarg_types {
+        match function.arg_names().iter().position(|n| n == name) {
+            Some(index) if arranged[index].is_none() => arranged[index] = Some(*arg_type),
+            _ => return None
+        }
+    }
+
+    let defaults = function.arg_defaults();
+    arranged.into_iter().enumerate()
+        .map(|(index, arg_type)| arg_type.or_else(|| defaults.get(index).copied().flatten().map(|_| params[index])))
+        .collect()
+}
+
+
+// Evaluate the default value of a parameter, the expression being parsed once
+fn default_value (expr: &str) -> Result<Box<dyn Any>, ReflectError> {
+    parsed_default (expr).and_then(|parsed| CTorParser::evaluate (&parsed)).map_err(|error| match error {
+        ReflectError::InExpression { .. } => error,
+        _ => ReflectError::InExpression { expression: expr.to_string(), error: Box::new(error) }
+    })
+}
+
+
+// Parsed default value expression, as cached
+fn parsed_default (expr: &str) -> Result<Arc<CtorExpr>, ReflectError> {
+    if let Some(parsed) = DEFAULTS.read().unwrap().get(expr) {
+        return Ok(parsed.clone());
+    }
+    let parsed = Arc::new(CTorParser::parse_value (expr)?);
+    DEFAULTS.write().unwrap().insert(expr.to_string(), parsed.clone());
+    Ok(parsed)
+}
@@ -0,0 +1,129 @@
+//! Registration of types by hand, without `reflect_impl`
+//! - for types whose ctors, methods, and static functions are only known at runtime, for
+//!   example generated from a schema
+//!
+
+use std::any::TypeId;
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+use crate::core::{Constructor, Method, Scope, StaticFunction, TypeInfo};
+use crate::core::registration::{register_constructor, register_function, register_method, register_type, find_type_by_id, global_type_by_id};
+
+
+/// Builder of the type information of a type, registered as a whole
+/// ```ignore
+///    let itype = TypeInfoBuilder::<Record>::new()
+///        .name("schema::Trade")
+///        .constructor(Box::new(RecordConstructor::new(&schema)))
+///        .method(Box::new(FieldGetter::new("price")))
+///        .register();
+/// ```
+pub struct TypeInfoBuilder<T: 'static> {
+    name: Option<String>,
+    constructors: Vec<Box<dyn Constructor>>,
+    methods: Vec<Box<dyn Method>>,
+    functions: Vec<Box<dyn StaticFunction>>,
+    _type: PhantomData<fn() -> T>,
+}
+
+
+impl<T: 'static> Default for TypeInfoBuilder<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+
+impl<T: 'static> TypeInfoBuilder<T> {
+
+    /// Builder without ctors, methods, or static functions
+    pub fn new () -> Self {
+        TypeInfoBuilder {
+            name: None,
+            constructors: Vec::new(),
+            methods: Vec::new(),
+            functions: Vec::new(),
+            _type: PhantomData
+        }
+    }
+
+    /// Name under which the type is registered, otherwise its registered name (see `registered_name`)
+    ///
+    /// # Arguments
+    /// - `name`: name of the type, for example `"schema::Trade"`
+    pub fn name (mut self, name: &str) -> Self {
+        self.name = Some(name.to_string());
+        self
+    }
+
+    /// Add a constructor
+    ///
+    /// # Arguments
+    /// - `constructor`: constructor to be added
+    pub fn constructor (mut self, constructor: Box<dyn Constructor>) -> Self {
+        self.constructors.push(constructor);
+        self
+    }
+
+    /// Add a method
+    /// - methods sharing a name are overloads of the name, resolved when called
+    ///
+    /// # Arguments
+    /// - `method`: method to be added
+    pub fn method (mut self, method: Box<dyn Method>) -> Self {
+        self.methods.push(method);
+        self
+    }
+
+    /// Add a static function
+    /// - static functions sharing a name are overloads of the name, resolved when called
+    ///
+    /// # Arguments
+    /// - `function`: function to be added
+    pub fn function (mut self, function: Box<dyn StaticFunction>) -> Self {
+        self.functions.push(function);
+        self
+    }
+
+    /// Register the type globally, adding to the members of the type if already registered
+    /// - as if registered with `register_type`, `register_constructor`, `register_method`,
+    ///   and `register_function`
+    ///
+    /// # Returns
+    /// - type information of the type as registered
+    pub fn register (self) -> Arc<TypeInfo> {
+        register_type::<T>(self.name.as_deref());
+        for constructor in self.constructors {
+            register_constructor::<T>(constructor);
+        }
+        for method in self.methods {
+            register_method::<T>(method);
+        }
+        for function in self.functions {
+            register_function::<T>(function);
+        }
+        global_type_by_id(TypeId::of::<T>()).expect("type registered")
+    }
+
+    /// Register the type within a scope, visible only until the scope ends (see `with_scope`)
+    ///
+    /// # Arguments
+    /// - `scope`: scope in which to register the type
+    ///
+    /// # Returns
+    /// - type information of the type as registered
+    pub fn register_in (self, scope: &Scope) -> Arc<TypeInfo> {
+        scope.register_type::<T>(self.name.as_deref());
+        for constructor in self.constructors {
+            scope.register_constructor::<T>(constructor);
+        }
+        for method in self.methods {
+            scope.register_method::<T>(method);
+        }
+        for function in self.functions {
+            scope.register_function::<T>(function);
+        }
+        find_type_by_id(TypeId::of::<T>()).expect("type registered")
+    }
+}
@@ -2,16 +2,78 @@
 use std::any::{TypeId};
 use std::any::type_name;
 
-use crate::core::{Function};
+use crate::core::{Function, ReflectError};
+use crate::core::numeric::numeric_family;
+use crate::core::arguments::{converted_owned, produced, resolve};
 
 use lazy_static::lazy_static;
 use std::collections::HashMap;
 use std::sync::{RwLock,Arc};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::any::Any;
 use std::str::FromStr;
 
 // Conversion function type
-type ConversionFn = fn(&Box<dyn Any>) -> Option<Box<dyn Any>>;
+pub(crate) type ConversionFn = fn(&Box<dyn Any>) -> Option<Box<dyn Any>>;
+
+// Conversion function as stored, which may capture state (see `Conversions::add_fn`)
+pub(crate) type Converter = Arc<dyn Fn(&Box<dyn Any>) -> Option<Box<dyn Any>> + Send + Sync>;
+
+// Converted arguments (see `Conversions::convert_argv`), with the conversion of each argument
+type TracedArgv = (Vec<Option<Box<dyn Any>>>, Vec<ConvertedArg>);
+
+// Function resolved, with its arguments converted (see `Conversions::resolve`)
+type ResolvedArgv<'a, T> = (&'a T, Vec<Box<dyn Any>>);
+
+// Conversion function, as stored
+fn step (convert: ConversionFn) -> Converter {
+    Arc::new(convert)
+}
+
+
+// Readable names of types participating in conversions and reflection
+lazy_static! {
+    static ref TYPE_NAMES: RwLock<HashMap<TypeId,String>> = RwLock::new(HashMap::new());
+}
+
+
+// Number of changes to the conversions map (see `Conversions::generation`)
+static GENERATION: AtomicUsize = AtomicUsize::new(0);
+
+
+// Whether numeric conversions reject values not representable in the target type
+// (see `Conversions::set_strict`)
+static STRICT: AtomicBool = AtomicBool::new(false);
+
+
+// Whether conversions may be chained when there is no direct conversion
+// (see `Conversions::set_transitive`)
+static TRANSITIVE: AtomicBool = AtomicBool::new(false);
+
+
+// Whether ctors, methods, and static functions are only called with arguments of their
+// parameter types (see `Conversions::set_exact_mode`)
+static EXACT: AtomicBool = AtomicBool::new(false);
+
+
+/// Set whether calls dispatch on exact matches only (see `Conversions::set_exact_mode`)
+pub fn set_exact_mode (exact: bool) {
+    Conversions::set_exact_mode(exact);
+}
+
+/// Whether calls dispatch on exact matches only (see `Conversions::set_exact_mode`)
+pub fn is_exact_mode () -> bool {
+    Conversions::is_exact_mode()
+}
+
+
+// Chained conversions found (or not) between pairs of types lacking a direct conversion,
+// cleared when conversions change
+type Chain = Option<Arc<Conversions>>;
+
+lazy_static! {
+    static ref CHAINS: RwLock<HashMap<(TypeId,TypeId),Chain>> = RwLock::new(HashMap::new());
+}
 
 
 // Type conversions map
@@ -23,23 +85,45 @@ lazy_static! {
             let mut m = rawmap.write().unwrap();
 
             let mut add = |t1: TypeId, t2: TypeId, score: i32, f: ConversionFn| {
-                m.insert((t1,t2), Arc::new(Conversions { score: score, convert: f}));
+                m.insert((t1,t2), Arc::new(Conversions { score, steps: vec![step(f)], via: None }));
             };
 
-            let ti32 = TypeId::of::<i32>();
-            let tu32 = TypeId::of::<u32>();
-            let ti64 = TypeId::of::<i64>();
-            let tu64 = TypeId::of::<u64>();
-            let tf64 = TypeId::of::<f64>();
-            let tstr = TypeId::of::<String>();
+            let ti8 = named::<i8>();
+            let ti16 = named::<i16>();
+            let tu8 = named::<u8>();
+            let tu16 = named::<u16>();
+            let ti32 = named::<i32>();
+            let tu32 = named::<u32>();
+            let ti64 = named::<i64>();
+            let tu64 = named::<u64>();
+            let tf32 = named::<f32>();
+            let tf64 = named::<f64>();
+            let tstr = named::<String>();
+            let trstr = named::<&String>();
+            let tslice = named::<&str>();
+            let tbool = named::<bool>();
+            let tchar = named::<char>();
+            named::<Null>();
+            named::<TupleValue>();
+
+            let vi32 = named::<Vec<i32>>();
+            let vi64 = named::<Vec<i64>>();
+            let vu32 = named::<Vec<u32>>();
+            let vf32 = named::<Vec<f32>>();
+            let vf64 = named::<Vec<f64>>();
 
-            let vi32 = TypeId::of::<Vec<i32>>();
-            let vi64 = TypeId::of::<Vec<i64>>();
-            let vf64 = TypeId::of::<Vec<f64>>();
+            let vvi32 = named::<Vec<Vec<i32>>>();
+            let vvi64 = named::<Vec<Vec<i64>>>();
+            let vvf64 = named::<Vec<Vec<f64>>>();
 
-            let si32 = TypeId::of::<&[i32]>();
-            let si64 = TypeId::of::<&[i64]>();
-            let sf64 = TypeId::of::<&[f64]>();
+            let si32 = named::<&[i32]>();
+            let si64 = named::<&[i64]>();
+            let su32 = named::<&[u32]>();
+            let sf32 = named::<&[f32]>();
+            let sf64 = named::<&[f64]>();
+            let svi32 = named::<&[Vec<i32>]>();
+            let svi64 = named::<&[Vec<i64>]>();
+            let svf64 = named::<&[Vec<f64>]>();
 
             // i32 conversions
             add (ti32, ti32, Conversions::EQUIVALENT,
@@ -52,6 +136,8 @@ lazy_static! {
                 |x| { to::<i32,u64>(x) });
             add (ti32, tf64, 150,
                 |x| { to::<i32,f64>(x) });
+            add (ti32, tf32, 120,
+                |x| { Some(Box::new(raw::<i32>(x) as f32) as Box<dyn Any>) });
 
             // u32 conversions
             add (tu32, tu32, Conversions::EQUIVALENT,
@@ -75,7 +161,9 @@ lazy_static! {
             add (ti64, tu64, 150,
                 |x| { to::<i64,u64>(x) });
             add (ti64, tf64, 100,
-                |x| { to::<i64,i32>(x) });
+                |x| { Some(Box::new(raw::<i64>(x) as f64) as Box<dyn Any>) });
+            add (ti64, tf32, 80,
+                |x| { Some(Box::new(raw::<i64>(x) as f32) as Box<dyn Any>) });
 
             // u64 conversions
             add (tu64, tu64, Conversions::EQUIVALENT,
@@ -93,17 +181,87 @@ lazy_static! {
             add (tf64, tf64, Conversions::EQUIVALENT,
                 |x| { to::<f64,f64>(x) });
             add (tf64, ti32, 150,
-                |x| { Some(Box::new(raw::<f64>(x).round() as i32) as Box<dyn Any>) });
+                |x| { round_as::<i32>(x, |r| r as i32) });
             add (tf64, tu32, 100,
-                |x| { Some(Box::new(raw::<f64>(x).round() as u32) as Box<dyn Any>) });
+                |x| { round_as::<u32>(x, |r| r as u32) });
             add (tf64, tu64, 150,
-                |x| { Some(Box::new(raw::<f64>(x).round() as u64) as Box<dyn Any>) });
+                |x| { round_as::<u64>(x, |r| r as u64) });
             add (tf64, ti64, 150,
-                |x| { Some(Box::new(raw::<f64>(x).round() as i64) as Box<dyn Any>) });
+                |x| { round_as::<i64>(x, |r| r as i64) });
+            add (tf64, tf32, 150,
+                |x| { narrow_to_f32(x) });
+
+            // f32 conversions
+            add (tf32, tf32, Conversions::EQUIVALENT,
+                |x| { to::<f32,f32>(x) });
+            add (tf32, tf64, 150,
+                |x| { to::<f32,f64>(x) });
+
+            // small integer conversions (narrowing is rejected when out of range)
+            add (ti8, ti8, Conversions::EQUIVALENT,
+                |x| { to::<i8,i8>(x) });
+            add (ti16, ti16, Conversions::EQUIVALENT,
+                |x| { to::<i16,i16>(x) });
+            add (tu8, tu8, Conversions::EQUIVALENT,
+                |x| { to::<u8,u8>(x) });
+            add (tu16, tu16, Conversions::EQUIVALENT,
+                |x| { to::<u16,u16>(x) });
+
+            add (ti64, ti16, 100,
+                |x| { to::<i64,i16>(x) });
+            add (ti64, tu16, 90,
+                |x| { to::<i64,u16>(x) });
+            add (ti64, ti8, 90,
+                |x| { to::<i64,i8>(x) });
+            add (ti64, tu8, 80,
+                |x| { to::<i64,u8>(x) });
+
+            add (ti32, ti16, 100,
+                |x| { to::<i32,i16>(x) });
+            add (ti32, tu16, 90,
+                |x| { to::<i32,u16>(x) });
+            add (ti32, ti8, 90,
+                |x| { to::<i32,i8>(x) });
+            add (ti32, tu8, 80,
+                |x| { to::<i32,u8>(x) });
+
+            add (tf64, ti16, 90,
+                |x| { round_to::<i16>(x) });
+            add (tf64, tu16, 80,
+                |x| { round_to::<u16>(x) });
+            add (tf64, ti8, 80,
+                |x| { round_to::<i8>(x) });
+            add (tf64, tu8, 70,
+                |x| { round_to::<u8>(x) });
+
+            add (ti8, ti32, 150,
+                |x| { to::<i8,i32>(x) });
+            add (ti8, ti64, 150,
+                |x| { to::<i8,i64>(x) });
+            add (ti8, tf64, 150,
+                |x| { to::<i8,f64>(x) });
+            add (ti16, ti32, 150,
+                |x| { to::<i16,i32>(x) });
+            add (ti16, ti64, 150,
+                |x| { to::<i16,i64>(x) });
+            add (ti16, tf64, 150,
+                |x| { to::<i16,f64>(x) });
+            add (tu8, ti32, 150,
+                |x| { to::<u8,i32>(x) });
+            add (tu8, ti64, 150,
+                |x| { to::<u8,i64>(x) });
+            add (tu8, tf64, 150,
+                |x| { to::<u8,f64>(x) });
+            add (tu16, ti32, 150,
+                |x| { to::<u16,i32>(x) });
+            add (tu16, ti64, 150,
+                |x| { to::<u16,i64>(x) });
+            add (tu16, tf64, 150,
+                |x| { to::<u16,f64>(x) });
 
             // string conversions
             add (tstr, tstr, Conversions::EQUIVALENT,
-                |x| { Some(Box::new(raw::<&String>(x)) as Box<dyn Any>) });
+                |x| { x.downcast_ref::<String>().map(|s| Box::new(s.clone()) as Box<dyn Any>) });
             add (tstr, ti32, 50,
                 |x| { try_parse::<i32>(x) });
             add (tstr, tu32, 50,
@@ -114,22 +272,228 @@ lazy_static! {
                 |x| { try_parse::<u64>(x) });
             add (tstr, tf64, 50,
                 |x| { try_parse::<f64>(x) });
+            add (tstr, tbool, 50,
+                |x| { try_parse::<bool>(x) });
+
+            add (ti64, tstr, 50,
+                |x| { stringify::<i64>(x) });
+            add (tf64, tstr, 50,
+                |x| { stringify::<f64>(x) });
+            add (tbool, tstr, 50,
+                |x| { stringify::<bool>(x) });
+
+            // string slice conversions (the parameter borrows the converted String)
+            add (tstr, tslice, Conversions::EQUIVALENT,
+                |x| { x.downcast_ref::<String>().map(|s| Box::new(s.clone()) as Box<dyn Any>) });
+            add (trstr, tslice, Conversions::EQUIVALENT,
+                |x| { x.downcast_ref::<&String>().map(|s| Box::new((*s).clone()) as Box<dyn Any>) });
+
+            // bool conversions
+            add (tbool, tbool, Conversions::EQUIVALENT,
+                |x| { to::<bool,bool>(x) });
+            add (ti64, tbool, 50,
+                |x| { Some(Box::new(raw::<i64>(x) != 0) as Box<dyn Any>) });
+
+            // char conversions (a string converts only if of a single character)
+            add (tchar, tchar, Conversions::EQUIVALENT,
+                |x| { to::<char,char>(x) });
+            add (tstr, tchar, 50,
+                |x| { single_char(x) });
+            add (tchar, tstr, 50,
+                |x| { stringify::<char>(x) });
 
             // vector conversions
             add (vi32, si32, Conversions::EQUIVALENT,
                 |x| { convert_vec::<i32,i32>(x) });
-            add (vi32, sf64, Conversions::EQUIVALENT,
-                |x| { convert_vec::<i32,f64>(x) });
             add (vi64, si64, Conversions::EQUIVALENT,
                 |x| { convert_vec::<i64,i64>(x) });
+            add (vu32, su32, Conversions::EQUIVALENT,
+                |x| { convert_vec::<u32,u32>(x) });
             add (vf64, sf64, Conversions::EQUIVALENT,
                 |x| { convert_vec::<f64,f64>(x) });
+            add (vf32, sf32, Conversions::EQUIVALENT,
+                |x| { convert_vec::<f32,f32>(x) });
+            add (vvi32, svi32, Conversions::EQUIVALENT,
+                |x| { convert_vec::<Vec<i32>,Vec<i32>>(x) });
+            add (vvi64, svi64, Conversions::EQUIVALENT,
+                |x| { convert_vec::<Vec<i64>,Vec<i64>>(x) });
+            add (vvf64, svf64, Conversions::EQUIVALENT,
+                |x| { convert_vec::<Vec<f64>,Vec<f64>>(x) });
+            add (vvi32, svf64, 150,
+                |x| { convert_matrix::<i32,f64>(x) });
+            add (vvi32, vvf64, 150,
+                |x| { convert_matrix::<i32,f64>(x) });
+            add (vvi32, svi64, 150,
+                |x| { convert_matrix::<i32,i64>(x) });
+            add (vvi32, vvi64, 150,
+                |x| { convert_matrix::<i32,i64>(x) });
+            add (vvi64, svf64, 150,
+                |x| { Conversions::convert_elements::<Vec<i64>,Vec<f64>>(x) });
+            add (vvi64, vvf64, 150,
+                |x| { Conversions::convert_elements::<Vec<i64>,Vec<f64>>(x) });
+
+            // map conversions (maps with integer values)
+            add (named::<HashMap<String,i32>>(), named::<HashMap<String,f64>>(), 150,
+                |x| { convert_map::<i32,f64>(x) });
+            add (named::<HashMap<String,i32>>(), named::<HashMap<String,i64>>(), 150,
+                |x| { convert_map::<i32,i64>(x) });
+
+            // remaining conversions among numeric types (not given explicitly above)
+            let numerics = [ti8, ti16, ti32, ti64, tu8, tu16, tu32, tu64, tf32, tf64];
+            for conversion in numeric_family(&numerics) {
+                m.entry((conversion.from, conversion.to))
+                    .or_insert_with(|| Arc::new(Conversions { score: conversion.score, steps: conversion.steps.into_iter().map(step).collect(), via: None }));
+            }
+
+            // vector conversions among numeric element types, scored as the element conversion
+            add_vec_conversions::<i32, i64>(&mut m);
+            add_vec_conversions::<i32, u32>(&mut m);
+            add_vec_conversions::<i32, f32>(&mut m);
+            add_vec_conversions::<i32, f64>(&mut m);
+            add_vec_conversions::<i64, i32>(&mut m);
+            add_vec_conversions::<i64, u32>(&mut m);
+            add_vec_conversions::<i64, f32>(&mut m);
+            add_vec_conversions::<i64, f64>(&mut m);
+            add_vec_conversions::<u32, i32>(&mut m);
+            add_vec_conversions::<u32, i64>(&mut m);
+            add_vec_conversions::<u32, f32>(&mut m);
+            add_vec_conversions::<u32, f64>(&mut m);
+            add_vec_conversions::<f32, i32>(&mut m);
+            add_vec_conversions::<f32, i64>(&mut m);
+            add_vec_conversions::<f32, u32>(&mut m);
+            add_vec_conversions::<f32, f64>(&mut m);
+            add_vec_conversions::<f64, i32>(&mut m);
+            add_vec_conversions::<f64, i64>(&mut m);
+            add_vec_conversions::<f64, u32>(&mut m);
+            add_vec_conversions::<f64, f32>(&mut m);
+
+            // tuple literals to common tuples (others are registered by reflection as required)
+            let mut add_tuple = |to: TypeId, f: ConversionFn| {
+                m.insert((named::<TupleValue>(), to), Arc::new(Conversions { score: 150, steps: vec![step(f)], via: None }));
+            };
+            add_tuple (named::<(i32, i32)>(), tuple2::<i32, i32>);
+            add_tuple (named::<(i64, i64)>(), tuple2::<i64, i64>);
+            add_tuple (named::<(f64, f64)>(), tuple2::<f64, f64>);
+            add_tuple (named::<(String, f64)>(), tuple2::<String, f64>);
+            add_tuple (named::<(i32, i32, i32)>(), tuple3::<i32, i32, i32>);
+            add_tuple (named::<(f64, f64, f64)>(), tuple3::<f64, f64, f64>);
+
+            // lists of objects, as constructed from a list of ctors, cannot be copied, so are
+            // passed through to be borrowed as a slice
+            m.insert((named::<Vec<Box<dyn Any>>>(), named::<&[Box<dyn Any>]>()),
+                Arc::new(Conversions { score: Conversions::EQUIVALENT, steps: Vec::new(), via: None }));
         }
         rawmap
     };
 }
 
 
+/// Absent value, given by the literal `null`
+/// - converts to `None` for parameters of type `Option<T>` (see `Conversions::add_optional`)
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Null;
+
+
+/// Tuple of values, given by a tuple literal such as `(0.0, 1.0)`
+/// - converts to tuples of 2 or 3 elements, converting each element (see `Conversions::add_tuple`)
+pub struct TupleValue(pub Vec<Box<dyn Any>>);
+
+
+/// Argument as passed to a function, after conversion (see `TypeInfo::create_traced`)
+#[derive(Clone, Debug, PartialEq)]
+pub struct ConvertedArg {
+    /// name of the type of the argument as given
+    pub from: String,
+    /// name of the parameter type, as passed
+    pub to: String,
+    /// score of the conversion used (200 if passed without conversion)
+    pub score: i32,
+}
+
+impl ConvertedArg {
+
+    /// Indicate whether the argument was converted, rather than passed as is
+    pub fn is_converted (&self) -> bool {
+        self.from != self.to
+    }
+}
+
+
+/// Score of arguments versus the parameters of a function, in detail (see `Conversions::score_detail`)
+/// - arguments are scored by position, such that a near miss (say one argument not convertible,
+///   or one argument too many) is distinguished from a total mismatch
+/// - scores are ordered by fit: a full match above any partial match, then by the number of
+///   arguments matched, the difference in arity (smaller first), the total score of the
+///   arguments matched, the number passed without conversion, and the worst argument score
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Score {
+    /// number of parameters of the function
+    pub parameters: usize,
+    /// number of arguments given
+    pub arguments: usize,
+    /// number of arguments convertible to the parameter in their position
+    pub matched: usize,
+    /// number of arguments of the parameter type, passed without conversion
+    pub equivalent: usize,
+    /// total score of the arguments matched
+    pub total: i32,
+    /// score of the worst argument matched (that of an equivalent argument if none)
+    pub worst: i32,
+}
+
+
+impl Score {
+
+    /// Whether every argument matches a parameter, and every parameter an argument
+    pub fn is_fit (&self) -> bool {
+        self.parameters == self.arguments && self.matched == self.arguments
+    }
+
+    /// Number of arguments in position of a parameter, but not convertible to it
+    pub fn unmatched (&self) -> usize {
+        self.parameters.min(self.arguments) - self.matched
+    }
+
+    /// Number of arguments matched that require a conversion
+    pub fn converted (&self) -> usize {
+        self.matched - self.equivalent
+    }
+
+    /// Score as a single value (see `Conversions::score`)
+    /// - `-200` if the number of arguments differs from the number of parameters, `-100` if an
+    ///   argument is not convertible, otherwise the total score
+    pub fn value (&self) -> i32 {
+        if self.parameters != self.arguments {
+            -200
+        } else if self.matched < self.arguments {
+            -100
+        } else if self.parameters == 0 {
+            Conversions::EQUIVALENT
+        } else {
+            self.total
+        }
+    }
+
+    // key by which scores are ordered, higher is better
+    fn key (&self) -> (bool, usize, std::cmp::Reverse<usize>, i32, usize, i32) {
+        (self.is_fit(), self.matched, std::cmp::Reverse(self.parameters.abs_diff(self.arguments)), self.total, self.equivalent, self.worst)
+    }
+}
+
+
+impl PartialOrd for Score {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Score {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.key().cmp(&other.key())
+    }
+}
+
+
 /// Type conversion record
 /// - note that we require a score so can rank possible alternative conversions; A
 ///   score of 200 would mean that has full conversion weight and a lower score
@@ -137,19 +501,46 @@ lazy_static! {
 ///
 /// - for a group of arguments requiring conversion, the function with the highest score
 ///   relative to the supplied arguments would be selected
+/// - a conversion is normally a single conversion function, but may be a chain of conversions
+///   (see `Conversions::set_transitive`)
+/// - conversions are scored only when looked up, at call time, and never at registration, as
+///   registration (by `ctor` functions) happens in no particular order
 pub struct Conversions {
     score: i32,
-    convert: ConversionFn,
+    steps: Vec<Converter>,
+    // conversion between other types this conversion is derived from, if any, scored when
+    // looked up (see `add_derived`)
+    via: Option<(TypeId, TypeId)>,
 }
 
 impl Conversions {
     const EQUIVALENT: i32 = 200;
 
+    /// Candidates scoring within this margin of the best candidate are considered ambiguous
+    /// (see `find_best_matches`)
+    pub const AMBIGUITY_EPSILON: i32 = 0;
+
+    /// Score of the conversions generated for single-argument ctors (see `add_auto`)
+    pub const AUTO: i32 = 100;
+
+    /// Maximum number of conversions chained to convert between two types
+    pub const MAX_HOPS: usize = 3;
+
+    /// Score deducted for each conversion chained beyond the first
+    pub const HOP_PENALTY: i32 = 10;
+
     /// Indicate whether this conversion pairing is T -> T or equivalent
     pub fn is_equivalent (&self) -> bool {
         self.score == Conversions::EQUIVALENT
     }
 
+    /// Indicate whether the argument is passed through as is, rather than converted
+    /// - the reflected function accepts the argument in place of the parameter type, for example
+    ///   a `Vec<Box<dyn Any>>` for a `&[Box<dyn Any>]` parameter
+    pub fn is_pass_through (&self) -> bool {
+        self.steps.is_empty()
+    }
+
     /// Add a type conversion
     /// - note that we require a score so can rank possible alternative conversions; A
     ///   score of 200 would mean that has full conversion weight and a lower score
@@ -165,13 +556,275 @@ impl Conversions {
     /// * `convert`: conversion function, converting from `from` type to `to` type
     pub fn add (from: TypeId, to: TypeId, score: i32, convert: ConversionFn) {
         let conversion = Conversions {
-            score: score,
-            convert: convert };
+            score,
+            steps: vec![step(convert)],
+            via: None };
 
         // get writer handle to conversions
         let mut map = CONVERSIONS.write().unwrap();
         // add conversion
         map.insert ((from, to), Arc::new(conversion));
+        drop(map);
+
+        Conversions::changed();
+    }
+
+    /// Add a type conversion given by a closure
+    /// - unlike `add`, the conversion may capture state, for example a configured rounding mode
+    ///
+    /// # Arguments
+    /// * `from`: type to convert from
+    /// * `to`: type to convert to
+    /// * `score`: score for this conversion (see `add`)
+    /// * `convert`: conversion closure, converting from `from` type to `to` type
+    pub fn add_fn<F> (from: TypeId, to: TypeId, score: i32, convert: F) where F: Fn(&Box<dyn Any>) -> Option<Box<dyn Any>> + Send + Sync + 'static {
+        let mut map = CONVERSIONS.write().unwrap();
+        map.insert ((from, to), Arc::new(Conversions { score, steps: vec![Arc::new(convert)], via: None }));
+        drop(map);
+
+        Conversions::changed();
+    }
+
+    /// Add a conversion generated for a single-argument ctor (see `#[reflect_impl(auto_convert)]`),
+    /// unless a conversion between the types is registered
+    /// - scored below the conversions wrapping a value (such as `T` to `Option<T>`), and replaced
+    ///   by any conversion registered between the types with `add`, whether before or after, so
+    ///   that explicit conversions take precedence
+    ///
+    /// # Arguments
+    /// * `from`: type to convert from (the parameter type of the ctor)
+    /// * `to`: type to convert to (the type created by the ctor)
+    /// * `convert`: conversion closure, calling the ctor
+    pub fn add_auto<F> (from: TypeId, to: TypeId, convert: F) where F: Fn(&Box<dyn Any>) -> Option<Box<dyn Any>> + Send + Sync + 'static {
+        let mut map = CONVERSIONS.write().unwrap();
+        if let std::collections::hash_map::Entry::Vacant(entry) = map.entry((from, to)) {
+            entry.insert(Arc::new(Conversions { score: Conversions::AUTO, steps: vec![Arc::new(convert)], via: None }));
+        }
+        drop(map);
+
+        Conversions::changed();
+    }
+
+    /// Add a type conversion derived from the conversion between other types, for example that
+    /// of a list of literals converting its elements
+    /// - scored when looked up, as the conversion `via` (capped at `score`), such that the
+    ///   conversion `via` may be registered before or after this one
+    /// - absent (not found) for as long as there is no conversion `via`
+    ///
+    /// # Arguments
+    /// * `from`: type to convert from
+    /// * `to`: type to convert to
+    /// * `score`: maximum score for this conversion
+    /// * `via`: `(from, to)` types of the conversion this conversion is derived from
+    /// * `convert`: conversion function, converting from `from` type to `to` type
+    pub fn add_derived (from: TypeId, to: TypeId, score: i32, via: (TypeId, TypeId), convert: ConversionFn) {
+        let mut map = CONVERSIONS.write().unwrap();
+        map.insert ((from, to), Arc::new(Conversions { score, steps: vec![step(convert)], via: Some(via) }));
+        drop(map);
+
+        Conversions::changed();
+    }
+
+    /// Add a type conversion in both directions
+    /// - see `add`
+    ///
+    /// # Arguments
+    /// * `a`: first type
+    /// * `b`: second type
+    /// * `score`: score for both conversions
+    /// * `a_to_b`: conversion function, converting from `a` type to `b` type
+    /// * `b_to_a`: conversion function, converting from `b` type to `a` type
+    pub fn add_bidirectional (a: TypeId, b: TypeId, score: i32, a_to_b: ConversionFn, b_to_a: ConversionFn) {
+        let mut map = CONVERSIONS.write().unwrap();
+        map.insert ((a, b), Arc::new(Conversions { score, steps: vec![step(a_to_b)], via: None }));
+        map.insert ((b, a), Arc::new(Conversions { score, steps: vec![step(b_to_a)], via: None }));
+        drop(map);
+
+        Conversions::changed();
+    }
+
+    /// Add conversions to `Option<T>`
+    /// - `T` converts to `Some(T)` and `Null` to `None`
+    /// - registered by reflection for each parameter of type `Option<T>`
+    pub fn add_optional<T: 'static + Clone> () {
+        let mut map = CONVERSIONS.write().unwrap();
+        map.insert ((named::<T>(), named::<Option<T>>()), Arc::new(Conversions { score: 150, steps: vec![step(some::<T>)], via: None }));
+        map.insert ((named::<Null>(), named::<Option<T>>()), Arc::new(Conversions { score: 150, steps: vec![step(none::<T>)], via: None }));
+        drop(map);
+
+        Conversions::changed();
+    }
+
+    /// Add conversion of a tuple literal (`TupleValue`) to the 2-tuple `(A, B)`
+    /// - each element is converted to the corresponding type of the tuple
+    /// - registered by reflection for each parameter of a 2-tuple type
+    pub fn add_tuple<A: 'static + Clone, B: 'static + Clone> () {
+        let mut map = CONVERSIONS.write().unwrap();
+        map.insert ((named::<TupleValue>(), named::<(A, B)>()), Arc::new(Conversions { score: 150, steps: vec![step(tuple2::<A, B>)], via: None }));
+        drop(map);
+
+        Conversions::changed();
+    }
+
+    /// Add conversion of a tuple literal (`TupleValue`) to the 3-tuple `(A, B, C)`
+    /// - see `add_tuple`
+    pub fn add_tuple3<A: 'static + Clone, B: 'static + Clone, C: 'static + Clone> () {
+        let mut map = CONVERSIONS.write().unwrap();
+        map.insert ((named::<TupleValue>(), named::<(A, B, C)>()), Arc::new(Conversions { score: 150, steps: vec![step(tuple3::<A, B, C>)], via: None }));
+        drop(map);
+
+        Conversions::changed();
+    }
+
+    /// Add conversions of lists to the array `[T; N]`
+    /// - from a list of `T`, or a list of numeric literals (`i32`, `i64`, or `f64`) convertible
+    ///   element-wise to `T`, such that a list literal fills a fixed-size array
+    /// - lists of other than `N` elements fail to convert
+    /// - registered by reflection for each parameter of an array type
+    pub fn add_array<T: 'static + Clone, const N: usize> () {
+        Conversions::add_lists::<T> (named::<[T; N]>(), [array::<T, T, N>, array::<i32, T, N>, array::<i64, T, N>, array::<f64, T, N>]);
+    }
+
+    /// Add conversions of lists to the collection `C` of `T`, such as `VecDeque<T>` or `HashSet<T>`
+    /// - from a list of `T`, or a list of numeric literals (`i32`, `i64`, or `f64`) convertible
+    ///   element-wise to `T`, collecting the elements (de-duplicating them for a set)
+    /// - registered by reflection for each parameter of type `VecDeque<T>`, `HashSet<T>`, or
+    ///   `BTreeSet<T>`
+    pub fn add_collection<T: 'static + Clone, C: 'static + FromIterator<T>> () {
+        Conversions::add_lists::<T> (named::<C>(), [collect::<T, T, C>, collect::<i32, T, C>, collect::<i64, T, C>, collect::<f64, T, C>]);
+    }
+
+    /// Add conversions between each pair of a family of numeric types
+    /// - conversions preserving every value (widening, for example `i16` to `i64`) score 150,
+    ///   others (narrowing, for example `i64` to `u8`, or `i64` to `f32`) score 100
+    /// - values which cannot be represented in the target type (out of range, NaN or infinite)
+    ///   fail to convert
+    /// - conversions already registered between a pair are retained, such that the family fills
+    ///   in those missing
+    /// - only primitive numeric types (`i8` … `u64`, `f32`, `f64`) are supported; other types are
+    ///   ignored
+    ///
+    /// # Arguments
+    /// * `types`: numeric types of the family
+    ///
+    /// # Returns
+    /// * number of conversions added
+    pub fn add_numeric_family (types: &[TypeId]) -> usize {
+        let mut map = CONVERSIONS.write().unwrap();
+        let mut added = 0;
+        for conversion in numeric_family(types) {
+            if let std::collections::hash_map::Entry::Vacant(entry) = map.entry((conversion.from, conversion.to)) {
+                entry.insert(Arc::new(Conversions { score: conversion.score, steps: conversion.steps.into_iter().map(step).collect(), via: None }));
+                added += 1;
+            }
+        }
+        drop(map);
+
+        Conversions::changed();
+        added
+    }
+
+    /// Convert a value
+    ///
+    /// # Arguments
+    /// * `v`: boxed value of the type converted from
+    ///
+    /// # Returns
+    /// * boxed value of the type converted to, or None if the value could not be converted
+    pub fn convert (&self, v: &Box<dyn Any>) -> Option<Box<dyn Any>> {
+        let (first, rest) = self.steps.split_first()?;
+        let mut value = first(v)?;
+        for step in rest {
+            value = step(&value)?;
+        }
+        Some(value)
+    }
+
+    /// Number of changes made to the registered conversions
+    /// - conversion plans cached by types (see `TypeInfo::create`) are invalidated when this changes
+    pub fn generation () -> usize {
+        GENERATION.load(Ordering::Acquire)
+    }
+
+    /// Set whether numeric conversions are strict
+    /// - NaN and infinite `f64` values never convert to integer types
+    /// - by default, conversions of `f64` to `i32`, `u32`, `i64` and `u64` round and then saturate
+    ///   values out of range (for example `1e20` becomes `i32::MAX`), and `f64` to `f32` overflows
+    ///   to infinity
+    /// - when strict, such values fail to convert, surfacing as `ReflectError::ConversionFailed`
+    /// - conversions to smaller integers are range checked in either mode
+    ///
+    /// # Arguments
+    /// * `strict`: true for checked conversions, false for lossy conversions (the default)
+    pub fn set_strict (strict: bool) {
+        STRICT.store(strict, Ordering::Release);
+    }
+
+    /// Whether numeric conversions are strict (see `Conversions::set_strict`)
+    pub fn is_strict () -> bool {
+        STRICT.load(Ordering::Acquire)
+    }
+
+    /// Set whether conversions may be chained
+    /// - when there is no direct conversion between two types, a chain of up to `MAX_HOPS`
+    ///   registered conversions is searched for, for example `bool` -> `String` -> `f64`
+    /// - the score of a chain is the product of the scores of its conversions (relative to
+    ///   equivalence), less `HOP_PENALTY` per conversion beyond the first, such that a chain is
+    ///   never equivalent and longer chains rank lower
+    /// - the chain with the best score is used, and is cached until conversions change
+    ///
+    /// # Arguments
+    /// * `transitive`: true to chain conversions, false for direct conversions only (the default)
+    pub fn set_transitive (transitive: bool) {
+        TRANSITIVE.store(transitive, Ordering::Release);
+        Conversions::changed();
+    }
+
+    /// Whether conversions may be chained (see `Conversions::set_transitive`)
+    pub fn is_transitive () -> bool {
+        TRANSITIVE.load(Ordering::Acquire)
+    }
+
+    /// Set whether calls dispatch on exact matches only
+    /// - when exact, `TypeInfo::create`, `call`, and `callstatic` (and their variants) only call a
+    ///   ctor, method, or static function whose parameters match the arguments as given (see
+    ///   `Function::matching`), without converting any argument (such as `i64` to `f64`) or
+    ///   filling parameters with their defaults, failing with `ReflectError::NoExactMatch` otherwise
+    /// - equivalent types (such as `Vec<f64>` for `&[f64]`) match in either mode
+    /// - fuzzy matching is the default
+    ///
+    /// # Arguments
+    /// * `exact`: true for exact matches only, false to convert arguments as needed (the default)
+    pub fn set_exact_mode (exact: bool) {
+        EXACT.store(exact, Ordering::Release);
+        Conversions::changed();
+    }
+
+    /// Whether calls dispatch on exact matches only (see `Conversions::set_exact_mode`)
+    pub fn is_exact_mode () -> bool {
+        EXACT.load(Ordering::Acquire)
+    }
+
+    /// Register the readable name of a type
+    /// - used to describe types in errors and introspection, given that a `TypeId` is opaque
+    /// - the name is the type name without crate / module paths, for example `Vec<f64>`
+    pub fn add_type_name<T: 'static + ?Sized> () {
+        named::<T>();
+    }
+
+    /// Readable name of a type
+    ///
+    /// # Arguments
+    /// * `id`: type id
+    ///
+    /// # Returns
+    /// * name registered for the type or `"<unknown>"` if the type is not known
+    pub fn type_name (id: TypeId) -> String {
+        let names = TYPE_NAMES.read().unwrap();
+        match names.get(&id) {
+            Some(name) => name.clone(),
+            None => "<unknown>".to_string()
+        }
     }
 
     /// Find a conversion between `from` and `to`
@@ -183,8 +836,85 @@ impl Conversions {
     /// # Returns
     /// * conversion or None
     pub fn find (from: TypeId, to: TypeId) -> Option<Arc<Conversions>> {
+        let direct = {
+            let map = CONVERSIONS.read().unwrap();
+            map.get(&(from,to)).and_then(|conversion| Conversions::resolved(&map, conversion))
+        };
+        if direct.is_some() || from == to || !Conversions::is_transitive() {
+            return direct;
+        }
+
+        // otherwise find (or recall) chain of conversions
+        if let Some(chain) = CHAINS.read().unwrap().get(&(from,to)) {
+            return chain.clone();
+        }
+        let chain = Conversions::find_chain(from, to).map(Arc::new);
+        CHAINS.write().unwrap().insert((from,to), chain.clone());
+        chain
+    }
+
+    /// Registered conversions
+    /// - chained conversions (see `set_transitive`) are not listed
+    /// - derived conversions (see `add_derived`) are listed with their current score, or not
+    ///   listed while the conversion they derive from is missing
+    ///
+    /// # Returns
+    /// * `(from, to, score)` for each registered conversion, sorted by type ids
+    pub fn list () -> Vec<(TypeId, TypeId, i32)> {
         let map = CONVERSIONS.read().unwrap();
-        map.get(&(from,to)).cloned()
+        let mut conversions: Vec<(TypeId, TypeId, i32)> = map.iter()
+            .filter_map(|((from, to), conversion)| {
+                Conversions::resolved(&map, conversion).map(|conversion| (*from, *to, conversion.score))
+            })
+            .collect();
+        conversions.sort();
+        conversions
+    }
+
+    /// Registered conversions as a Graphviz digraph, for visualizing which types convert to which
+    /// - an edge per conversion listed by `list`, from and to the readable type names, labelled
+    ///   with its score, for example `"i32" -> "f64" [label="100"];`
+    /// - edges are sorted by type names, such that the output is stable
+    ///
+    /// # Returns
+    /// * DOT source of the graph
+    pub fn to_dot () -> String {
+        let mut edges: Vec<(String, String, i32)> = Conversions::list().into_iter()
+            .map(|(from, to, score)| (Conversions::type_name(from), Conversions::type_name(to), score))
+            .collect();
+        edges.sort();
+
+        let mut dot = String::from("digraph conversions {\n");
+        for (from, to, score) in edges {
+            dot.push_str(&format!("    \"{}\" -> \"{}\" [label=\"{}\"];\n", dot_escape(&from), dot_escape(&to), score));
+        }
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// Determine whether a value of type `from` can be passed as type `to`
+    ///
+    /// # Arguments
+    /// * `from`: type to convert from
+    /// * `to`: type to convert to
+    ///
+    /// # Returns
+    /// * true if the types are the same or there is a conversion
+    pub fn can_convert (from: TypeId, to: TypeId) -> bool {
+        Conversions::score_of(from, to).is_some()
+    }
+
+    /// Score of converting a value of type `from` to type `to`
+    ///
+    /// # Arguments
+    /// * `from`: type to convert from
+    /// * `to`: type to convert to
+    ///
+    /// # Returns
+    /// * score of the conversion (200 if the types are the same or equivalent), or None if there
+    ///   is no conversion (or, with the `policy` feature, the policy installed rejects it)
+    pub fn score_of (from: TypeId, to: TypeId) -> Option<i32> {
+        Conversions::arg_score(from, to)
     }
 
     /// Score a given argument vector versus target parameter types
@@ -196,25 +926,38 @@ impl Conversions {
     ///
     /// # Returns
     /// * score for given argument set.  Higher positive value -> better fit and lower implies
-    ///   worse fit.  A negative score implies no fit at all
+    ///   worse fit.  A negative score implies no fit at all.  No arguments for no parameters is a
+    ///   perfect fit (scoring as equivalent)
     pub fn score (target: &[TypeId], args: &[Box<dyn Any>]) -> i32 {
-        // if # of args and parameters don't match punt
-        if target.len() != args.len() {
-            return -200;
-        }
+        Self::score_detail (target, args).value()
+    }
 
-        // otherwise score parameters
-        let mut score = 0;
+    /// Score a given argument vector versus target parameter types, in detail
+    /// - unlike `score`, arguments are scored even if some argument is not convertible or the
+    ///   number of arguments differs, such that the nearest of the candidates failing to match
+    ///   can be found (see `find_nearest`)
+    ///
+    /// # Arguments
+    /// * `target`: function parameter types
+    /// * `args`: incoming argument vector for function
+    ///
+    /// # Returns
+    /// * score of the arguments, by position
+    pub fn score_detail (target: &[TypeId], args: &[Box<dyn Any>]) -> Score {
+        let mut score = Score {
+            parameters: target.len(),
+            arguments: args.len(),
+            matched: 0,
+            equivalent: 0,
+            total: 0,
+            worst: Conversions::EQUIVALENT
+        };
         for (to_arg, from_arg) in target.iter().zip(args) {
-            let arg_type = (**from_arg).type_id();
-            match Conversions::find(arg_type, *to_arg) {
-                Some(conversion) => {
-                    score += conversion.score;
-                }
-                None => {
-                    score = -100;
-                    break
-                }
+            if let Some(arg_score) = Self::arg_score ((**from_arg).type_id(), *to_arg) {
+                score.matched += 1;
+                score.equivalent += usize::from(arg_score == Conversions::EQUIVALENT);
+                score.total += arg_score;
+                score.worst = score.worst.min(arg_score);
             }
         }
         score
@@ -225,6 +968,16 @@ impl Conversions {
     ///   those candidates with the appropriate name or for ctors, where the name is not
     ///   important
     ///
+    /// # Resolution
+    /// Candidates are ranked by, in order of precedence:
+    /// 1. total score of the argument conversions (see `score`), higher first
+    /// 2. number of arguments passed without conversion (equivalent), more first
+    /// 3. score of the worst argument conversion, higher first (prefer the least lossy)
+    /// 4. signature (argument type names), in lexical order
+    ///
+    /// As ctors and methods are registered in an unspecified order, the signature serves as a
+    /// final deterministic tie-breaker
+    ///
     /// # Arguments
     /// * `candidates`: list of candidate functions (ctors, methods, static methods)
     /// * `args`: argument list
@@ -232,64 +985,361 @@ impl Conversions {
     /// # Returns
     /// * best function or None if no convertible matches
     pub fn find_best_match<'a, T: ?Sized + Function> (candidates: &'a [Box<T>], args: &[Box<dyn Any>]) -> Option<&'a T> {
-        // nothing to do if no candidates provided
-        if candidates.len() == 0 {
-            return None
-        }
-
-        let mut best_candidate = &candidates[0];
-        let mut best_score = -100;
+        let mut best: Option<(&'a T, (i32, usize, i32))> = None;
+        let arg_types: Vec<TypeId> = args.iter().map(|arg| (**arg).type_id()).collect();
 
         for candidate in candidates {
-            let cargs: &[TypeId] = candidate.arg_types();
-
-            // evaluate score of given arguments relative to argument types of candidate
-            let score = Self::score(cargs, args);
+            // evaluate rank of given arguments relative to argument types of candidate
+            let rank = match Self::rank (candidate.arg_types(), &arg_types) {
+                Some(rank) if rank.0 > 0 => rank,
+                _ => continue
+            };
 
-            if score > best_score {
-                best_score = score;
-                best_candidate = candidate;
+            let better = match &best {
+                None => true,
+                Some((best_candidate, best_rank)) => rank > *best_rank ||
+                    (rank == *best_rank && candidate.arg_type_names() < best_candidate.arg_type_names())
+            };
+            if better {
+                best = Some((candidate.as_ref(), rank));
             }
         }
 
-        return if best_score > 0 {
-            Some(best_candidate)
-        } else {
-            None
+        best.map(|(candidate, _)| candidate)
+    }
+
+    /// Find the candidate nearest to matching the arguments, whether or not it matches
+    /// - for diagnostics where no candidate matches, for example reporting the near miss of a
+    ///   single argument of the wrong type
+    /// - candidates are ranked by `score_detail`, then by signature, in lexical order
+    ///
+    /// # Arguments
+    /// * `candidates`: list of candidate functions (ctors, methods, static methods)
+    /// * `args`: argument list
+    ///
+    /// # Returns
+    /// * nearest function with its score, or None if there are no candidates
+    pub fn find_nearest<'a, T: ?Sized + Function> (candidates: &'a [Box<T>], args: &[Box<dyn Any>]) -> Option<(&'a T, Score)> {
+        let mut nearest: Option<(&'a T, Score)> = None;
+        for candidate in candidates {
+            let score = Self::score_detail (candidate.arg_types(), args);
+            let nearer = match &nearest {
+                None => true,
+                Some((best, best_score)) => score > *best_score ||
+                    (score == *best_score && candidate.arg_type_names() < best.arg_type_names())
+            };
+            if nearer {
+                nearest = Some((candidate.as_ref(), score));
+            }
         }
+        nearest
+    }
+
+    /// Resolve the best matched function for arguments, converting the arguments to its parameters
+    /// - the candidate is chosen, and arguments converted, as when creating or calling through
+    ///   `TypeInfo`, including exact dispatch (see `set_exact_mode`) and parameters taking their
+    ///   default value, though without caching the conversions to apply
+    /// - arguments are taken by value, as arguments passed without conversion (reflected
+    ///   objects, for example) cannot be copied
+    /// ```ignore
+    ///    let (ctor, argv) = Conversions::resolve(&itype.constructors, args)?.ok_or(...)?;
+    ///    let refs: Vec<&dyn Any> = argv.iter().map(|arg| arg.as_ref()).collect();
+    ///    let obj = ctor.create(&refs)?;
+    /// ```
+    ///
+    /// # Arguments
+    /// * `candidates`: list of candidate functions (ctors, methods, static methods)
+    /// * `args`: argument list
+    ///
+    /// # Returns
+    /// * best function with the arguments converted to its parameters (followed by the default
+    ///   values of parameters without an argument), `None` if no convertible matches, or an
+    ///   error converting an argument or evaluating a default value
+    pub fn resolve<T: ?Sized + Function> (candidates: &[Box<T>], args: Vec<Box<dyn Any>>) -> Result<Option<ResolvedArgv<'_, T>>, ReflectError> {
+        let (best, produced) = match resolve (None, candidates, &args, None)? {
+            Some((best, arguments)) => (best, produced(arguments)),
+            None => return Ok(None)
+        };
+        Ok(Some((best, converted_owned(args, produced))))
+    }
+
+    /// Find all candidates matching arguments equally well
+    /// - candidates whose total score is within `epsilon` of the best score, best first
+    /// - more than one match implies the best match is ambiguous
+    ///
+    /// # Arguments
+    /// * `candidates`: list of candidate functions (ctors, methods, static methods)
+    /// * `args`: argument list
+    /// * `epsilon`: margin below the best score within which candidates are retained
+    ///
+    /// # Returns
+    /// * best functions or an empty vector if no convertible matches
+    pub fn find_best_matches<'a, T: ?Sized + Function> (candidates: &'a [Box<T>], args: &[Box<dyn Any>], epsilon: i32) -> Vec<&'a T> {
+        let mut scored: Vec<(&'a T, i32)> = candidates.iter()
+            .map(|c| (c.as_ref(), Self::score (c.arg_types(), args)))
+            .filter(|(_, score)| *score > 0)
+            .collect();
+        scored.sort_by_key(|(_, score)| std::cmp::Reverse(*score));
+
+        let best = match scored.first() {
+            Some((_, score)) => *score,
+            None => return Vec::new()
+        };
+        scored.into_iter()
+            .take_while(|(_, score)| *score >= best - epsilon)
+            .map(|(candidate, _)| candidate)
+            .collect()
     }
 
     /// Convert incoming argument vector to be compatible with target function arguments
+    /// - arguments already of the parameter type are not converted (reflected objects, for
+    ///   example, cannot be copied); these are returned as `None` and should be passed through
+    ///   from the original argument vector (see `Conversions::argv`)
+    ///
+    /// # Arguments
+    /// * `parameters`: target function parameter types
+    /// * `args`: incoming argv to be converted
+    ///
+    /// # Returns
+    /// * converted arguments or the failed conversion
+    pub fn convert_argv (parameters: &[TypeId], args: &[Box<dyn Any>]) -> Result<Vec<Option<Box<dyn Any>>>, ReflectError> {
+        Conversions::convert_argv_traced(parameters, args).map(|(newargs, _)| newargs)
+    }
+
+    /// Conversion of each argument as passed to a function, given the arguments resolved for it
+    /// (see `TypeInfo::create_traced`)
+    /// - parameters beyond the arguments, taking their default value, are traced as passed as is
+    ///
+    /// # Arguments
+    /// * `parameters`: target function parameter types
+    /// * `args`: arguments given
+    ///
+    /// # Returns
+    /// * the conversion of each argument, as recorded by `convert_argv_traced`
+    pub(crate) fn trace (parameters: &[TypeId], args: &[Box<dyn Any>]) -> Vec<ConvertedArg> {
+        parameters.iter().enumerate().map(|(index, to_type)| {
+            let arg_type = args.get(index).map_or(*to_type, |arg| (**arg).type_id());
+            let score = match arg_type == *to_type {
+                true => Conversions::EQUIVALENT,
+                false => Conversions::find(arg_type, *to_type).map_or(0, |conversion| conversion.score)
+            };
+            ConvertedArg { from: Conversions::type_name(arg_type), to: Conversions::type_name(*to_type), score }
+        }).collect()
+    }
+
+    /// Convert incoming argument vector to be compatible with target function arguments,
+    /// recording the conversion of each argument
+    /// - as `convert_argv`
     ///
     /// # Arguments
     /// * `parameters`: target function parameter types
     /// * `args`: incoming argv to be converted
     ///
     /// # Returns
-    /// * converted arguments or None if failed
-    pub fn convert_argv (parameters: &[TypeId], args: &[Box<dyn Any>]) -> Option<Vec<Box<dyn Any>>> {
+    /// * converted arguments and the conversion of each argument, or the failed conversion
+    pub fn convert_argv_traced (parameters: &[TypeId], args: &[Box<dyn Any>]) -> Result<TracedArgv, ReflectError> {
         // check target args vs provided args
         if parameters.len() != args.len() {
-            return None;
+            return Err(ReflectError::InvalidArgument { index: parameters.len().min(args.len()) });
         }
 
-        let mut newargs: Vec<Box<dyn Any>> = Vec::new();
+        let mut newargs: Vec<Option<Box<dyn Any>>> = Vec::new();
+        let mut trace: Vec<ConvertedArg> = Vec::new();
         for (to_type, from_arg) in parameters.iter().zip(args) {
-            match Conversions::find(from_arg.type_id(), *to_type) {
+            let arg_type = (**from_arg).type_id();
+            let mut traced = |score: i32| trace.push(ConvertedArg {
+                from: Conversions::type_name(arg_type),
+                to: Conversions::type_name(*to_type),
+                score
+            });
+            if arg_type == *to_type {
+                traced(Conversions::EQUIVALENT);
+                newargs.push(None);
+                continue;
+            }
+
+            let converted = match Conversions::find(arg_type, *to_type) {
+                Some(conversion) if conversion.is_pass_through() => {
+                    traced(conversion.score);
+                    newargs.push(None);
+                    continue;
+                }
                 Some(conversion) => {
-                    let cfun = conversion.convert;
-                    match cfun(from_arg) {
-                        Some(v) => newargs.push(v),
-                        None => return None
-                    }
+                    traced(conversion.score);
+                    conversion.convert(from_arg)
                 }
-                None => {
-                    return None
+                None => None
+            };
+            match converted {
+                Some(v) => newargs.push(Some(v)),
+                None => return Err(ReflectError::ConversionFailed {
+                    from: Conversions::type_name(arg_type),
+                    to: Conversions::type_name(*to_type)
+                })
+            }
+        }
+
+        Ok((newargs, trace))
+    }
+
+    /// Convert a `Vec<T>` element-wise to a `Vec<R>` using the registered `T` -> `R` conversion
+    /// - used to register collection conversions for reflected types, for example from
+    ///   `Vec<String>` to `&[MyEnum]`
+    ///
+    /// # Arguments
+    /// * `v`: boxed `Vec<T>`
+    ///
+    /// # Returns
+    /// * boxed `Vec<R>` or None if any element fails to convert
+    pub fn convert_elements<T, R> (v: &Box<dyn Any>) -> Option<Box<dyn Any>> where T: 'static + Clone, R: 'static {
+        let vec = v.downcast_ref::<Vec<T>>()?;
+        let conversion = Conversions::find(TypeId::of::<T>(), TypeId::of::<R>())?;
+
+        let mut converted = Vec::<R>::with_capacity(vec.len());
+        for item in vec {
+            let from = Box::new(item.clone()) as Box<dyn Any>;
+            let to = conversion.convert(&from)?;
+            converted.push(*to.downcast::<R>().ok()?);
+        }
+
+        Some(Box::new(converted) as Box<dyn Any>)
+    }
+
+    /// Build the argument view handed to a reflected function
+    /// - takes the converted argument where present, otherwise passes through the original
+    ///
+    /// # Arguments
+    /// * `args`: incoming argv
+    /// * `converted`: converted arguments as produced by `convert_argv` (or empty if no
+    ///   conversion was required)
+    ///
+    /// # Returns
+    /// * argument references to pass to `Constructor::create`, `Method::call`, etc.
+    pub fn argv<'a> (args: &'a [Box<dyn Any>], converted: &'a [Option<Box<dyn Any>>]) -> Vec<&'a dyn Any> {
+        args.iter().enumerate().map(|(i, arg)| {
+            match converted.get(i) {
+                Some(Some(v)) => v.as_ref(),
+                _ => arg.as_ref()
+            }
+        }).collect()
+    }
+
+    // add conversions of a list of `T`, and of lists of numeric literals (`i32`, `i64`, `f64`),
+    // to the given type, those of literals derived from the conversion of their elements to `T`
+    // - `converts` are the conversions from `Vec<T>`, `Vec<i32>`, `Vec<i64>`, and `Vec<f64>`
+    fn add_lists<T: 'static> (to: TypeId, converts: [ConversionFn; 4]) {
+        let [same, from_i32, from_i64, from_f64] = converts;
+        let literals = [
+            (named::<Vec<i32>>(), TypeId::of::<i32>(), from_i32),
+            (named::<Vec<i64>>(), TypeId::of::<i64>(), from_i64),
+            (named::<Vec<f64>>(), TypeId::of::<f64>(), from_f64),
+        ];
+
+        let mut map = CONVERSIONS.write().unwrap();
+        for (from, element, convert) in literals {
+            if element != TypeId::of::<T>() {
+                let via = Some((element, TypeId::of::<T>()));
+                map.insert ((from, to), Arc::new(Conversions { score: 150, steps: vec![step(convert)], via }));
+            }
+        }
+        map.insert ((named::<Vec<T>>(), to), Arc::new(Conversions { score: 150, steps: vec![step(same)], via: None }));
+        drop(map);
+
+        Conversions::changed();
+    }
+
+    // note a change to the conversions, invalidating cached chains and conversion plans
+    fn changed () {
+        CHAINS.write().unwrap().clear();
+        GENERATION.fetch_add(1, Ordering::Release);
+    }
+
+    // the conversion as scored now, or None if derived from a conversion which is missing
+    // - derived conversions (see `add_derived`) are scored as the direct conversion they derive
+    //   from, as registered at the time of the lookup
+    fn resolved (map: &HashMap<(TypeId,TypeId),Arc<Conversions>>, conversion: &Arc<Conversions>) -> Option<Arc<Conversions>> {
+        let (from, to) = match conversion.via {
+            None => return Some(conversion.clone()),
+            Some(via) => via
+        };
+        let score = match from == to {
+            true => Conversions::EQUIVALENT,
+            false => Conversions::resolved(map, map.get(&(from, to))?)?.score
+        };
+        Some(Arc::new(Conversions { score: score.min(conversion.score), steps: conversion.steps.clone(), via: None }))
+    }
+
+    // find the best scoring chain of at most `MAX_HOPS` conversions from `from` to `to`
+    // - as a conversion scores at most as equivalent, extending a chain never improves its score,
+    //   so chains are extended a hop at a time from the best chain to each type; a type already
+    //   reached by a chain scoring as well is not revisited, which also guards against cycles
+    fn find_chain (from: TypeId, to: TypeId) -> Option<Conversions> {
+        let map = CONVERSIONS.read().unwrap();
+
+        // best chain to each type reached, as (score, conversion steps)
+        let mut best: HashMap<TypeId, (i32, Vec<Converter>)> = HashMap::new();
+        best.insert(from, (Conversions::EQUIVALENT, Vec::new()));
+        let mut frontier = vec![from];
+
+        for _ in 0..Conversions::MAX_HOPS {
+            let mut next = Vec::new();
+            for node in frontier {
+                let (score, steps) = best[&node].clone();
+                let penalty = if steps.is_empty() { 0 } else { Conversions::HOP_PENALTY };
+
+                for ((_, t2), conversion) in map.iter().filter(|((t1, _), _)| *t1 == node) {
+                    let Some(conversion) = Conversions::resolved(&map, conversion) else {
+                        continue;
+                    };
+                    let extended = score * conversion.score / Conversions::EQUIVALENT - penalty;
+                    if extended <= 0 || best.get(t2).is_some_and(|(s, _)| *s >= extended) {
+                        continue;
+                    }
+
+                    let mut chain = steps.clone();
+                    chain.extend(conversion.steps.iter().cloned());
+                    best.insert(*t2, (extended, chain));
+                    if *t2 != to {
+                        next.push(*t2);
+                    }
                 }
             }
+            frontier = next;
+        }
+
+        best.remove(&to).map(|(score, steps)| Conversions { score, steps, via: None })
+    }
+
+    // score of converting a single argument, or None if not convertible
+    fn arg_score (from: TypeId, to: TypeId) -> Option<i32> {
+        // arguments already of the parameter type pass through as-is
+        if from == to {
+            return Some(Conversions::EQUIVALENT);
         }
+        let score = Conversions::find(from, to).map(|conversion| conversion.score);
+        #[cfg(feature = "policy")]
+        let score = crate::core::policy::policy_score(from, to, score);
+        score
+    }
 
-        Some(newargs)
+    // rank of a candidate as (total score, # of equivalent arguments, worst argument score),
+    // given the types of the arguments, or None if the arguments are not convertible
+    pub(crate) fn rank (target: &[TypeId], args: &[TypeId]) -> Option<(i32, usize, i32)> {
+        if target.len() != args.len() {
+            return None;
+        }
+        if target.is_empty() {
+            return Some((Conversions::EQUIVALENT, 0, Conversions::EQUIVALENT));
+        }
+
+        let mut rank = (0, 0, Conversions::EQUIVALENT);
+        for (to_arg, from_arg) in target.iter().zip(args) {
+            let arg_score = Self::arg_score (*from_arg, *to_arg)?;
+            rank.0 += arg_score;
+            rank.1 += usize::from(arg_score == Conversions::EQUIVALENT);
+            rank.2 = rank.2.min(arg_score);
+        }
+        Some(rank)
     }
 }
 
@@ -314,8 +1364,45 @@ where
     })
 }
 
+// Register conversions of `Vec<T>` to `&[R]` and to `Vec<R>`, converting element-wise
+// - scored as the conversion of `T` to `R`, such that lossy element conversions rank lower
+fn add_vec_conversions<T, R> (m: &mut HashMap<(TypeId,TypeId),Arc<Conversions>>) where T: 'static + Clone, R: 'static {
+    let score = match m.get(&(TypeId::of::<T>(), TypeId::of::<R>())) {
+        Some(conversion) => conversion.score,
+        None => return
+    };
+
+    let convert: ConversionFn = Conversions::convert_elements::<T, R>;
+    m.insert((named::<Vec<T>>(), named::<&[R]>()), Arc::new(Conversions { score, steps: vec![step(convert)], via: None }));
+    m.insert((named::<Vec<T>>(), named::<Vec<R>>()), Arc::new(Conversions { score, steps: vec![step(convert)], via: None }));
+}
+
+// Copy map from value type T to value type R, converting each value
+fn convert_map<T, R> (boxed: &Box<dyn Any>) -> Option<Box<dyn Any>> where T: 'static + Clone, R: 'static + Clone {
+    let map = boxed.downcast_ref::<HashMap<String, T>>()?;
+    let mut converted = HashMap::<String, R>::with_capacity(map.len());
+    for (key, value) in map {
+        converted.insert(key.clone(), element::<R>(&(Box::new(value.clone()) as Box<dyn Any>))?);
+    }
+    Some(Box::new(converted) as Box<dyn Any>)
+}
+
+// Copy vector of vectors (matrix) from element type T to element type R
+fn convert_matrix<T, R>(boxed: &Box<dyn Any>) -> Option<Box<dyn Any>>
+where
+    T: 'static + Clone,
+    R: 'static + TryFrom<T>,
+{
+    boxed.downcast_ref::<Vec<Vec<T>>>().map(|matrix| {
+        let converted: Vec<Vec<R>> = matrix.iter()
+            .map(|row| row.iter().filter_map(|item| R::try_from(item.clone()).ok()).collect())
+            .collect();
+        Box::new(converted) as Box<dyn Any>
+    })
+}
+
 // Conversion for boxed primitive types to another type
-fn to<T: 'static,R: 'static> (v: &Box<dyn Any>) -> Option<Box<dyn Any>>  where T: Copy, R: TryFrom<T> {
+fn to<T,R> (v: &Box<dyn Any>) -> Option<Box<dyn Any>>  where T: 'static + Copy, R: 'static + TryFrom<T> {
     let r: Option<R> = v.downcast_ref::<T>().and_then(|value| { (*value).try_into().ok() });
     match r {
         Some(x) => Some(Box::new(x) as Box<dyn Any>),
@@ -324,15 +1411,226 @@ fn to<T: 'static,R: 'static> (v: &Box<dyn Any>) -> Option<Box<dyn Any>>  where T
 }
 
 
+// Register readable name for type, returning its type id
+fn named<T: 'static + ?Sized> () -> TypeId {
+    let id = TypeId::of::<T>();
+    let mut names = TYPE_NAMES.write().unwrap();
+    names.entry(id).or_insert_with(|| readable_type_name(type_name::<T>()));
+    id
+}
+
+// Escape a type name for a quoted DOT identifier
+fn dot_escape (name: &str) -> String {
+    name.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+// Strip crate and module paths (and erased lifetimes) from a type name, for example
+// `alloc::vec::Vec<alloc::string::String>` -> `Vec<String>`
+pub(crate) fn readable_type_name (name: &str) -> String {
+    let mut readable = String::with_capacity(name.len());
+    let mut segment = String::new();
+
+    for ch in name.chars() {
+        if ch.is_alphanumeric() || ch == '_' || ch == ':' {
+            segment.push(ch);
+        } else {
+            readable.push_str(segment.rsplit("::").next().unwrap_or(""));
+            segment.clear();
+            readable.push(ch);
+        }
+    }
+    readable.push_str(segment.rsplit("::").next().unwrap_or(""));
+
+    // lifetimes are erased (given as `'_`), so are dropped, for example `Tokens<'_>` -> `Tokens`
+    strip_lifetimes(&readable)
+}
+
+// Drop erased lifetimes (`'_`) from the generic arguments of a type name, dropping the brackets
+// if no argument remains, for example `Pair<'_, '_, f64>` -> `Pair<f64>` and `Foo<'_, '_>` -> `Foo`
+fn strip_lifetimes (name: &str) -> String {
+    let open = match name.find('<') {
+        Some(open) => open,
+        None => return name.to_string()
+    };
+    let close = match closing_bracket(name, open) {
+        Some(close) => close,
+        None => return name.to_string()
+    };
+
+    let args: Vec<String> = generic_arguments(&name[open + 1..close]).into_iter()
+        .filter(|arg| *arg != "'_")
+        .map(strip_lifetimes)
+        .collect();
+    let rest = strip_lifetimes(&name[close + 1..]);
+    if args.is_empty() {
+        format!("{}{}", &name[..open], rest)
+    } else {
+        format!("{}<{}>{}", &name[..open], args.join(", "), rest)
+    }
+}
+
+// Position of the `>` closing the `<` at `open` (not counting the `>` of `->`)
+fn closing_bracket (name: &str, open: usize) -> Option<usize> {
+    let mut depth = 0;
+    let mut prior = ' ';
+    for (i, ch) in name[open..].char_indices() {
+        match ch {
+            '<' => depth += 1,
+            '>' if prior != '-' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(open + i);
+                }
+            }
+            _ => ()
+        }
+        prior = ch;
+    }
+    None
+}
+
+// Generic arguments separated by top-level commas, trimmed, for example `'_, Vec<(i32, f64)>`
+fn generic_arguments (args: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut depth = 0;
+    let mut start = 0;
+    let mut prior = ' ';
+    for (i, ch) in args.char_indices() {
+        match ch {
+            '<' | '(' | '[' => depth += 1,
+            '>' if prior != '-' => depth -= 1,
+            ')' | ']' => depth -= 1,
+            ',' if depth == 0 => {
+                parts.push(args[start..i].trim());
+                start = i + 1;
+            }
+            _ => ()
+        }
+        prior = ch;
+    }
+    parts.push(args[start..].trim());
+    parts
+}
+
+
+// Round a boxed f64 to an integer type, rejecting NaN, infinite and values out of range for the type
+fn round_to<R> (v: &Box<dyn Any>) -> Option<Box<dyn Any>>  where R: 'static + TryFrom<i64> {
+    let rounded = raw::<f64>(v).round();
+    if !rounded.is_finite() {
+        return None;
+    }
+    R::try_from(rounded as i64).ok().map(|x| Box::new(x) as Box<dyn Any>)
+}
+
+
+// Round a boxed f64 to an integer type
+// - NaN and infinite values are rejected
+// - out of range values are cast by `lossy` (saturating), unless strict, in which case they are
+//   rejected
+fn round_as<R> (v: &Box<dyn Any>, lossy: fn(f64) -> R) -> Option<Box<dyn Any>>  where R: 'static + TryFrom<i128> {
+    let rounded = raw::<f64>(v).round();
+    if !rounded.is_finite() {
+        None
+    } else if Conversions::is_strict() {
+        R::try_from(rounded as i128).ok().map(|x| Box::new(x) as Box<dyn Any>)
+    } else {
+        Some(Box::new(lossy(rounded)) as Box<dyn Any>)
+    }
+}
+
+
+// Narrow a boxed f64 to f32
+// - values beyond the range of f32 become infinite, unless strict, in which case they are rejected
+fn narrow_to_f32 (v: &Box<dyn Any>) -> Option<Box<dyn Any>> {
+    let x = raw::<f64>(v);
+    let narrowed = x as f32;
+    if Conversions::is_strict() && x.is_finite() && narrowed.is_infinite() {
+        None
+    } else {
+        Some(Box::new(narrowed) as Box<dyn Any>)
+    }
+}
+
+
 // Get raw underlying value
-fn raw<T: 'static> (v: &Box<dyn Any>) -> T  where T: Copy {
+fn raw<T> (v: &Box<dyn Any>) -> T  where T: 'static + Copy {
     *v.downcast_ref::<T>().unwrap()
 }
 
+// Convert an element of a tuple literal to `T`
+fn element<T: 'static + Clone> (v: &Box<dyn Any>) -> Option<T> {
+    if let Some(x) = v.downcast_ref::<T>() {
+        return Some(x.clone());
+    }
+    let converted = Conversions::find((**v).type_id(), TypeId::of::<T>())?.convert(v)?;
+    converted.downcast::<T>().ok().map(|x| *x)
+}
+
+// Convert a boxed `Vec<S>` of `N` elements to the array `[T; N]`, converting each element
+fn array<S: 'static + Clone, T: 'static + Clone, const N: usize> (v: &Box<dyn Any>) -> Option<Box<dyn Any>> {
+    let vec = v.downcast_ref::<Vec<S>>()?;
+    if vec.len() != N {
+        return None;
+    }
+    let elements: Vec<T> = vec.iter()
+        .map(|x| element::<T>(&(Box::new(x.clone()) as Box<dyn Any>)))
+        .collect::<Option<Vec<T>>>()?;
+    let array: [T; N] = elements.try_into().ok()?;
+    Some(Box::new(array) as Box<dyn Any>)
+}
+
+// Collect a boxed `Vec<S>` into the collection `C` of `T`, converting each element
+fn collect<S: 'static + Clone, T: 'static + Clone, C: 'static + FromIterator<T>> (v: &Box<dyn Any>) -> Option<Box<dyn Any>> {
+    let collection: C = v.downcast_ref::<Vec<S>>()?.iter()
+        .map(|x| element::<T>(&(Box::new(x.clone()) as Box<dyn Any>)))
+        .collect::<Option<C>>()?;
+    Some(Box::new(collection) as Box<dyn Any>)
+}
+
+// Convert a boxed tuple literal to a 2-tuple
+fn tuple2<A: 'static + Clone, B: 'static + Clone> (v: &Box<dyn Any>) -> Option<Box<dyn Any>> {
+    match v.downcast_ref::<TupleValue>()?.0.as_slice() {
+        [a, b] => Some(Box::new((element::<A>(a)?, element::<B>(b)?)) as Box<dyn Any>),
+        _ => None
+    }
+}
+
+// Convert a boxed tuple literal to a 3-tuple
+fn tuple3<A: 'static + Clone, B: 'static + Clone, C: 'static + Clone> (v: &Box<dyn Any>) -> Option<Box<dyn Any>> {
+    match v.downcast_ref::<TupleValue>()?.0.as_slice() {
+        [a, b, c] => Some(Box::new((element::<A>(a)?, element::<B>(b)?, element::<C>(c)?)) as Box<dyn Any>),
+        _ => None
+    }
+}
+
+// Wrap a boxed value in `Some`
+fn some<T: 'static + Clone> (v: &Box<dyn Any>) -> Option<Box<dyn Any>> {
+    v.downcast_ref::<T>().map(|x| Box::new(Some(x.clone())) as Box<dyn Any>)
+}
+
+// Convert a boxed `Null` to `None`
+fn none<T: 'static> (v: &Box<dyn Any>) -> Option<Box<dyn Any>> {
+    v.downcast_ref::<Null>().map(|_| Box::new(None::<T>) as Box<dyn Any>)
+}
+
+// Format a primitive type as a string
+fn stringify<T: 'static + ToString> (v: &Box<dyn Any>) -> Option<Box<dyn Any>> {
+    v.downcast_ref::<T>().map(|x| Box::new(x.to_string()) as Box<dyn Any>)
+}
+
+// Convert a string of a single character to `char`
+fn single_char (v: &Box<dyn Any>) -> Option<Box<dyn Any>> {
+    let mut chars = v.downcast_ref::<String>()?.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) => Some(Box::new(c) as Box<dyn Any>),
+        _ => None
+    }
+}
+
 // Parse a string to a primitive type
 fn try_parse<T: 'static + Copy + FromStr> (v: &Box<dyn Any>) -> Option<Box<dyn Any>> {
-    let raw: &String = *v.downcast_ref::<&String>().unwrap();
-    match (*raw).parse::<T>() {
+    let raw = v.downcast_ref::<String>()?;
+    match raw.parse::<T>() {
         Ok(v) => Some(Box::new(v) as Box<dyn Any>),
         Err(_) => None
     }
@@ -0,0 +1,132 @@
+use std::fmt;
+
+
+/// Errors raised by reflection
+/// - finding types
+/// - matching ctors, methods, static functions to an argument vector
+/// - converting arguments
+/// - parsing ctor expressions
+///
+/// Note that methods and static functions are both reported as methods
+#[derive(Clone, Debug, PartialEq)]
+pub enum ReflectError {
+    /// no type registered with the given name
+    TypeNotFound(String),
+    /// more than one type registered with the given short name
+    /// - `candidates` holds the registered name of each, for example `a::Config`
+    AmbiguousType { name: String, candidates: Vec<String> },
+    /// none of the type's ctors can be called with the given arguments
+    /// - `candidates` holds the signature of each ctor, for example `(i32, f64)`
+    NoMatchingConstructor { type_name: String, arg_count: usize, candidates: Vec<String> },
+    /// the type has no method (or static function) with the given name
+    MethodNotFound { type_name: String, method: String },
+    /// the method (or static function) cannot be called with the given arguments
+    /// - `candidates` holds the signature of the method, for example `(i32, f64)`
+    NoMatchingMethod { type_name: String, method: String, arg_count: usize, candidates: Vec<String> },
+    /// no ctor (method is `"*"`), method, or static function takes the arguments as given, calls
+    /// dispatching on exact matches only (see `set_exact_mode`)
+    /// - `arg_types` holds the names of the types of the arguments given
+    /// - `candidates` holds the signature of each of the candidates
+    NoExactMatch { type_name: String, method: String, arg_types: Vec<String>, candidates: Vec<String> },
+    /// more than one ctor matches the given arguments equally well (method is `"*"` for ctors)
+    /// - `candidates` holds the signature of each of the matching ctors
+    AmbiguousMatch { type_name: String, method: String, candidates: Vec<String> },
+    /// an argument could not be converted to the parameter type
+    ConversionFailed { from: String, to: String },
+    /// the result of a reflected call is not of the type requested (see `TypeInfo::create_as`)
+    DowncastFailed { expected: String, actual: String },
+    /// an argument handed to a reflected function is missing or of the wrong type
+    InvalidArgument { index: usize },
+    /// the method was called through `call` but takes `&mut self` (`required`), or was called
+    /// through `call_mut` but takes `&self`
+    MutableReceiver { method: String, required: bool },
+    /// the method takes `self` by value, so must be called through `call_consuming`
+    ConsumingReceiver { method: String },
+    /// the object on which a method was called is not of the type of the method
+    /// - `expected` is the registered name of the type of the method
+    WrongReceiverType { expected: String },
+    /// the method is async, so must be called through `call_async` (feature `async`)
+    AsyncReceiver { method: String },
+    /// the type does not support the operation, which is generated on request (for example
+    /// `clone`, generated by `#[reflect_impl(clone)]`)
+    Unsupported { type_name: String, operation: String },
+    /// the reflected function returned an error (as `Result<T, E>`), given as the error's message
+    CallFailed(String),
+    /// a ctor expression could not be parsed or evaluated
+    ParseError(String),
+    /// evaluation of a sub-expression of a ctor expression failed
+    /// - `expression` is the text of the (innermost) failing sub-expression, for example
+    ///   `Momentum(SMA, [200])` within `Resample(Momentum(SMA, [200]), 300)`
+    InExpression { expression: String, error: Box<ReflectError> },
+}
+
+
+impl fmt::Display for ReflectError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReflectError::TypeNotFound(name) =>
+                write!(f, "could not find type '{}'", name),
+            ReflectError::AmbiguousType { name, candidates } =>
+                write!(f, "ambiguous type '{}', candidates {}", name, candidates.join(" and ")),
+            ReflectError::NoMatchingConstructor { type_name, arg_count, candidates } =>
+                write!(f, "could not find ctor of '{}' for {} arguments{}", type_name, arg_count, expected(candidates)),
+            ReflectError::MethodNotFound { type_name, method } =>
+                write!(f, "could not find method '{}' of '{}'", method, type_name),
+            ReflectError::NoMatchingMethod { type_name, method, arg_count, candidates } =>
+                write!(f, "incompatible arguments for method '{}' of '{}' ({} arguments){}", method, type_name, arg_count, expected(candidates)),
+            ReflectError::NoExactMatch { type_name, method, arg_types, candidates } if method == "*" =>
+                write!(f, "no ctor of '{}' takes ({}) exactly{}", type_name, arg_types.join(", "), expected(candidates)),
+            ReflectError::NoExactMatch { type_name, method, arg_types, candidates } =>
+                write!(f, "method '{}' of '{}' does not take ({}) exactly{}", method, type_name, arg_types.join(", "), expected(candidates)),
+            ReflectError::AmbiguousMatch { type_name, method, candidates } if method == "*" =>
+                write!(f, "ambiguous ctor of '{}', candidates {}", type_name, candidates.join(" and ")),
+            ReflectError::AmbiguousMatch { type_name, method, candidates } =>
+                write!(f, "ambiguous method '{}' of '{}', candidates {}", method, type_name, candidates.join(" and ")),
+            ReflectError::ConversionFailed { from, to } =>
+                write!(f, "failed to convert {} to {}", from, to),
+            ReflectError::DowncastFailed { expected, actual } =>
+                write!(f, "expected result of type {} but got {}", expected, actual),
+            ReflectError::InvalidArgument { index } =>
+                write!(f, "invalid argument type for parameter {}", index),
+            ReflectError::MutableReceiver { method, required: true } =>
+                write!(f, "method '{}' takes &mut self and must be called with call_mut", method),
+            ReflectError::MutableReceiver { method, required: false } =>
+                write!(f, "method '{}' takes &self and must be called with call", method),
+            ReflectError::ConsumingReceiver { method } =>
+                write!(f, "method '{}' takes self by value and must be called with call_consuming", method),
+            ReflectError::WrongReceiverType { expected } =>
+                write!(f, "method called on an object other than a '{}'", expected),
+            ReflectError::AsyncReceiver { method } =>
+                write!(f, "method '{}' is async and must be called with call_async", method),
+            ReflectError::Unsupported { type_name, operation } =>
+                write!(f, "type '{}' does not support {} (see #[reflect_impl({})])", type_name, operation, operation),
+            ReflectError::CallFailed(message) =>
+                write!(f, "{}", message),
+            ReflectError::ParseError(message) =>
+                write!(f, "{}", message),
+            ReflectError::InExpression { expression, error } =>
+                write!(f, "{} in '{}'", error, expression),
+        }
+    }
+}
+
+
+impl std::error::Error for ReflectError {}
+
+
+// Describe candidate signatures, for example ", expected (i32, f64) or (i32)"
+fn expected (candidates: &[String]) -> String {
+    if candidates.is_empty() {
+        String::new()
+    } else {
+        format!(", expected {}", candidates.join(" or "))
+    }
+}
+
+
+/// Allows callers still working with `String` errors to use `?` or `.into()`
+impl From<ReflectError> for String {
+    fn from(error: ReflectError) -> Self {
+        error.to_string()
+    }
+}
@@ -10,8 +10,28 @@ mod types;
 mod registration;
 mod parts;
 mod conversions;
+mod errors;
+mod plans;
+mod arguments;
+mod scope;
+mod builder;
+#[cfg(feature = "policy")]
+mod policy;
+mod numeric;
 
-pub use parts::{Constructor, Method, StaticFunction, Function};
+pub use parts::{Constructor, Method, StaticFunction, Function, Selected, Signature};
+#[cfg(feature = "async")]
+pub use parts::AsyncResult;
 pub use types::TypeInfo;
-pub use conversions::Conversions;
-pub use registration::{register_constructor, register_method, register_function, find_type};
+pub(crate) use types::downcast;
+pub use conversions::{Conversions, ConvertedArg, Null, Score, TupleValue, set_exact_mode, is_exact_mode};
+pub use errors::ReflectError;
+pub use registration::{register_constructor, register_method, register_function, find_type, find_type_by_id, create, registered_types, registry_summary};
+pub use registration::{unregister, unregister_method, clear_registry};
+pub use registration::{register_type, register_clone, register_debug, register_eq, register_type_name, registered_name, type_shortname, qualified_name, lookup_type, name_collisions, duplicate_constructors};
+pub use registration::{register_free_function, call_function, FUNCTIONS_NAMESPACE};
+pub(crate) use registration::free_functions;
+pub use scope::{Scope, with_scope};
+pub use builder::TypeInfoBuilder;
+#[cfg(feature = "policy")]
+pub use policy::{ConversionPolicy, DefaultPolicy, with_policy};
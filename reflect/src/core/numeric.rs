@@ -0,0 +1,206 @@
+//! Conversions among families of numeric types
+//! - a conversion from numeric type `A` to `B` is a chain of two steps: from `A` to an
+//!   intermediate `Number` and from the `Number` to `B`, such that conversions between any pair
+//!   of a family are wired up from a pair of conversion functions per type
+//!
+
+use std::any::{Any, TypeId};
+
+use crate::core::conversions::ConversionFn;
+
+
+/// Score of a numeric conversion preserving every value of the type converted from
+pub(crate) const LOSSLESS: i32 = 150;
+
+/// Score of a numeric conversion which may lose range or precision
+pub(crate) const LOSSY: i32 = 100;
+
+
+/// Conversion between two numeric types of a family
+pub(crate) struct NumericConversion {
+    pub from: TypeId,
+    pub to: TypeId,
+    pub score: i32,
+    pub steps: Vec<ConversionFn>,
+}
+
+
+/// Conversions between each pair of the given numeric types
+/// - conversions preserving every value (widening) score `LOSSLESS`, others `LOSSY`
+/// - values which cannot be represented in the target type (out of range, NaN or infinite)
+///   fail to convert; conversions to floating point round to the nearest value
+/// - only primitive numeric types (`i8` … `u64`, `f32`, `f64`) are supported; other types are
+///   ignored
+///
+/// # Arguments
+/// * `types`: numeric types of the family
+///
+/// # Returns
+/// * conversions from each type of the family to each other
+pub(crate) fn numeric_family (types: &[TypeId]) -> Vec<NumericConversion> {
+    let family: Vec<NumericType> = primitives().into_iter()
+        .filter(|t| types.contains(&t.id))
+        .collect();
+
+    let mut conversions = Vec::new();
+    for from in family.iter() {
+        for to in family.iter().filter(|to| to.id != from.id) {
+            conversions.push(NumericConversion {
+                from: from.id,
+                to: to.id,
+                score: if from.lossless_to(to) { LOSSLESS } else { LOSSY },
+                steps: vec![from.widen, to.narrow],
+            });
+        }
+    }
+    conversions
+}
+
+
+// Intermediate value of a numeric conversion
+#[derive(Clone, Copy)]
+enum Number {
+    Int(i128),
+    Float(f64),
+}
+
+
+// Description of a primitive numeric type
+struct NumericType {
+    id: TypeId,
+    bits: u32,
+    signed: bool,
+    float: bool,
+    widen: ConversionFn,
+    narrow: ConversionFn,
+}
+
+
+impl NumericType {
+
+    // determine whether every value of this type can be represented by another
+    fn lossless_to (&self, other: &NumericType) -> bool {
+        match (self.float, other.float) {
+            (true, true) => other.bits >= self.bits,
+            (true, false) => false,
+            // integer must fit within the mantissa of the float
+            (false, true) => self.bits < if other.bits == 64 { 53 } else { 24 },
+            (false, false) => match (self.signed, other.signed) {
+                (false, true) => other.bits > self.bits,
+                (true, false) => false,
+                _ => other.bits >= self.bits,
+            },
+        }
+    }
+}
+
+
+// Primitive numeric types
+// - implemented for the primitive integer and floating point types
+trait Primitive: 'static + Copy {
+    const BITS: u32;
+    const SIGNED: bool;
+    const FLOAT: bool;
+
+    fn to_number (self) -> Number;
+    fn from_number (n: Number) -> Option<Self>;
+}
+
+
+macro_rules! integer_primitive {
+    ($($int:ty),*) => {$(
+        impl Primitive for $int {
+            const BITS: u32 = <$int>::BITS;
+            const SIGNED: bool = <$int>::MIN != 0;
+            const FLOAT: bool = false;
+
+            fn to_number (self) -> Number {
+                Number::Int(i128::from(self))
+            }
+
+            fn from_number (n: Number) -> Option<Self> {
+                match n {
+                    Number::Int(x) => <$int>::try_from(x).ok(),
+                    Number::Float(x) if x.is_finite() => <$int>::try_from(x.round() as i128).ok(),
+                    Number::Float(_) => None,
+                }
+            }
+        }
+    )*};
+}
+
+integer_primitive!(i8, i16, i32, i64, u8, u16, u32, u64);
+
+
+impl Primitive for f32 {
+    const BITS: u32 = 32;
+    const SIGNED: bool = true;
+    const FLOAT: bool = true;
+
+    fn to_number (self) -> Number {
+        Number::Float(f64::from(self))
+    }
+
+    fn from_number (n: Number) -> Option<Self> {
+        let x = match n {
+            Number::Int(x) => x as f32,
+            Number::Float(x) if x.is_finite() && (x as f32).is_infinite() => return None,
+            Number::Float(x) => x as f32,
+        };
+        Some(x)
+    }
+}
+
+
+impl Primitive for f64 {
+    const BITS: u32 = 64;
+    const SIGNED: bool = true;
+    const FLOAT: bool = true;
+
+    fn to_number (self) -> Number {
+        Number::Float(self)
+    }
+
+    fn from_number (n: Number) -> Option<Self> {
+        match n {
+            Number::Int(x) => Some(x as f64),
+            Number::Float(x) => Some(x),
+        }
+    }
+}
+
+
+// Describe a primitive numeric type
+fn numeric<T: Primitive> () -> NumericType {
+    NumericType {
+        id: TypeId::of::<T>(),
+        bits: T::BITS,
+        signed: T::SIGNED,
+        float: T::FLOAT,
+        widen: widen::<T>,
+        narrow: narrow::<T>,
+    }
+}
+
+
+// All primitive numeric types
+fn primitives () -> Vec<NumericType> {
+    vec![
+        numeric::<i8>(), numeric::<i16>(), numeric::<i32>(), numeric::<i64>(),
+        numeric::<u8>(), numeric::<u16>(), numeric::<u32>(), numeric::<u64>(),
+        numeric::<f32>(), numeric::<f64>(),
+    ]
+}
+
+
+// Convert a boxed primitive to a boxed intermediate `Number`
+fn widen<T: Primitive> (v: &Box<dyn Any>) -> Option<Box<dyn Any>> {
+    v.downcast_ref::<T>().map(|x| Box::new(x.to_number()) as Box<dyn Any>)
+}
+
+
+// Convert a boxed intermediate `Number` to a boxed primitive
+fn narrow<T: Primitive> (v: &Box<dyn Any>) -> Option<Box<dyn Any>> {
+    let n = *v.downcast_ref::<Number>()?;
+    T::from_number(n).map(|x| Box::new(x) as Box<dyn Any>)
+}
@@ -1,6 +1,16 @@
 
 use std::any::{Any, TypeId};
-use crate::Conversions;
+use std::fmt;
+#[cfg(feature = "async")]
+use std::future::Future;
+#[cfg(feature = "async")]
+use std::pin::Pin;
+use crate::{Conversions, ReflectError};
+
+
+/// Future of the result of an async method (see `Method::call_async`)
+#[cfg(feature = "async")]
+pub type AsyncResult<'a> = Pin<Box<dyn Future<Output = Result<Box<dyn Any>, ReflectError>> + 'a>>;
 
 
 ///
@@ -13,9 +23,67 @@ pub trait Function: Send + Sync {
     /// Return the argument signature
     fn arg_types(&self) -> &[TypeId];
 
+    /// Return the readable names of the argument types, for example `["i32", "&[f64]"]`
+    fn arg_type_names(&self) -> Vec<String> {
+        self.arg_types().iter().map(|t| Conversions::type_name(*t)).collect()
+    }
+
     /// The object type associated with this call
+    /// - for functions returning `Result<T, E>` or `Option<T>` this is `T`
+    /// - the boxed value of a function returning `Option<T>` is the `Option<T>` itself, unless
+    ///   reflected with `#[reflect(none_as_error)]`, in which case it is `T` (with `None` an error;
+    ///   see `boxed_type`)
     fn return_type(&self) -> TypeId;
 
+    /// The type of the value boxed by a call
+    /// - the return type, except for functions returning `Option<T>` (unless reflected with
+    ///   `#[reflect(none_as_error)]`), boxing the `Option<T>` itself
+    fn boxed_type(&self) -> TypeId {
+        self.return_type()
+    }
+
+    /// Return the readable name of the return type, for example `f64` or `Vec<i32>`
+    /// - empty if not known to the function, in which case `signature` looks up the name of
+    ///   the `TypeId` among the names known to `Conversions`
+    fn return_type_name(&self) -> &str {
+        ""
+    }
+
+    /// Return the doc comment of the function (empty if undocumented)
+    fn doc(&self) -> &str {
+        ""
+    }
+
+    /// Return the names of the parameters, for example `["ma", "windows"]`
+    /// - empty if not known to the function, in which case the function cannot be called with
+    ///   arguments by name
+    fn arg_names(&self) -> &[&str] {
+        &[]
+    }
+
+    /// Return the default value of each parameter, as an expression such as `"1.0"`, or `None`
+    /// if the parameter has no default
+    /// - empty if no parameter has a default; arguments may be omitted for parameters with a
+    ///   default, the expression being evaluated (and converted to the parameter type) instead
+    fn arg_defaults(&self) -> &[Option<&str>] {
+        &[]
+    }
+
+    /// Return the signature (argument and return types, and doc comment)
+    fn signature(&self) -> Signature {
+        Signature {
+            arg_types: self.arg_types().to_vec(),
+            arg_type_names: self.arg_type_names(),
+            arg_names: self.arg_names().iter().map(|name| name.to_string()).collect(),
+            return_type: self.return_type(),
+            return_type_name: match self.return_type_name() {
+                "" => Conversions::type_name(self.return_type()),
+                name => name.to_string()
+            },
+            doc: self.doc().to_string(),
+        }
+    }
+
     /// Determine if arguments match this callable
     ///
     /// # Arguments
@@ -47,6 +115,61 @@ pub trait Function: Send + Sync {
 }
 
 
+///
+/// Signature of a ctor, method, or static function
+/// - argument types and their readable names
+/// - parameter names (empty if not known)
+/// - return type and its readable name
+/// - doc comment (empty if undocumented)
+///
+#[derive(Clone, Debug, PartialEq)]
+pub struct Signature {
+    pub arg_types: Vec<TypeId>,
+    pub arg_type_names: Vec<String>,
+    pub arg_names: Vec<String>,
+    pub return_type: TypeId,
+    pub return_type_name: String,
+    pub doc: String,
+}
+
+
+/// Describes the arguments of the signature, for example `(i32, &[f64])`
+impl fmt::Display for Signature {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({})", self.arg_type_names.join(", "))
+    }
+}
+
+
+///
+/// Ctor, method, or static function selected for a call among the candidates of its name (see
+/// `TypeInfo::create_selected` and `TypeInfo::call_selected`)
+/// - name of the function (`*` for ctors)
+/// - readable names of its argument types
+/// - score of the arguments given versus its parameters (see `Conversions::score`)
+///
+#[derive(Clone, Debug, PartialEq)]
+pub struct Selected {
+    pub name: String,
+    pub arg_type_names: Vec<String>,
+    pub score: i32,
+}
+
+
+impl Selected {
+
+    /// Describe the function selected for the given arguments
+    pub(crate) fn new<F: ?Sized + Function> (function: &F, args: &[Box<dyn Any>]) -> Self {
+        let parameters = &function.arg_types()[..args.len().min(function.arg_types().len())];
+        Selected {
+            name: function.name().to_string(),
+            arg_type_names: function.arg_type_names(),
+            score: Conversions::score(parameters, args)
+        }
+    }
+}
+
+
 ///
 /// Constructor reflection information
 ///
@@ -58,7 +181,21 @@ pub trait Constructor: Function {
     ///
     /// # Returns
     /// * constructed instance
-    fn create(&self, args: &[Box<dyn Any>]) -> Result<Box<dyn Any>, String>;
+    fn create(&self, args: &[&dyn Any]) -> Result<Box<dyn Any>, ReflectError>;
+
+    /// call a ctor, taking ownership of the arguments
+    /// - arguments for container parameters (such as `Vec<f64>`) are moved into the ctor
+    ///   rather than cloned; by default the arguments are passed to `create`
+    ///
+    /// # Arguments
+    /// * `args`: a list of arguments to the ctor
+    ///
+    /// # Returns
+    /// * constructed instance
+    fn create_owned(&self, args: Vec<Box<dyn Any>>) -> Result<Box<dyn Any>, ReflectError> {
+        let args: Vec<&dyn Any> = args.iter().map(|arg| arg.as_ref()).collect();
+        self.create(&args)
+    }
 
     /// create a boxed clone of this struct
     fn clone_boxed(&self) -> Box<dyn Constructor>;
@@ -78,7 +215,82 @@ pub trait Method: Function {
     ///
     /// # Returns
     /// * function value
-    fn call(&self, obj: &Box<dyn Any>, args: &[Box<dyn Any>]) -> Result<Box<dyn Any>, String>;
+    fn call(&self, obj: &Box<dyn Any>, args: &[&dyn Any]) -> Result<Box<dyn Any>, ReflectError>;
+
+    /// call a method on object, taking ownership of the arguments
+    /// - arguments for container parameters (such as `Vec<f64>`) are moved into the method
+    ///   rather than cloned; by default the arguments are passed to `call`
+    ///
+    /// # Arguments
+    /// * `obj`: object on which the method should be called
+    /// * `args`: a list of arguments to the method
+    ///
+    /// # Returns
+    /// * function value
+    fn call_owned(&self, obj: &Box<dyn Any>, args: Vec<Box<dyn Any>>) -> Result<Box<dyn Any>, ReflectError> {
+        let args: Vec<&dyn Any> = args.iter().map(|arg| arg.as_ref()).collect();
+        self.call(obj, &args)
+    }
+
+    /// call a method taking `&mut self` on object
+    /// - methods taking `&self` do not implement this and are called with `call`
+    ///
+    /// # Arguments
+    /// * `obj`: object on which the method should be called
+    /// * `args`: a list of arguments to the method
+    ///
+    /// # Returns
+    /// * function value
+    fn call_mut(&self, _obj: &mut Box<dyn Any>, _args: &[&dyn Any]) -> Result<Box<dyn Any>, ReflectError> {
+        Err(ReflectError::MutableReceiver { method: self.name().to_string(), required: false })
+    }
+
+    /// call a method on object, taking ownership of the object
+    /// - methods taking `self` implement this; others are called with `call` or `call_mut`,
+    ///   dropping the object thereafter
+    ///
+    /// # Arguments
+    /// * `obj`: object on which the method should be called
+    /// * `args`: a list of arguments to the method
+    ///
+    /// # Returns
+    /// * function value
+    fn call_consuming(&self, mut obj: Box<dyn Any>, args: &[&dyn Any]) -> Result<Box<dyn Any>, ReflectError> {
+        if self.is_mut() {
+            self.call_mut(&mut obj, args)
+        } else {
+            self.call(&obj, args)
+        }
+    }
+
+    /// determine whether the method takes `&mut self` (and so must be called with `call_mut`)
+    fn is_mut(&self) -> bool {
+        false
+    }
+
+    /// determine whether the method takes `self` (and so must be called with `call_consuming`)
+    fn is_consuming(&self) -> bool {
+        false
+    }
+
+    /// call an async method on object, returning the future of its result
+    /// - methods other than async methods are called with `call`, the future being ready
+    ///
+    /// # Arguments
+    /// * `obj`: object on which the method should be called
+    /// * `args`: a list of arguments to the method
+    ///
+    /// # Returns
+    /// * future of the function value
+    #[cfg(feature = "async")]
+    fn call_async<'a>(&'a self, obj: &'a Box<dyn Any>, args: &'a [&'a dyn Any]) -> AsyncResult<'a> {
+        Box::pin(std::future::ready(self.call(obj, args)))
+    }
+
+    /// determine whether the method is async (and so must be called with `call_async`)
+    fn is_async(&self) -> bool {
+        false
+    }
 
     /// create a boxed clone of this struct
     fn clone_boxed(&self) -> Box<dyn Method>;
@@ -97,7 +309,7 @@ pub trait StaticFunction: Function {
     ///
     /// # Returns
     /// * constructed instance
-    fn call(&self, args: &[Box<dyn Any>]) -> Result<Box<dyn Any>, String>;
+    fn call(&self, args: &[&dyn Any]) -> Result<Box<dyn Any>, ReflectError>;
 
     /// create a boxed clone of this struct
     fn clone_boxed(&self) -> Box<dyn StaticFunction>;
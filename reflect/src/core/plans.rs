@@ -0,0 +1,171 @@
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+
+use crate::core::{Conversions, Function, ReflectError, is_exact_mode};
+
+
+/// Conversions required to call a function given arguments of particular types
+/// - index of the chosen candidate (for ctors, of which there may be several)
+/// - per argument, the conversion to apply or `None` to pass the argument through
+#[derive(Clone)]
+pub(crate) struct ConversionPlan {
+    pub index: usize,
+    converters: Vec<Option<Arc<Conversions>>>,
+    generation: usize,
+}
+
+
+impl ConversionPlan {
+
+    /// Determine the plan for calling the best of the candidates with the given arguments
+    ///
+    /// # Arguments
+    /// * `candidates`: candidate functions (ctors, or a single method / static function)
+    /// * `args`: argument list
+    ///
+    /// # Returns
+    /// * plan or None if no candidate can be called with the arguments
+    pub fn new<T: ?Sized + Function> (candidates: &[Box<T>], args: &[Box<dyn Any>]) -> Option<ConversionPlan> {
+        let generation = Conversions::generation();
+
+        // when exact, the best of the candidates matching the arguments as given is called
+        if is_exact_mode() {
+            let index = (0..candidates.len())
+                .filter(|&i| candidates[i].matching(args))
+                .max_by_key(|&i| (Conversions::score(candidates[i].arg_types(), args), std::cmp::Reverse(i)))?;
+            return Some(ConversionPlan { index, converters: vec![None; args.len()], generation });
+        }
+
+        // a single candidate (method or static function) matching the arguments is called as is,
+        // unless a policy scoring the conversions is installed, otherwise the best candidate is
+        // chosen by score
+        let best = match candidates {
+            [single] if single.matching(args) && !has_policy() => single.as_ref(),
+            _ => Conversions::find_best_match(candidates, args)?
+        };
+        let index = candidates.iter().position(|c| std::ptr::addr_eq(c.as_ref(), best))?;
+
+        // arguments matching the parameters are passed through, otherwise converted
+        let converters = if best.matching(args) {
+            vec![None; args.len()]
+        } else {
+            let mut converters = Vec::with_capacity(args.len());
+            for (to_type, from_arg) in best.arg_types().iter().zip(args) {
+                let arg_type = (**from_arg).type_id();
+                if arg_type == *to_type {
+                    converters.push(None);
+                } else {
+                    let conversion = Conversions::find(arg_type, *to_type)?;
+                    converters.push((!conversion.is_pass_through()).then_some(conversion));
+                }
+            }
+            converters
+        };
+
+        Some(ConversionPlan { index, converters, generation })
+    }
+
+    /// Convert arguments according to plan
+    ///
+    /// # Arguments
+    /// * `parameters`: parameter types of the chosen candidate
+    /// * `args`: argument list, of the types the plan was made for
+    ///
+    /// # Returns
+    /// * converted arguments (see `Conversions::argv`) or the failed conversion
+    pub fn convert (&self, parameters: &[TypeId], args: &[Box<dyn Any>]) -> Result<Vec<Option<Box<dyn Any>>>, ReflectError> {
+        let mut newargs = Vec::with_capacity(args.len());
+        for ((converter, to_type), from_arg) in self.converters.iter().zip(parameters).zip(args) {
+            match converter {
+                None => newargs.push(None),
+                Some(conversion) => match conversion.convert(from_arg) {
+                    Some(v) => newargs.push(Some(v)),
+                    None => return Err(ReflectError::ConversionFailed {
+                        from: Conversions::type_name((**from_arg).type_id()),
+                        to: Conversions::type_name(*to_type)
+                    })
+                }
+            }
+        }
+        Ok(newargs)
+    }
+}
+
+
+// Determine whether a policy scoring conversions is installed (see `with_policy`), which may
+// reject the conversion of arguments to equivalent types
+fn has_policy () -> bool {
+    #[cfg(feature = "policy")]
+    return crate::core::policy::has_policy();
+    #[cfg(not(feature = "policy"))]
+    false
+}
+
+
+/// Kind of function a plan is for (as methods and static functions may share a name)
+#[derive(Clone, Copy)]
+pub(crate) enum PlanKind {
+    Constructor,
+    Method,
+    StaticFunction,
+}
+
+
+// plans by name of function, then by the types of the arguments
+type PlansByName = HashMap<String, HashMap<Vec<TypeId>, ConversionPlan>>;
+
+
+/// Cache of conversion plans of a type
+/// - by kind and name of function, then by the types of the arguments
+/// - plans made prior to a change in the registered conversions are recomputed
+#[derive(Default)]
+pub(crate) struct PlanCache {
+    plans: RwLock<[PlansByName; 3]>,
+}
+
+
+impl PlanCache {
+
+    /// Find plan for calling the candidates with the given arguments, making it if not cached
+    ///
+    /// # Arguments
+    /// * `kind`: kind of function
+    /// * `name`: name of function (for ctors, all share the same name)
+    /// * `candidates`: candidate functions
+    /// * `args`: argument list
+    ///
+    /// # Returns
+    /// * plan or None if no candidate can be called with the arguments
+    pub fn find<T: ?Sized + Function> (&self, kind: PlanKind, name: &str, candidates: &[Box<T>], args: &[Box<dyn Any>]) -> Option<ConversionPlan> {
+        // plans are made for the registered scores, so are not used under a policy
+        #[cfg(feature = "policy")]
+        if crate::core::policy::has_policy() {
+            return ConversionPlan::new(candidates, args);
+        }
+
+        let arg_types: Vec<TypeId> = args.iter().map(|arg| (**arg).type_id()).collect();
+
+        {
+            let plans = self.plans.read().unwrap();
+            let cached = plans[kind as usize].get(name).and_then(|by_args| by_args.get(&arg_types));
+            if let Some(plan) = cached {
+                if plan.generation == Conversions::generation() {
+                    return Some(plan.clone());
+                }
+            }
+        }
+
+        let plan = ConversionPlan::new(candidates, args)?;
+        let mut plans = self.plans.write().unwrap();
+        plans[kind as usize].entry(name.to_string()).or_default().insert(arg_types, plan.clone());
+        Some(plan)
+    }
+
+    /// Remove all plans (for example when functions are registered)
+    pub fn clear (&mut self) {
+        for plans in self.plans.get_mut().unwrap().iter_mut() {
+            plans.clear();
+        }
+    }
+}
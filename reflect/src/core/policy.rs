@@ -0,0 +1,86 @@
+//! Policies ranking argument conversions in place of the registered scores
+//! - a policy is installed for a closure on the current thread (see `with_policy`), such that
+//!   the same registry can serve, say, a lenient config loader and a strict API
+//! - arguments of the parameter type pass as is, regardless of the policy; the policy scores (or
+//!   rejects) the conversion of the others, including those of equivalent types (such as
+//!   `Vec<f64>` for `&[f64]`), and so decides which overload is called
+//! - conversion plans cached by types are bypassed while a policy is installed
+//!
+
+use std::any::TypeId;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+
+thread_local! {
+    // policies installed, innermost last
+    static POLICIES: RefCell<Vec<Rc<dyn ConversionPolicy>>> = const { RefCell::new(Vec::new()) };
+}
+
+
+/// Ranking of the conversion of arguments to parameter types (see `with_policy`)
+pub trait ConversionPolicy {
+
+    /// Score of passing an argument of type `from` for a parameter of type `to`
+    /// - by default the score of the registered conversion
+    ///
+    /// # Arguments
+    /// * `from`: type of the argument
+    /// * `to`: type of the parameter
+    /// * `score`: score of the registered conversion, or None if there is no conversion
+    ///
+    /// # Returns
+    /// * score of the conversion, or None to reject the conversion
+    fn score (&self, _from: TypeId, _to: TypeId, score: Option<i32>) -> Option<i32> {
+        score
+    }
+}
+
+
+/// Policy scoring conversions as registered, as if no policy were installed
+pub struct DefaultPolicy;
+
+impl ConversionPolicy for DefaultPolicy {}
+
+
+// Uninstalls the innermost policy when dropped, including when the closure panics
+struct PolicyGuard;
+
+impl Drop for PolicyGuard {
+    fn drop(&mut self) {
+        POLICIES.with(|policies| policies.borrow_mut().pop());
+    }
+}
+
+
+/// Call a closure with a policy scoring the conversion of arguments on the current thread
+/// ```ignore
+///    let obj = reflect::with_policy(NoCoercion, || CTorParser::create("Order(100, 10.5)"))?;
+/// ```
+///
+/// # Arguments
+/// - `policy`: policy, in place of any policy installed by an enclosing call
+/// - `f`: closure
+///
+/// # Returns
+/// - result of the closure; the policy is uninstalled thereafter
+pub fn with_policy<R> (policy: impl ConversionPolicy + 'static, f: impl FnOnce() -> R) -> R {
+    POLICIES.with(|policies| policies.borrow_mut().push(Rc::new(policy)));
+    let _guard = PolicyGuard;
+    f()
+}
+
+
+/// Score of a conversion under the policy installed on the current thread, if any
+pub(crate) fn policy_score(from: TypeId, to: TypeId, score: Option<i32>) -> Option<i32> {
+    match POLICIES.with(|policies| policies.borrow().last().cloned()) {
+        Some(policy) => policy.score(from, to, score),
+        None => score
+    }
+}
+
+
+/// Determine whether a policy is installed on the current thread
+pub(crate) fn has_policy() -> bool {
+    POLICIES.with(|policies| !policies.borrow().is_empty())
+}
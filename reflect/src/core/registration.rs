@@ -1,114 +1,496 @@
 
-use std::any::{TypeId};
+use std::any::{Any, TypeId};
 use std::any::type_name;
+use std::fmt;
 
 use crate::core::{Constructor, Method, StaticFunction};
-use crate::core::TypeInfo;
+use crate::core::{TypeInfo, Conversions, ReflectError};
+use crate::core::conversions::readable_type_name;
+use crate::core::scope::{scoped_type, scoped_type_by_id};
 
 use lazy_static::lazy_static;
 use std::collections::HashMap;
-use std::sync::Mutex;
+use std::sync::RwLock;
 use std::sync::Arc;
 
 
 //
 // Repository of reflected types
+// - read-mostly: types are registered at startup, then looked up concurrently
 //
 lazy_static! {
-    static ref TYPE_REGISTRY: Mutex<HashMap<String, Arc<TypeInfo>>> = Mutex::new(HashMap::new());
+    static ref TYPE_REGISTRY: RwLock<HashMap<String, Arc<TypeInfo>>> = RwLock::new(HashMap::new());
+}
+
+//
+// Names under which types are registered, where other than the short type name, and the
+// registered name of each type by its fully qualified name
+//
+lazy_static! {
+    static ref TYPE_NAMES: RwLock<HashMap<TypeId, String>> = RwLock::new(HashMap::new());
+    static ref QUALIFIED_NAMES: RwLock<HashMap<String, String>> = RwLock::new(HashMap::new());
+}
+
+//
+// Names contested by more than one type, with the name the later type was registered under, and
+// ctors registered with the same argument types as another ctor of the type
+//
+lazy_static! {
+    static ref COLLISIONS: RwLock<Vec<(String, String)>> = RwLock::new(Vec::new());
+    static ref DUPLICATE_CTORS: RwLock<Vec<(String, Vec<TypeId>)>> = RwLock::new(Vec::new());
+}
+
+
+/// Name of the synthetic type holding free functions (reported in errors)
+pub const FUNCTIONS_NAMESPACE: &str = "functions";
+
+//
+// Repository of free functions, held as the static functions of a synthetic type
+//
+lazy_static! {
+    static ref FUNCTION_REGISTRY: RwLock<Arc<TypeInfo>> =
+        RwLock::new(Arc::new(TypeInfo::new(FUNCTIONS_NAMESPACE.to_string(), TypeId::of::<()>())));
 }
 
 
 /// Get shortened type name for a given type
 /// - avoids crate and module in the type so can use a more human naming
+/// - generic arguments are retained, so that instantiations are distinct, for example `Buffer<f64>`
 pub fn type_shortname<T: 'static>() -> String {
     // get short name for type (trimming off the crate and module)
-    let type_name = type_name::<T>();
-    type_name.split("::").last().unwrap_or(type_name).to_string()
+    readable_type_name(type_name::<T>())
+}
+
+/// Get fully qualified type name for a given type
+/// - includes the crate and module, for example `mycrate::indicators::Momentum`
+pub fn qualified_name<T: 'static>() -> String {
+    type_name::<T>().to_string()
+}
+
+/// Register a type under a given name rather than its short type name
+/// - for example under a namespace, such as `"indicators::Momentum"`
+/// - ctors, methods, and static functions registered for the type, before or after, are
+///   registered under the given name
+/// - if the name is taken by another type, the type keeps its name and the collision is
+///   recorded (see `name_collisions`)
+///
+/// # Arguments
+/// - `name`: name under which the type is registered
+pub fn register_type_name<T: 'static>(name: &str) {
+    let objtype = TypeId::of::<T>();
+    let mut registry = TYPE_REGISTRY.write().unwrap();
+    if registry.get(name).is_some_and(|info| info.objtype != objtype) {
+        COLLISIONS.write().unwrap().push((name.to_string(), registered_name::<T>()));
+        return;
+    }
+
+    let mut names = TYPE_NAMES.write().unwrap();
+    QUALIFIED_NAMES.write().unwrap().insert(qualified_name::<T>(), name.to_string());
+
+    // move the type if already registered under another name
+    let prior = names.insert(objtype, name.to_string()).unwrap_or_else(type_shortname::<T>);
+    if prior != name {
+        if let Some(mut type_info) = registry.remove(&prior) {
+            Arc::make_mut(&mut type_info).name = name.to_string();
+            registry.insert(name.to_string(), type_info);
+        }
+    }
+}
+
+
+/// Name under which a type is registered
+/// - the name given with `register_type_name`, or otherwise the short type name
+pub fn registered_name<T: 'static>() -> String {
+    let names = TYPE_NAMES.read().unwrap();
+    match names.get(&TypeId::of::<T>()) {
+        Some(name) => name.clone(),
+        None => type_shortname::<T>()
+    }
+}
+
+
+/// Names contested by more than one type
+/// - where a type is registered under a name already taken by another type, the type is
+///   registered under its fully qualified name instead (or keeps its name if renamed with
+///   `register_type_name`)
+///
+/// # Returns
+/// - `(contested name, name the later type is registered under)` in order of registration
+pub fn name_collisions() -> Vec<(String, String)> {
+    COLLISIONS.read().unwrap().clone()
+}
+
+
+/// Ctors with the same argument types as another ctor of their type
+/// - only one of such ctors can be matched when creating an instance, shadowing the other
+///
+/// # Returns
+/// - `(type name, signature)` for each duplicate, for example `("Momentum", "(i32, f64)")`,
+///   in order of registration
+pub fn duplicate_constructors() -> Vec<(String, String)> {
+    let duplicates = DUPLICATE_CTORS.read().unwrap();
+    duplicates.iter()
+        .map(|(name, arg_types)| {
+            let arg_names: Vec<String> = arg_types.iter().map(|t| Conversions::type_name(*t)).collect();
+            (name.clone(), format!("({})", arg_names.join(", ")))
+        })
+        .collect()
 }
 
+
 /// Get type information for given named type
+/// - see `lookup_type`
 ///
 /// # Arguments
 /// - `name`: name of type (as string)
 ///
 /// # Returns
 /// - `Some(typeinfo)` OR
-/// - `None`
+/// - `None` if there is no such type, or the name is ambiguous
 pub fn find_type(name: &str) -> Option<Arc<TypeInfo>> {
-    let registry = TYPE_REGISTRY.lock().unwrap();
-    match registry.get(name) {
-        Some(info) => Some(info.clone()),
-        None => None
+    lookup_type(name).ok()
+}
+
+
+/// Get type information for given named type
+/// - the name is the registered name, the fully qualified name, or the short name (without
+///   namespace or module), for example `indicators::Momentum` may be found as `Momentum`
+/// - types registered within a scope of the current thread are found first (see `with_scope`)
+///
+/// # Arguments
+/// - `name`: name of type (as string)
+///
+/// # Returns
+/// - type information, `TypeNotFound`, or `AmbiguousType` if more than one type has the short name
+pub fn lookup_type(name: &str) -> Result<Arc<TypeInfo>, ReflectError> {
+    if let Some(info) = scoped_type(name) {
+        return Ok(info);
+    }
+
+    let registry = TYPE_REGISTRY.read().unwrap();
+    if let Some(info) = registry.get(name) {
+        return Ok(info.clone());
+    }
+    if let Some(info) = QUALIFIED_NAMES.read().unwrap().get(name).and_then(|registered| registry.get(registered)) {
+        return Ok(info.clone());
+    }
+
+    let mut candidates: Vec<&Arc<TypeInfo>> = registry.iter()
+        .filter(|(key, _)| readable_type_name(key) == name)
+        .map(|(_, info)| info)
+        .collect();
+    match candidates.len() {
+        0 => Err(ReflectError::TypeNotFound(name.to_string())),
+        1 => Ok(candidates.remove(0).clone()),
+        _ => {
+            let mut names: Vec<String> = candidates.iter().map(|info| info.name.clone()).collect();
+            names.sort();
+            Err(ReflectError::AmbiguousType { name: name.to_string(), candidates: names })
+        }
     }
 }
 
 
+/// Create an instance of a named type given arguments
+/// - equivalent to `find_type` followed by `TypeInfo::create`
+///
+/// # Arguments
+/// - `name`: name of type (as string)
+/// - `args`: arguments to ctor
+///
+/// # Returns
+/// - new object instance, or `TypeNotFound` or `AmbiguousType` (see `lookup_type`)
+pub fn create(name: &str, args: &[Box<dyn Any>]) -> Result<Box<dyn Any>, ReflectError> {
+    lookup_type(name)?.create(args)
+}
+
+
+/// Get type information for a given type id
+/// - useful where an object has been created through reflection and its type name is
+///   not at hand
+/// - types registered within a scope of the current thread are found first (see `with_scope`)
+///
+/// # Arguments
+/// - `objtype`: type id of the (reflected) type
+///
+/// # Returns
+/// - `Some(typeinfo)` OR
+/// - `None`
+pub fn find_type_by_id(objtype: TypeId) -> Option<Arc<TypeInfo>> {
+    scoped_type_by_id(objtype).or_else(|| global_type_by_id(objtype))
+}
+
+
+/// Get type information for a given type id from the global registry, disregarding scopes
+pub(crate) fn global_type_by_id(objtype: TypeId) -> Option<Arc<TypeInfo>> {
+    let registry = TYPE_REGISTRY.read().unwrap();
+    registry.values().find(|info| info.objtype == objtype).cloned()
+}
+
+
+/// Names of all registered types
+///
+/// # Returns
+/// - type names, sorted
+pub fn registered_types() -> Vec<String> {
+    let registry = TYPE_REGISTRY.read().unwrap();
+    let mut names: Vec<String> = registry.keys().cloned().collect();
+    names.sort();
+    names
+}
+
+
+/// Summary of all registered types
+///
+/// # Returns
+/// - `(name, ctor count, method count)` for each type, sorted by name
+pub fn registry_summary() -> Vec<(String, usize, usize)> {
+    let registry = TYPE_REGISTRY.read().unwrap();
+    let mut summary: Vec<(String, usize, usize)> = registry.values()
+        .map(|info| (info.name.clone(), info.constructors.len(), info.methods.values().map(Vec::len).sum()))
+        .collect();
+    summary.sort();
+    summary
+}
+
+
+/// Remove a type from the registry
+/// - primarily intended for tests and for dynamically reloading types
+/// - the names recorded for the type (its name given by `register_type_name` or taken on a
+///   collision, its qualified name, collisions it was renamed by, and its duplicate ctors) are
+///   removed with it, such that the type is registered as if new if registered again
+///
+/// # Arguments
+/// - `name`: name of type (as string)
+///
+/// # Returns
+/// - `true` if the type was registered
+pub fn unregister(name: &str) -> bool {
+    let mut registry = TYPE_REGISTRY.write().unwrap();
+    match registry.remove(name) {
+        Some(type_info) => {
+            forget_names(&type_info);
+            true
+        }
+        None => false
+    }
+}
+
+
+/// Remove a method (with all of its overloads) from a registered type
+/// - primarily intended for tests and for dynamically reloading types
+///
+/// # Arguments
+/// - `type_name`: name of type (as string)
+/// - `method_name`: name of method
+///
+/// # Returns
+/// - `true` if the method was registered
+pub fn unregister_method(type_name: &str, method_name: &str) -> bool {
+    let mut registry = TYPE_REGISTRY.write().unwrap();
+    match registry.get_mut(type_name) {
+        Some(type_info) => {
+            let type_info = Arc::make_mut(type_info);
+            type_info.plans.clear();
+            type_info.methods.remove(method_name).is_some()
+        }
+        None => false
+    }
+}
+
+
+/// Remove all types and free functions from the registry
+/// - primarily intended for tests and for dynamically reloading types; note that types
+///   registered by `reflect_impl` are only registered once, at startup
+/// - the names recorded for types (see `unregister`), collisions, and duplicate ctors are
+///   removed as well; registered conversions remain
+pub fn clear_registry() {
+    let mut registry = TYPE_REGISTRY.write().unwrap();
+    registry.clear();
+    TYPE_NAMES.write().unwrap().clear();
+    QUALIFIED_NAMES.write().unwrap().clear();
+    COLLISIONS.write().unwrap().clear();
+    DUPLICATE_CTORS.write().unwrap().clear();
+    *FUNCTION_REGISTRY.write().unwrap() = Arc::new(TypeInfo::new(FUNCTIONS_NAMESPACE.to_string(), TypeId::of::<()>()));
+}
+
+
+// Remove the names recorded for a type removed from the registry
+// - collisions where another type took the name of the type remain, as the other type is still
+//   registered under the name it was given instead
+fn forget_names(type_info: &TypeInfo) {
+    TYPE_NAMES.write().unwrap().remove(&type_info.objtype);
+    QUALIFIED_NAMES.write().unwrap().retain(|_, registered| *registered != type_info.name);
+    COLLISIONS.write().unwrap().retain(|(_, renamed)| *renamed != type_info.name);
+    DUPLICATE_CTORS.write().unwrap().retain(|(name, _)| *name != type_info.name);
+}
+
+
+/// Register a type without any ctors, methods, or static functions
+/// - the type is known to the registry (for example to `find_type`), such that members can be
+///   registered later, or the type can take part in conversions
+/// - registering a type already registered leaves it as is (other than renaming it)
+///
+/// # Arguments
+/// - `name`: name under which the type is registered, or `None` for its short type name
+pub fn register_type<T: 'static>(name: Option<&str>) {
+    if let Some(name) = name {
+        register_type_name::<T>(name);
+    }
+    let mut registry = TYPE_REGISTRY.write().unwrap();
+    type_entry::<T>(&mut registry);
+}
+
+
 /// Register a constructor for a given type
+/// - a ctor with the same argument types as a ctor already registered is recorded as a
+///   duplicate (see `duplicate_constructors`)
 ///
 /// # Arguments
 /// - `constructor`: constructor to be added
 pub fn register_constructor<T: 'static>(constructor: Box<dyn Constructor>) {
-    let mut registry = TYPE_REGISTRY.lock().unwrap();
-    let short_name = type_shortname::<T>();
-
-    // get type associated with this ctor (or create type entry)
-    let type_info = registry.entry(short_name.clone()).or_insert_with(|| {
-        Arc::new(TypeInfo {
-            name: short_name,
-            objtype: TypeId::of::<T>(),
-            constructors: Vec::new(),
-            methods: HashMap::new(),
-            functions: HashMap::new()
-        })
-    });
+    let mut registry = TYPE_REGISTRY.write().unwrap();
+    let type_info = type_entry::<T>(&mut registry);
 
-    Arc::make_mut(type_info).constructors.push(constructor);
+    if type_info.constructors.iter().any(|ctor| ctor.arg_types() == constructor.arg_types()) {
+        let duplicate = (type_info.name.clone(), constructor.arg_types().to_vec());
+        DUPLICATE_CTORS.write().unwrap().push(duplicate);
+    }
+
+    type_info.constructors.push(constructor);
 }
 
 /// Register a method for a given type
+/// - methods sharing a name are overloads of the name, resolved when called
 ///
 /// # Arguments
 /// - `method`: method to be added
 pub fn register_method<T: 'static>(method: Box<dyn Method>) {
-    let mut registry = TYPE_REGISTRY.lock().unwrap();
-    let short_name = type_shortname::<T>();
-
-    // get type associated with this method (or create type entry)
-    let type_info = registry.entry(short_name.clone()).or_insert_with(|| {
-        Arc::new(TypeInfo {
-            name: short_name,
-            objtype: TypeId::of::<T>(),
-            constructors: Vec::new(),
-            methods: HashMap::new(),
-            functions: HashMap::new()
-        })
-    });
+    let mut registry = TYPE_REGISTRY.write().unwrap();
+    let type_info = type_entry::<T>(&mut registry);
 
     let key = method.name().to_string();
-    Arc::make_mut(type_info).methods.insert(key, method);
+    type_info.methods.entry(key).or_default().push(method);
 }
 
 
 /// Register a static function for a given type
+/// - static functions sharing a name are overloads of the name, resolved when called
 ///
 /// # Arguments
 /// - `function`: function to be added
 pub fn register_function<T: 'static>(function: Box<dyn StaticFunction>) {
-    let mut registry = TYPE_REGISTRY.lock().unwrap();
-    let short_name = type_shortname::<T>();
-
-    // get type associated with this ctor (or create type entry)
-    let type_info = registry.entry(short_name.clone()).or_insert_with(|| {
-        Arc::new(TypeInfo {
-            name: short_name,
-            objtype: TypeId::of::<T>(),
-            constructors: Vec::new(),
-            methods: HashMap::new(),
-            functions: HashMap::new()
-        })
-    });
+    let mut registry = TYPE_REGISTRY.write().unwrap();
+    let type_info = type_entry::<T>(&mut registry);
 
     let key = function.name().to_string();
-    Arc::make_mut(type_info).functions.insert(key, function);
+    type_info.functions.entry(key).or_default().push(function);
+}
+
+
+/// Register the clone of a type, such that its objects can be cloned through reflection (see
+/// `TypeInfo::clone_object`)
+/// - registered by `#[reflect_impl(clone)]`
+pub fn register_clone<T: Clone + 'static>() {
+    let mut registry = TYPE_REGISTRY.write().unwrap();
+    let type_info = type_entry::<T>(&mut registry);
+    type_info.cloner = Some(clone_any::<T>);
+}
+
+
+/// Register the `Debug` formatting of a type, such that its objects can be formatted through
+/// reflection (see `TypeInfo::debug_string`)
+/// - registered by `#[reflect_impl(debug)]`
+pub fn register_debug<T: fmt::Debug + 'static>() {
+    let mut registry = TYPE_REGISTRY.write().unwrap();
+    let type_info = type_entry::<T>(&mut registry);
+    type_info.formatter = Some(debug_any::<T>);
+}
+
+
+/// Register the `PartialEq` comparison of a type, such that its objects can be compared through
+/// reflection (see `TypeInfo::objects_equal`)
+/// - registered by `#[reflect_impl(eq)]`
+pub fn register_eq<T: PartialEq + 'static>() {
+    let mut registry = TYPE_REGISTRY.write().unwrap();
+    let type_info = type_entry::<T>(&mut registry);
+    type_info.comparator = Some(eq_any::<T>);
+}
+
+
+// Clone an object of type `T`
+fn clone_any<T: Clone + 'static>(obj: &dyn Any) -> Option<Box<dyn Any>> {
+    obj.downcast_ref::<T>().map(|v| Box::new(v.clone()) as Box<dyn Any>)
+}
+
+
+// Format an object of type `T`
+fn debug_any<T: fmt::Debug + 'static>(obj: &dyn Any) -> Option<String> {
+    obj.downcast_ref::<T>().map(|v| format!("{:?}", v))
+}
+
+
+// Compare objects of type `T`
+fn eq_any<T: PartialEq + 'static>(a: &dyn Any, b: &dyn Any) -> Option<bool> {
+    Some(a.downcast_ref::<T>()? == b.downcast_ref::<T>()?)
+}
+
+
+// Get mutable type information for a given type (creating the type entry if new)
+// - `Arc::make_mut` only clones the type information if it is shared, i.e. has been handed
+//   out by `find_type` and is still held.  Registration at startup therefore accumulates
+//   members in place; registration thereafter is copy-on-write, such that holders of the prior
+//   type information see a consistent snapshot
+// - if the name is taken by another type, the type is registered under its fully qualified
+//   name instead, recording the collision
+fn type_entry<T: 'static>(registry: &mut HashMap<String, Arc<TypeInfo>>) -> &mut TypeInfo {
+    let objtype = TypeId::of::<T>();
+    let mut name = registered_name::<T>();
+    Conversions::add_type_name::<T>();
+
+    if registry.get(&name).is_some_and(|info| info.objtype != objtype) {
+        let qualified = qualified_name::<T>();
+        COLLISIONS.write().unwrap().push((name, qualified.clone()));
+        TYPE_NAMES.write().unwrap().insert(objtype, qualified.clone());
+        name = qualified;
+    }
+    QUALIFIED_NAMES.write().unwrap().insert(qualified_name::<T>(), name.clone());
+
+    let type_info = registry.entry(name.clone()).or_insert_with(|| Arc::new(TypeInfo::new(name, objtype)));
+    let type_info = Arc::make_mut(type_info);
+    type_info.plans.clear();
+    type_info
+}
+
+
+/// Register a free function (a function not associated with a type)
+///
+/// # Arguments
+/// - `function`: function to be added
+pub fn register_free_function(function: Box<dyn StaticFunction>) {
+    let mut registry = FUNCTION_REGISTRY.write().unwrap();
+    let key = function.name().to_string();
+    let functions = Arc::make_mut(&mut registry);
+    functions.plans.clear();
+    functions.functions.entry(key).or_default().push(function);
+}
+
+
+/// Call a free function by name
+///
+/// # Arguments
+/// - `name`: function name
+/// - `args`: arguments to function
+///
+/// # Returns
+/// - function result `Result<Box<dyn Any>, ReflectError>`
+pub fn call_function(name: &str, args: &[Box<dyn Any>]) -> Result<Box<dyn Any>, ReflectError> {
+    // release the lock before the call, in case the function itself calls through reflection
+    free_functions().callstatic(name, args)
+}
+
+
+/// Free functions, held as the static functions of a synthetic type
+pub(crate) fn free_functions() -> Arc<TypeInfo> {
+    FUNCTION_REGISTRY.read().unwrap().clone()
 }
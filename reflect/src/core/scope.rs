@@ -0,0 +1,178 @@
+//! Registries scoped to a closure on the current thread
+//! - types registered within a scope are visible only to lookups on the same thread, and only
+//!   until the scope ends, for example to register types for the evaluation of a single request
+//! - lookups (`lookup_type`, `find_type_by_id`, and so ctor expressions) consult the innermost
+//!   scope first, then enclosing scopes, then the global registry
+//! - conversions and free functions are not scoped, remaining global
+//!
+
+use std::any::TypeId;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+use crate::core::{Constructor, Method, StaticFunction, TypeInfo};
+use crate::core::conversions::readable_type_name;
+use crate::core::registration::{registered_name, global_type_by_id};
+
+
+thread_local! {
+    // types registered by scope, innermost scope last
+    static SCOPES: RefCell<Vec<HashMap<String, Arc<TypeInfo>>>> = const { RefCell::new(Vec::new()) };
+}
+
+
+/// Registry scoped to a call of `with_scope`
+/// - types registered with the scope shadow types registered globally (or in enclosing
+///   scopes) of the same name
+/// - registering members of a type registered globally extends a copy of the global type,
+///   leaving the global type as is
+/// - bound to the thread of `with_scope`, so cannot be handed to another thread
+pub struct Scope {
+    depth: usize,
+    _thread: PhantomData<*const ()>,
+}
+
+
+impl Scope {
+
+    /// Register a type without any ctors, methods, or static functions (see `register_type`)
+    ///
+    /// # Arguments
+    /// - `name`: name under which the type is registered, or `None` for its registered name
+    pub fn register_type<T: 'static> (&self, name: Option<&str>) {
+        self.update::<T>(name, |_| ());
+    }
+
+    /// Register a constructor for a given type
+    ///
+    /// # Arguments
+    /// - `constructor`: constructor to be added
+    pub fn register_constructor<T: 'static> (&self, constructor: Box<dyn Constructor>) {
+        self.update::<T>(None, |type_info| type_info.constructors.push(constructor));
+    }
+
+    /// Register a method for a given type
+    /// - methods sharing a name are overloads of the name, resolved when called
+    ///
+    /// # Arguments
+    /// - `method`: method to be added
+    pub fn register_method<T: 'static> (&self, method: Box<dyn Method>) {
+        let key = method.name().to_string();
+        self.update::<T>(None, |type_info| type_info.methods.entry(key).or_default().push(method));
+    }
+
+    /// Register a static function for a given type
+    /// - static functions sharing a name are overloads of the name, resolved when called
+    ///
+    /// # Arguments
+    /// - `function`: function to be added
+    pub fn register_function<T: 'static> (&self, function: Box<dyn StaticFunction>) {
+        let key = function.name().to_string();
+        self.update::<T>(None, |type_info| type_info.functions.entry(key).or_default().push(function));
+    }
+
+    /// Names of the types registered with this scope
+    ///
+    /// # Returns
+    /// - type names, sorted
+    pub fn registered_types (&self) -> Vec<String> {
+        SCOPES.with(|scopes| {
+            let mut names: Vec<String> = scopes.borrow()[self.depth].keys().cloned().collect();
+            names.sort();
+            names
+        })
+    }
+
+    // Update the type information of a type within this scope, creating it from the type as
+    // visible to the scope (or empty) if not yet registered with the scope
+    fn update<T: 'static> (&self, name: Option<&str>, f: impl FnOnce(&mut TypeInfo)) {
+        let objtype = TypeId::of::<T>();
+        let existing = SCOPES.with(|scopes| {
+            scopes.borrow()[self.depth].iter()
+                .find(|(_, info)| info.objtype == objtype)
+                .map(|(key, info)| (key.clone(), info.clone()))
+        });
+
+        let (key, mut type_info) = match existing {
+            Some(existing) => existing,
+            None => {
+                let name = registered_name::<T>();
+                let visible = scoped_type_by_id(objtype).or_else(|| global_type_by_id(objtype));
+                (name.clone(), visible.unwrap_or_else(|| Arc::new(TypeInfo::new(name, objtype))))
+            }
+        };
+
+        let type_info_mut = Arc::make_mut(&mut type_info);
+        type_info_mut.plans.clear();
+        if let Some(name) = name {
+            type_info_mut.name = name.to_string();
+        }
+        f(type_info_mut);
+
+        SCOPES.with(|scopes| {
+            let mut scopes = scopes.borrow_mut();
+            let registry = &mut scopes[self.depth];
+            registry.remove(&key);
+            registry.insert(type_info.name.clone(), type_info);
+        });
+    }
+}
+
+
+// Ends the scope when dropped, including when the closure of the scope panics
+struct ScopeGuard;
+
+impl Drop for ScopeGuard {
+    fn drop(&mut self) {
+        SCOPES.with(|scopes| scopes.borrow_mut().pop());
+    }
+}
+
+
+/// Call a closure with a registry scoped to the closure (see `Scope`)
+/// ```ignore
+///    let obj = reflect::with_scope(|scope| {
+///        scope.register_constructor::<Tenant>(Box::new(TenantConstructor::new(config)));
+///        CTorParser::create ("Tenant(42)")
+///    })?;
+/// ```
+///
+/// # Arguments
+/// - `f`: closure, given the scope in which to register types
+///
+/// # Returns
+/// - result of the closure; types registered with the scope are dropped thereafter
+pub fn with_scope<R> (f: impl FnOnce(&Scope) -> R) -> R {
+    let depth = SCOPES.with(|scopes| {
+        let mut scopes = scopes.borrow_mut();
+        scopes.push(HashMap::new());
+        scopes.len() - 1
+    });
+    let _guard = ScopeGuard;
+    f(&Scope { depth, _thread: PhantomData })
+}
+
+
+/// Find a type by name among the scopes of the current thread, innermost first
+/// - the name is the registered name, or the short name (without namespace)
+pub(crate) fn scoped_type(name: &str) -> Option<Arc<TypeInfo>> {
+    SCOPES.with(|scopes| {
+        scopes.borrow().iter().rev().find_map(|registry| {
+            registry.get(name).cloned().or_else(|| {
+                registry.iter().find(|(key, _)| readable_type_name(key) == name).map(|(_, info)| info.clone())
+            })
+        })
+    })
+}
+
+
+/// Find a type by type id among the scopes of the current thread, innermost first
+pub(crate) fn scoped_type_by_id(objtype: TypeId) -> Option<Arc<TypeInfo>> {
+    SCOPES.with(|scopes| {
+        scopes.borrow().iter().rev().find_map(|registry| {
+            registry.values().find(|info| info.objtype == objtype).cloned()
+        })
+    })
+}
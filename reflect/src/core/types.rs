@@ -1,16 +1,23 @@
 
-use std::any::{Any, TypeId};
+use std::any::{Any, TypeId, type_name};
 use std::collections::HashMap;
 use std::sync::Arc;
-use crate::{Constructor, Conversions, Method, StaticFunction};
+use crate::{Constructor, Conversions, ConvertedArg, Function, Method, StaticFunction, ReflectError, Selected, Signature};
+#[cfg(feature = "async")]
+use crate::AsyncResult;
+use crate::core::conversions::{is_exact_mode, readable_type_name};
+use crate::core::plans::{PlanCache, PlanKind};
+use crate::core::arguments::{Resolved, argv, converted_owned, find_best_arranged, find_viable, produced, resolve};
 
 
 /// Information about a type
 /// - name of type (short name as string)
 /// - type id `TypeId`
 /// - list of constructors
-/// - list of methods
-/// - list of functions
+/// - methods, by name (overloads of a name in order of registration)
+/// - static functions, by name (overloads of a name in order of registration)
+/// - cache of the conversions required to call functions, by argument types
+/// - operations on objects of the type, if registered (clone, debug, eq)
 ///
 /// In addition, there are methods to:
 /// - find type by name
@@ -22,11 +29,25 @@ pub struct TypeInfo {
     pub name: String,
     pub objtype: TypeId,
     pub constructors: Vec<Box<dyn Constructor>>,
-    pub methods: HashMap<String,Box<dyn Method>>,
-    pub functions: HashMap<String,Box<dyn StaticFunction>>,
+    pub methods: HashMap<String,Vec<Box<dyn Method>>>,
+    pub functions: HashMap<String,Vec<Box<dyn StaticFunction>>>,
+    pub(crate) plans: PlanCache,
+    pub(crate) cloner: Option<CloneFn>,
+    pub(crate) formatter: Option<DebugFn>,
+    pub(crate) comparator: Option<EqFn>,
 }
 
 
+/// Clone an object of a type, or `None` if the object is not of the type
+pub(crate) type CloneFn = fn(&dyn Any) -> Option<Box<dyn Any>>;
+
+/// Format an object of a type with `{:?}`, or `None` if the object is not of the type
+pub(crate) type DebugFn = fn(&dyn Any) -> Option<String>;
+
+/// Compare two objects of a type for equality, or `None` if either object is not of the type
+pub(crate) type EqFn = fn(&dyn Any, &dyn Any) -> Option<bool>;
+
+
 /// Reflection interface to a type in the reflection system
 /// - find type by name
 /// - create type given ctor arguments
@@ -34,6 +55,25 @@ pub struct TypeInfo {
 /// - call static functions on type
 impl TypeInfo {
 
+    /// Type information without ctors, methods, static functions, or operations
+    ///
+    /// # Arguments
+    /// - `name`: name under which the type is registered
+    /// - `objtype`: type id of the type
+    pub(crate) fn new (name: String, objtype: TypeId) -> TypeInfo {
+        TypeInfo {
+            name,
+            objtype,
+            constructors: Vec::new(),
+            methods: HashMap::new(),
+            functions: HashMap::new(),
+            plans: PlanCache::default(),
+            cloner: None,
+            formatter: None,
+            comparator: None
+        }
+    }
+
     /// Find type associated with name
     ///
     /// # Arguments
@@ -45,69 +85,282 @@ impl TypeInfo {
         crate::find_type (name)
     }
 
+    /// Names of the methods of this type, sorted
+    pub fn method_names (&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self.methods.keys().map(|k| k.as_str()).collect();
+        names.sort();
+        names
+    }
+
+    /// Names of the static functions of this type, sorted
+    pub fn static_names (&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self.functions.keys().map(|k| k.as_str()).collect();
+        names.sort();
+        names
+    }
+
+    /// Number of arguments of each ctor, in order of registration
+    pub fn constructor_arities (&self) -> Vec<usize> {
+        self.constructors.iter().map(|c| c.arg_types().len()).collect()
+    }
+
+    /// Signature of a method or static function
+    ///
+    /// # Arguments
+    /// - `name`: method or static function name (methods take precedence)
+    ///
+    /// # Returns
+    /// - signature (of the first registered, if overloaded) or `None` if there is no method or
+    ///   static function of that name
+    pub fn signature (&self, name: &str) -> Option<Signature> {
+        match self.methods.get(name).and_then(|m| m.first()) {
+            Some(m) => Some(m.signature()),
+            None => self.functions.get(name).and_then(|f| f.first()).map(|f| f.signature())
+        }
+    }
+
     /// Construct instance of this type given arguments
+    /// - the best matching ctor is called (see `Conversions::find_best_match`)
+    /// - if no ctor takes the arguments given, ctors with defaults for the remaining parameters
+    ///   are considered (see `Function::arg_defaults`)
     ///
     /// # Arguments
     /// - `args`: arguments to ctor
     ///
     /// # Returns
-    /// - new object instance (in the form of `Result<Box<dyn Any>, String>`)
-    pub fn create (&self, args: &[Box<dyn Any>]) -> Result<Box<dyn Any>, String> {
-        // find matching ctor (if any)
-        let ctor = match Conversions::find_best_match(&self.constructors, args) {
-            Some(c) => c,
-            None => return Err(format!("could not find ctor for {} arguments", args.len()))
-        };
-        let parameters = ctor.arg_types();
+    /// - new object instance (in the form of `Result<Box<dyn Any>, ReflectError>`)
+    pub fn create (&self, args: &[Box<dyn Any>]) -> Result<Box<dyn Any>, ReflectError> {
+        let (ctor, arguments) = self.resolve(PlanKind::Constructor, "*", Some(&self.constructors), args, None)?;
+        ctor.create (&argv(&arguments))
+    }
 
-        // see if immediate match of arguments
-        if ctor.matching(args) {
-            ctor.create (args)
-        }
-        // otherwise need to convert arguments to be compatible
-        else if Conversions::score (ctor.arg_types(), args) > 0 {
-            match Conversions::convert_argv(parameters, args) {
-                Some(newargs) => ctor.create (&newargs),
-                None => Err(format!("incompatible arguments for ctor"))
-            }
+    /// Construct instance of this type given arguments, taking ownership of the arguments
+    /// - as `create`, though arguments for container parameters (such as `Vec<f64>`) are moved
+    ///   into the ctor rather than cloned, for example for large vectors
+    ///
+    /// # Arguments
+    /// - `args`: arguments to ctor
+    ///
+    /// # Returns
+    /// - new object instance (in the form of `Result<Box<dyn Any>, ReflectError>`)
+    pub fn create_owned (&self, args: Vec<Box<dyn Any>>) -> Result<Box<dyn Any>, ReflectError> {
+        let (ctor, arguments) = self.resolve(PlanKind::Constructor, "*", Some(&self.constructors), &args, None)?;
+        let newargs = produced(arguments);
+        ctor.create_owned (converted_owned(args, newargs))
+    }
 
-        } else {
-            Err(format!("incompatible arguments for ctor"))
+    /// Construct instance of this type given arguments, along with the ctor selected
+    /// - as `create`, for observing which of the ctors is used
+    ///
+    /// # Arguments
+    /// - `args`: arguments to ctor
+    ///
+    /// # Returns
+    /// - new object instance and the ctor selected (see `Selected`)
+    pub fn create_selected (&self, args: &[Box<dyn Any>]) -> Result<(Box<dyn Any>, Selected), ReflectError> {
+        let (ctor, arguments) = self.resolve(PlanKind::Constructor, "*", Some(&self.constructors), args, None)?;
+        Ok((ctor.create (&argv(&arguments))?, Selected::new(ctor, args)))
+    }
+
+    /// Construct instance of this type given arguments, along with the arguments as passed to the
+    /// ctor after conversion
+    /// - for diagnosing which ctor was called and how its arguments were converted, for example
+    ///   that `3` was passed as `3.0`
+    /// - as `create`, parameters without an argument taking their default value (traced as
+    ///   passed as is)
+    ///
+    /// # Arguments
+    /// - `args`: arguments to ctor
+    ///
+    /// # Returns
+    /// - new object instance and the conversion of each argument (see `ConvertedArg`)
+    pub fn create_traced (&self, args: &[Box<dyn Any>]) -> Result<(Box<dyn Any>, Vec<ConvertedArg>), ReflectError> {
+        let (ctor, arguments) = self.resolve(PlanKind::Constructor, "*", Some(&self.constructors), args, None)?;
+        let trace = Conversions::trace(ctor.arg_types(), args);
+        Ok((ctor.create (&argv(&arguments))?, trace))
+    }
+
+    /// Construct instance of this type given arguments, failing if the best matching ctor is
+    /// ambiguous
+    /// - ctors are ambiguous if their scores are within `Conversions::AMBIGUITY_EPSILON` of the
+    ///   best score, where `create` would pick one by tie-breaking
+    ///
+    /// # Arguments
+    /// - `args`: arguments to ctor
+    ///
+    /// # Returns
+    /// - new object instance (in the form of `Result<Box<dyn Any>, ReflectError>`)
+    pub fn create_strict (&self, args: &[Box<dyn Any>]) -> Result<Box<dyn Any>, ReflectError> {
+        let matches = Conversions::find_best_matches(&self.constructors, args, Conversions::AMBIGUITY_EPSILON);
+        if matches.len() > 1 {
+            return Err(ReflectError::AmbiguousMatch {
+                type_name: self.name.clone(),
+                method: "*".to_string(),
+                candidates: matches.iter().map(|c| c.signature().to_string()).collect()
+            });
         }
+        self.create (args)
+    }
+
+    /// Construct instance of this type given arguments, downcast to `T`
+    ///
+    /// # Arguments
+    /// - `args`: arguments to ctor
+    ///
+    /// # Returns
+    /// - new object instance or an error if the ctor fails or the instance is not a `T`
+    pub fn create_as<T: 'static> (&self, args: &[Box<dyn Any>]) -> Result<Box<T>, ReflectError> {
+        downcast (self.create(args)?)
+    }
 
+    /// Construct instance of this type given arguments by name, in any order
+    /// - the best matching of the ctors with parameters of the given names is called (see
+    ///   `Conversions::find_best_match`), parameters without an argument taking their default
+    ///   value
+    ///
+    /// # Arguments
+    /// - `kwargs`: arguments to ctor, by parameter name
+    ///
+    /// # Returns
+    /// - new object instance (in the form of `Result<Box<dyn Any>, ReflectError>`)
+    pub fn create_kwargs (&self, kwargs: &[(String, Box<dyn Any>)]) -> Result<Box<dyn Any>, ReflectError> {
+        self.create_named (&[], kwargs)
+    }
+
+    /// Construct instance of this type given positional arguments followed by arguments by name
+    /// - as `create_kwargs`, with the positional arguments given for the leading parameters, and
+    ///   parameters without an argument taking their default value
+    ///
+    /// # Arguments
+    /// - `args`: positional arguments to ctor
+    /// - `kwargs`: remaining arguments to ctor, by parameter name
+    ///
+    /// # Returns
+    /// - new object instance (in the form of `Result<Box<dyn Any>, ReflectError>`)
+    pub(crate) fn create_named (&self, args: &[Box<dyn Any>], kwargs: &[(String, Box<dyn Any>)]) -> Result<Box<dyn Any>, ReflectError> {
+        match find_best_arranged (&self.constructors, args, kwargs, None)? {
+            Some((ctor, arguments)) => ctor.create (&argv(&arguments)),
+            None if is_exact_mode() => Err(self.no_exact_match("*", args, kwargs, &self.constructors)),
+            None => Err(self.no_matching_constructor(args.len() + kwargs.len()))
+        }
     }
 
     /// Call method by name
+    /// - the best matching of the methods of that name is called (see
+    ///   `Conversions::find_best_match`)
     ///
     /// # Arguments
     /// - `name`: method name
     /// - `args`: arguments to ctor
     ///
     /// # Returns
-    /// - method result `Result<Box<dyn Any>, String>`)
-    pub fn call (&self, obj: &Box<dyn Any>, name: &str, args: &[Box<dyn Any>]) -> Result<Box<dyn Any>, String> {
-        // find matching method
-        let method = match self.methods.get(name) {
-            Some(m) => m,
-            None => return Err(format!("could not find method: '{}'", name))
-        };
-        let parameters = method.arg_types();
+    /// - method result `Result<Box<dyn Any>, ReflectError>`)
+    pub fn call (&self, obj: &Box<dyn Any>, name: &str, args: &[Box<dyn Any>]) -> Result<Box<dyn Any>, ReflectError> {
+        let (method, arguments) = self.resolve(PlanKind::Method, name, self.methods.get(name), args, None)?;
+        method.call (obj, &argv(&arguments))
+    }
 
-        // see if immediate match of arguments
-        if method.matching(args) {
-            method.call(obj, args)
-        }
-        // otherwise need to convert arguments to be compatible
-        else if Conversions::score (parameters, args) > 0 {
-            match Conversions::convert_argv(parameters, args) {
-                Some(newargs) => method.call (obj, &newargs),
-                None => Err(format!("incompatible arguments for method: '{}'", name))
-            }
+    /// Call method by name, taking ownership of the arguments
+    /// - as `call`, though arguments for container parameters (such as `Vec<f64>`) are moved
+    ///   into the method rather than cloned, for example for large vectors
+    ///
+    /// # Arguments
+    /// - `name`: method name
+    /// - `args`: arguments to method
+    ///
+    /// # Returns
+    /// - method result `Result<Box<dyn Any>, ReflectError>`)
+    pub fn call_owned (&self, obj: &Box<dyn Any>, name: &str, args: Vec<Box<dyn Any>>) -> Result<Box<dyn Any>, ReflectError> {
+        let (method, arguments) = self.resolve(PlanKind::Method, name, self.methods.get(name), &args, None)?;
+        let newargs = produced(arguments);
+        method.call_owned (obj, converted_owned(args, newargs))
+    }
+
+    /// Call method by name, along with the method selected
+    /// - as `call`, for observing which of the overloads of the name is used
+    ///
+    /// # Arguments
+    /// - `name`: method name
+    /// - `args`: arguments to method
+    ///
+    /// # Returns
+    /// - method result and the method selected (see `Selected`)
+    pub fn call_selected (&self, obj: &Box<dyn Any>, name: &str, args: &[Box<dyn Any>]) -> Result<(Box<dyn Any>, Selected), ReflectError> {
+        let (method, arguments) = self.resolve(PlanKind::Method, name, self.methods.get(name), args, None)?;
+        Ok((method.call (obj, &argv(&arguments))?, Selected::new(method, args)))
+    }
+
+    /// Call method by name, downcasting the result to `T`
+    /// - of overloads taking the same parameters, the one returning `T` is preferred (for example
+    ///   `Option<f64>` for a method returning `Option<f64>`, see `Function::boxed_type`)
+    ///
+    /// # Arguments
+    /// - `name`: method name
+    /// - `args`: arguments to method
+    ///
+    /// # Returns
+    /// - method result or an error if the call fails or the result is not a `T`
+    pub fn call_as<T: 'static> (&self, obj: &Box<dyn Any>, name: &str, args: &[Box<dyn Any>]) -> Result<Box<T>, ReflectError> {
+        let returning = Some(TypeId::of::<T>());
+        let (method, arguments) = self.resolve(PlanKind::Method, name, self.methods.get(name), args, returning)?;
+        downcast (method.call (obj, &argv(&arguments))?)
+    }
+
+    /// Call method by name on a mutable object
+    /// - required for methods taking `&mut self`, but may be used for any method
+    ///
+    /// # Arguments
+    /// - `name`: method name
+    /// - `args`: arguments to method
+    ///
+    /// # Returns
+    /// - method result `Result<Box<dyn Any>, ReflectError>`)
+    pub fn call_mut (&self, obj: &mut Box<dyn Any>, name: &str, args: &[Box<dyn Any>]) -> Result<Box<dyn Any>, ReflectError> {
+        let (method, arguments) = self.resolve(PlanKind::Method, name, self.methods.get(name), args, None)?;
+        if method.is_mut() {
+            method.call_mut (obj, &argv(&arguments))
         } else {
-            Err(format!("incompatible arguments for method: '{}'", name))
+            method.call (obj, &argv(&arguments))
         }
     }
 
+    /// Call method by name, taking ownership of the object
+    /// - required for methods taking `self`, such as builder methods; other methods are
+    ///   called as by `call_mut`, dropping the object thereafter
+    ///
+    /// # Arguments
+    /// - `obj`: object on which to call the method
+    /// - `name`: method name
+    /// - `args`: arguments to method
+    ///
+    /// # Returns
+    /// - method result `Result<Box<dyn Any>, ReflectError>`
+    pub fn call_consuming (&self, obj: Box<dyn Any>, name: &str, args: &[Box<dyn Any>]) -> Result<Box<dyn Any>, ReflectError> {
+        let (method, arguments) = self.resolve(PlanKind::Method, name, self.methods.get(name), args, None)?;
+        method.call_consuming (obj, &argv(&arguments))
+    }
+
+    /// Call async method by name, returning the future of its result (feature `async`)
+    /// - required for async methods, but may be used for any method taking `&self`
+    /// - the method is resolved, and arguments converted, when called; failure to do so is
+    ///   surfaced when the future is awaited
+    ///
+    /// # Arguments
+    /// - `obj`: object on which to call the method
+    /// - `name`: method name
+    /// - `args`: arguments to method
+    ///
+    /// # Returns
+    /// - future of the method result `Result<Box<dyn Any>, ReflectError>`
+    #[cfg(feature = "async")]
+    pub fn call_async<'a> (&'a self, obj: &'a Box<dyn Any>, name: &str, args: &'a [Box<dyn Any>]) -> AsyncResult<'a> {
+        let resolved = self.resolve(PlanKind::Method, name, self.methods.get(name), args, None);
+        Box::pin(async move {
+            let (method, arguments) = resolved?;
+            method.call_async (obj, &argv(&arguments)).await
+        })
+    }
+
     /// Call method by name
     ///
     /// # Arguments
@@ -115,42 +368,210 @@ impl TypeInfo {
     /// - `args`: arguments to ctor
     ///
     /// # Returns
-    /// - method result `Result<Box<dyn Any>, String>`)
-    pub fn callstatic (&self, name: &str, args: &[Box<dyn Any>]) -> Result<Box<dyn Any>, String> {
-        // find matching static function
-        let function = match self.functions.get(name) {
-            Some(m) => m,
-            None => return Err(format!("could not find function: '{}'", name))
+    /// - method result `Result<Box<dyn Any>, ReflectError>`)
+    pub fn callstatic (&self, name: &str, args: &[Box<dyn Any>]) -> Result<Box<dyn Any>, ReflectError> {
+        let (function, arguments) = self.resolve(PlanKind::StaticFunction, name, self.functions.get(name), args, None)?;
+        function.call (&argv(&arguments))
+    }
+
+    /// Clone an object of this type
+    /// - requires the type to be `Clone`, with the clone registered by `#[reflect_impl(clone)]`
+    ///   (or `register_clone`)
+    ///
+    /// # Arguments
+    /// - `obj`: object to clone
+    ///
+    /// # Returns
+    /// - a copy of the object, or an error if the type has no registered clone or the object is
+    ///   not of this type
+    pub fn clone_object (&self, obj: &Box<dyn Any>) -> Result<Box<dyn Any>, ReflectError> {
+        let cloner = self.cloner.ok_or_else(|| self.unsupported("clone"))?;
+        cloner(obj.as_ref()).ok_or_else(|| self.not_of_type(obj))
+    }
+
+    /// Format an object of this type with `{:?}`, for example to log objects created from
+    /// configuration
+    /// - requires the type to be `Debug`, with the formatting registered by
+    ///   `#[reflect_impl(debug)]` (or `register_debug`)
+    ///
+    /// # Arguments
+    /// - `obj`: object to format
+    ///
+    /// # Returns
+    /// - debug representation of the object, or an error if the type has no registered
+    ///   formatting or the object is not of this type
+    pub fn debug_string (&self, obj: &Box<dyn Any>) -> Result<String, ReflectError> {
+        let formatter = self.formatter.ok_or_else(|| self.unsupported("debug"))?;
+        formatter(obj.as_ref()).ok_or_else(|| self.not_of_type(obj))
+    }
+
+    /// Compare two objects of this type for equality, for example to check that configuration
+    /// yields the same objects
+    /// - requires the type to be `PartialEq`, with the comparison registered by
+    ///   `#[reflect_impl(eq)]` (or `register_eq`)
+    ///
+    /// # Arguments
+    /// - `a`, `b`: objects to compare
+    ///
+    /// # Returns
+    /// - whether the objects are equal, or an error if the type has no registered comparison or
+    ///   either object is not of this type
+    pub fn objects_equal (&self, a: &Box<dyn Any>, b: &Box<dyn Any>) -> Result<bool, ReflectError> {
+        let comparator = self.comparator.ok_or_else(|| self.unsupported("eq"))?;
+        comparator(a.as_ref(), b.as_ref()).ok_or_else(|| {
+            let mismatched = if (**a).type_id() == self.objtype { b } else { a };
+            self.not_of_type(mismatched)
+        })
+    }
+
+    /// Check that some ctor can be called with arguments of the given types, without calling it
+    /// - arguments by name and parameters with defaults are arranged as by `create_kwargs`
+    ///
+    /// # Arguments
+    /// - `arg_types`: types of the positional arguments
+    /// - `kwarg_types`: types of the arguments by parameter name
+    ///
+    /// # Returns
+    /// - type of the object the ctor would create, or the error `create` would report
+    pub(crate) fn check_create (&self, arg_types: &[TypeId], kwarg_types: &[(String, TypeId)]) -> Result<TypeId, ReflectError> {
+        match find_viable (&self.constructors, arg_types, kwarg_types) {
+            Some(ctor) => Ok(ctor.boxed_type()),
+            None => Err(self.no_matching_constructor(arg_types.len() + kwarg_types.len()))
+        }
+    }
+
+    /// Check that some method of the name can be called with arguments of the given types,
+    /// without calling it
+    ///
+    /// # Returns
+    /// - type the method would return, or the error `call` would report
+    pub(crate) fn check_call (&self, name: &str, arg_types: &[TypeId]) -> Result<TypeId, ReflectError> {
+        self.check_function (name, self.methods.get(name), arg_types)
+    }
+
+    /// Check that some static function of the name can be called with arguments of the given
+    /// types, without calling it
+    ///
+    /// # Returns
+    /// - type the function would return, or the error `callstatic` would report
+    pub(crate) fn check_callstatic (&self, name: &str, arg_types: &[TypeId]) -> Result<TypeId, ReflectError> {
+        self.check_function (name, self.functions.get(name), arg_types)
+    }
+
+    // check that some method or static function of the name can be called with arguments of the
+    // given types
+    fn check_function<F: ?Sized + Function> (&self, name: &str, candidates: Option<&Vec<Box<F>>>, arg_types: &[TypeId]) -> Result<TypeId, ReflectError> {
+        let candidates = match candidates {
+            Some(candidates) => candidates,
+            None => return Err(self.method_not_found(name))
         };
-        let parameters = function.arg_types();
+        match find_viable (candidates, arg_types, &[]) {
+            Some(function) => Ok(function.boxed_type()),
+            None => Err(self.no_matching_method(name, arg_types.len(), candidates))
+        }
+    }
 
-        // see if immediate match of arguments
-        if function.matching(args) {
-            function.call(args)
+    // find the best of the ctors, or of the methods or static functions of a name, matching the
+    // arguments, and convert the arguments as required (see `arguments::resolve`)
+    fn resolve<'a, 'b, F: ?Sized + Function> (&self, kind: PlanKind, name: &str, candidates: Option<&'a Vec<Box<F>>>, args: &'b [Box<dyn Any>], returning: Option<TypeId>)
+        -> Result<Resolved<'a, 'b, F>, ReflectError> {
+        let candidates = match candidates {
+            Some(candidates) => candidates,
+            None => return Err(self.method_not_found(name))
+        };
+        match resolve (Some((&self.plans, kind, name)), candidates, args, returning)? {
+            Some(resolved) => Ok(resolved),
+            None if is_exact_mode() => Err(self.no_exact_match(name, args, &[], candidates)),
+            None => Err(match kind {
+                PlanKind::Constructor => self.no_matching_constructor(args.len()),
+                _ => self.no_matching_method(name, args.len(), candidates)
+            })
         }
-        // otherwise need to convert arguments to be compatible
-        else if Conversions::score (parameters, args) > 0 {
-            match Conversions::convert_argv(parameters, args) {
-                Some(newargs) => function.call (&newargs),
-                None => Err(format!("incompatible arguments for function: '{}'", name))
-            }
-        } else {
-            Err(format!("incompatible arguments for function: '{}'", name))
+    }
+
+    // error for ctors not matching arguments
+    fn no_matching_constructor (&self, arg_count: usize) -> ReflectError {
+        ReflectError::NoMatchingConstructor {
+            type_name: self.name.clone(),
+            arg_count,
+            candidates: self.constructors.iter().map(|c| c.signature().to_string()).collect()
+        }
+    }
+
+    // error for ctors (named "*"), methods, or static functions not taking the arguments as given
+    // - arguments by name are described as `name: type`
+    fn no_exact_match<F: ?Sized + Function> (&self, name: &str, args: &[Box<dyn Any>], kwargs: &[(String, Box<dyn Any>)], candidates: &[Box<F>]) -> ReflectError {
+        let positional = args.iter().map(|arg| Conversions::type_name((**arg).type_id()));
+        let by_name = kwargs.iter().map(|(name, arg)| format!("{}: {}", name, Conversions::type_name((**arg).type_id())));
+        ReflectError::NoExactMatch {
+            type_name: self.name.clone(),
+            method: name.to_string(),
+            arg_types: positional.chain(by_name).collect(),
+            candidates: candidates.iter().map(|c| c.signature().to_string()).collect()
         }
     }
 
+    // error for unknown method or static function
+    fn method_not_found (&self, name: &str) -> ReflectError {
+        ReflectError::MethodNotFound {
+            type_name: self.name.clone(),
+            method: name.to_string()
+        }
+    }
+
+    // error for an operation not registered for the type
+    fn unsupported (&self, operation: &str) -> ReflectError {
+        ReflectError::Unsupported {
+            type_name: self.name.clone(),
+            operation: operation.to_string()
+        }
+    }
+
+    // error for an object not of the type
+    fn not_of_type (&self, obj: &Box<dyn Any>) -> ReflectError {
+        ReflectError::DowncastFailed {
+            expected: self.name.clone(),
+            actual: Conversions::type_name((**obj).type_id())
+        }
+    }
+
+    // error for method or static function not matching arguments
+    fn no_matching_method<F: ?Sized + Function> (&self, name: &str, arg_count: usize, candidates: &[Box<F>]) -> ReflectError {
+        ReflectError::NoMatchingMethod {
+            type_name: self.name.clone(),
+            method: name.to_string(),
+            arg_count,
+            candidates: candidates.iter().map(|c| c.signature().to_string()).collect()
+        }
+    }
+
+}
+
+
+// Downcast the result of a reflected call to `T`
+pub(crate) fn downcast<T: 'static> (value: Box<dyn Any>) -> Result<Box<T>, ReflectError> {
+    let actual = (*value).type_id();
+    value.downcast::<T>().map_err(|_| ReflectError::DowncastFailed {
+        expected: readable_type_name(type_name::<T>()),
+        actual: Conversions::type_name(actual)
+    })
 }
 
 
-/// TypeInfo requires clone in order to use Arc::make_mut
+/// TypeInfo is cloned on write, by `Arc::make_mut` when registering members of a type handed out
+/// (see `type_entry`), such that readers keep their snapshot; the plan cache is not cloned
 impl Clone for TypeInfo {
     fn clone(&self) -> Self {
         TypeInfo {
             name: self.name.clone(),
             objtype: self.objtype,
             constructors: self.constructors.iter().map(|c| c.clone_boxed()).collect(),
-            methods: self.methods.iter().map(|(k, v)| (k.clone(), v.clone_boxed())).collect(),
-            functions: self.functions.iter().map(|(k, v)| (k.clone(), v.clone_boxed())).collect(),
+            methods: self.methods.iter().map(|(k, v)| (k.clone(), v.iter().map(|m| m.clone_boxed()).collect())).collect(),
+            functions: self.functions.iter().map(|(k, v)| (k.clone(), v.iter().map(|f| f.clone_boxed()).collect())).collect(),
+            plans: PlanCache::default(),
+            cloner: self.cloner,
+            formatter: self.formatter,
+            comparator: self.comparator,
         }
     }
 }
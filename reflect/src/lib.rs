@@ -10,51 +10,156 @@
 //! # Registering a Type
 //! Adding a type for reflection is accomplished as:
 //! ```
-//!    //#[reflect_type]
+//!    use reflect_macros::reflect_impl;
+//!
+//!    struct Test1 {
+//!        alpha: i32
+//!    }
+//!
+//!    #[reflect_impl]
 //!    impl Test1 {
 //!        fn new (a: i32) -> Self {
-//!            return Test1 { alpha: a };
+//!            Test1 { alpha: a }
 //!        }
 //!
 //!        fn f(&self, x: i32) -> i32 {
-//!            return x * self.alpha;
+//!            x * self.alpha
 //!        }
 //!    }
 //! ```
 //!
+//! Types and conversions are registered by `#[ctor::ctor]` functions, run before `main` in no
+//! particular order across crates.  Registration therefore never scores or resolves conversions;
+//! candidates are scored only when called, such that a conversion (for example `String` to an
+//! enum of another crate) may be registered before or after the types whose functions rely on
+//! it.  Conversions derived from others, such as that of a list of literals to an array, are
+//! registered with `Conversions::add_derived` and scored as the conversion they derive from when
+//! looked up.
+//!
 //! # Finding and Creating a Type
 //! The `TypeInfo` struct has functions and method for reflecting a given type.  Finding
 //! a type is accomplished as:
 //! ```
+//! # use reflect::TypeInfo;
+//! # use reflect_macros::reflect_impl;
+//! # use std::any::Any;
+//! # struct Test1 { alpha: i32 }
+//! # #[reflect_impl]
+//! # impl Test1 {
+//! #     fn new (a: i32) -> Self { Test1 { alpha: a } }
+//! #     fn f(&self, x: i32) -> i32 { x * self.alpha }
+//! # }
 //!    let itype = TypeInfo::find_type(&"Test1").expect("could not find type");
 //! ```
 //! One of the type's ctors can be invoked by matching an argument list with the signature
 //! of one of the ctors.  The object instance is created as:
 //! ```
+//! # use reflect::TypeInfo;
+//! # use reflect_macros::reflect_impl;
+//! # use std::any::Any;
+//! # struct Test1 { alpha: i32 }
+//! # #[reflect_impl]
+//! # impl Test1 {
+//! #     fn new (a: i32) -> Self { Test1 { alpha: a } }
+//! #     fn f(&self, x: i32) -> i32 { x * self.alpha }
+//! # }
+//! # let itype = TypeInfo::find_type(&"Test1").unwrap();
 //!    // create argv vector
 //!    let args_ctor = vec![Box::new(42i32) as Box<dyn Any>];
 //!    // find ctor and create obj
 //!    let obj = itype.create(&args_ctor).expect("failed to call ctor");
 //! ```
+//! Arguments may also be given by parameter name, in any order, with `create_kwargs`, or in a
+//! ctor expression as `Momentum(SMA, decay=0.9, windows=[200, 50])`.
+//!
+//! A list of ctors, such as `Ensemble([Momentum(SMA, [200]), Momentum(EMA, [50])])`, constructs
+//! each of its elements, which are received by a parameter of type `&[Box<dyn Any>]` (in order,
+//! each to be downcast to its type).
 //! # Calling methods on an object
 //! The `TypeInfo` struct has functions for calling methods and static functions.  A method is
 //! called as:
 //! ```
+//! # use reflect::TypeInfo;
+//! # use reflect_macros::reflect_impl;
+//! # use std::any::Any;
+//! # struct Test1 { alpha: i32 }
+//! # #[reflect_impl]
+//! # impl Test1 {
+//! #     fn new (a: i32) -> Self { Test1 { alpha: a } }
+//! #     fn f(&self, x: i32) -> i32 { x * self.alpha }
+//! # }
+//! # let itype = TypeInfo::find_type(&"Test1").unwrap();
+//! # let obj = itype.create(&[Box::new(42i32) as Box<dyn Any>]).unwrap();
 //!    // create argv vector
 //!    let argv = vec![Box::new(3i32) as Box<dyn Any>];
 //!    // call "f" method
-//!    let result = TypeInfo::call (obj, "f", argv);
+//!    let result = itype.call (&obj, "f", &argv);
+//! # assert_eq!(*result.unwrap().downcast_ref::<i32>().unwrap(), 126);
+//! ```
+//!
+//! # Scoped registration
+//! Types registered within `with_scope` are visible, on the current thread, only until the
+//! closure returns, shadowing types registered globally:
+//! ```ignore
+//!    let obj = reflect::with_scope(|scope| {
+//!        scope.register_constructor::<Tenant>(ctor);
+//!        CTorParser::create ("Tenant(42)")
+//!    })?;
+//! ```
+//!
+//! # Conversion policies
+//! With the `policy` feature, the conversion of arguments is ranked by a `ConversionPolicy`
+//! installed with `with_policy` on the current thread, in place of the registered scores, for
+//! example rejecting lossy conversions for a strict API while configuration is loaded leniently:
+//! ```ignore
+//!    let obj = reflect::with_policy(Lossless, || CTorParser::create ("Momentum(SMA, 0.9)"))?;
+//! ```
+//!
+//! Conversion may be disabled altogether with `set_exact_mode`, such that ctors, methods, and
+//! static functions are only called with arguments of their parameter types:
+//! ```ignore
+//!    reflect::set_exact_mode(true);
+//!    assert!(CTorParser::create ("Gauge(4)").is_err());     // i64 is not converted to f64
+//! ```
+//!
+//! # Creating from JSON
+//! With the `reflect_serde` feature, objects can be created from JSON configuration, where a
+//! ctor is given as `{ "type": "Momentum", "args": [...] }` or `{ "ctor": "Momentum(...)" }`:
+//! ```ignore
+//!    let obj = reflect::from_json ("Momentum", &config)?;
+//! ```
+//!
+//! # Creating from Python
+//! With the `pyo3` feature, the `PyReflect` python module (see `python::py_reflect`) creates
+//! reflected types and calls their methods from python:
+//! ```text
+//!    momentum = PyReflect.create("Momentum", "SMA", [200, 50, 20], 0.9)
+//!    value = PyReflect.call(momentum, "value", 42)
 //! ```
 //!
 
 
 mod core;
 mod parser;
+#[cfg(feature = "pyo3")]
+pub mod python;
 
-pub use core::{Constructor, Method, StaticFunction, Function};
+pub use core::{Constructor, Method, StaticFunction, Function, Selected, Signature};
+#[cfg(feature = "async")]
+pub use core::AsyncResult;
 pub use core::TypeInfo;
-pub use core::Conversions;
-pub use core::{register_constructor, register_method, register_function, find_type};
-pub use parser::CTorParser;
+pub use core::{Conversions, ConvertedArg, Null, Score, TupleValue, set_exact_mode, is_exact_mode};
+pub use core::ReflectError;
+pub use core::{register_constructor, register_method, register_function, find_type, find_type_by_id, create, registered_types, registry_summary};
+pub use core::{unregister, unregister_method, clear_registry};
+pub use core::{register_type, register_clone, register_debug, register_eq, register_type_name, registered_name, type_shortname, qualified_name, lookup_type, name_collisions, duplicate_constructors};
+pub use core::{register_free_function, call_function, FUNCTIONS_NAMESPACE};
+pub use core::{Scope, with_scope};
+#[cfg(feature = "policy")]
+pub use core::{ConversionPolicy, DefaultPolicy, with_policy};
+pub use core::TypeInfoBuilder;
+pub use parser::{CTorParser, CtorExpr};
+#[cfg(feature = "reflect_serde")]
+pub use parser::from_json;
 
 
@@ -0,0 +1,137 @@
+//! Abstract syntax tree for ctor expressions
+//! - the parsed form of an expression, independent of evaluation
+//! - allows expressions to be inspected (for example to check referenced types) before any
+//!   object is constructed
+//! - displays as canonical text, which parses to an equal expression
+//!
+
+use std::fmt;
+
+
+/// Parsed ctor expression
+/// - `Ctor`: a ctor expression such as `Momentum(SMA, [200, 50], 0.9)`
+/// - `Call`: a method called on the result of an expression, such as `Momentum(SMA).value(42)`
+/// - `Keyword`: an argument of a ctor given by parameter name, such as `windows=[200, 50]`,
+///   following any positional arguments of the ctor
+/// - the remaining variants are literal arguments (`Null` being the literal `null`)
+#[derive(Clone, Debug, PartialEq)]
+pub enum CtorExpr {
+    Ctor { name: String, args: Vec<CtorExpr> },
+    Call { target: Box<CtorExpr>, method: String, args: Vec<CtorExpr> },
+    Keyword { name: String, value: Box<CtorExpr> },
+    Int(i64),
+    Float(f64),
+    Bool(bool),
+    Null,
+    Str(String),
+    Char(char),
+    Ident(String),
+    List(Vec<CtorExpr>),
+    Tuple(Vec<CtorExpr>),
+    Map(Vec<(String, CtorExpr)>),
+}
+
+
+impl CtorExpr {
+
+    /// Names of all types referenced by ctors within this expression
+    /// - in order of appearance, outermost first; duplicates are retained
+    pub fn type_names(&self) -> Vec<&str> {
+        let mut names = Vec::new();
+        self.collect_type_names(&mut names);
+        names
+    }
+
+    // collect type names recursively
+    fn collect_type_names<'a>(&'a self, names: &mut Vec<&'a str>) {
+        match self {
+            CtorExpr::Ctor { name, args } => {
+                names.push(name);
+                for arg in args {
+                    arg.collect_type_names(names);
+                }
+            }
+            CtorExpr::Call { target, args, .. } => {
+                target.collect_type_names(names);
+                for arg in args {
+                    arg.collect_type_names(names);
+                }
+            }
+            CtorExpr::Keyword { value, .. } => value.collect_type_names(names),
+            CtorExpr::List(elements) => {
+                for element in elements {
+                    element.collect_type_names(names);
+                }
+            }
+            _ => ()
+        }
+    }
+}
+
+
+/// Canonical text of the expression, as it would be written
+/// - arguments and elements are separated by `", "`, and arguments by name written `name=value`
+/// - floats are written with the precision required to parse to the same value (non-finite
+///   floats have no literal)
+/// - strings and characters are quoted, escaping quotes, backslashes, newlines, and tabs
+impl fmt::Display for CtorExpr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CtorExpr::Ctor { name, args } => write!(f, "{}({})", name, Joined(args)),
+            CtorExpr::Call { target, method, args } => write!(f, "{}.{}({})", target, method, Joined(args)),
+            CtorExpr::Keyword { name, value } => write!(f, "{}={}", name, value),
+            CtorExpr::Int(v) => write!(f, "{}", v),
+            CtorExpr::Float(v) => write!(f, "{:?}", v),
+            CtorExpr::Bool(v) => write!(f, "{}", v),
+            CtorExpr::Null => write!(f, "null"),
+            CtorExpr::Str(s) => write!(f, "\"{}\"", escape(s, '"')),
+            CtorExpr::Char(c) => write!(f, "'{}'", escape(&c.to_string(), '\'')),
+            CtorExpr::Ident(s) => write!(f, "{}", s),
+            CtorExpr::List(elements) => write!(f, "[{}]", Joined(elements)),
+            CtorExpr::Tuple(elements) => write!(f, "({})", Joined(elements)),
+            CtorExpr::Map(entries) => {
+                write!(f, "{{")?;
+                for (index, (key, value)) in entries.iter().enumerate() {
+                    let separator = if index > 0 { ", " } else { "" };
+                    write!(f, "{}\"{}\": {}", separator, escape(key, '"'), value)?;
+                }
+                write!(f, "}}")
+            }
+        }
+    }
+}
+
+
+// Expressions displayed separated by ", "
+struct Joined<'a>(&'a [CtorExpr]);
+
+impl fmt::Display for Joined<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, expr) in self.0.iter().enumerate() {
+            if index > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}", expr)?;
+        }
+        Ok(())
+    }
+}
+
+
+// Escape text for quoting with `quote`, with the escapes understood by the grammar
+fn escape(text: &str, quote: char) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\t' => escaped.push_str("\\t"),
+            ch if ch == quote => {
+                escaped.push('\\');
+                escaped.push(ch);
+            }
+            ch => escaped.push(ch)
+        }
+    }
+    escaped
+}
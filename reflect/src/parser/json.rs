@@ -0,0 +1,103 @@
+//! Construction of objects from JSON values (feature `reflect_serde`)
+//! - a JSON value is mapped to a ctor expression AST, which is then evaluated as a parsed
+//!   ctor expression would be
+//!
+//! A ctor is given as a JSON object, either as a type and its arguments or as a ctor expression:
+//! ```text
+//!   { "type": "Momentum", "args": ["SMA", [200, 50, 20], [0.2, 0.3, 0.5]] }
+//!   { "ctor": "Momentum(SMA, [200, 50, 20], [0.2, 0.3, 0.5])" }
+//! ```
+//! Arguments map to the values of ctor expressions: integers to `i64`, other numbers to `f64`,
+//! arrays to `Vec`, strings to `String`, `null` to `Null`, and other objects to maps, such
+//! that the usual conversions apply.
+//!
+
+use std::any::Any;
+
+use serde_json::{Map, Value};
+
+use crate::{Conversions, CTorParser, ReflectError, lookup_type};
+use crate::parser::CtorExpr;
+
+
+/// Create an instance of a named type from a JSON value
+/// ```ignore
+///    let config = serde_json::json!({ "type": "Resample", "args": [{ "ctor": "Momentum(SMA, [200])" }, 300] });
+///    let obj = reflect::from_json ("Resample", &config)?;
+/// ```
+///
+/// # Arguments
+/// - `type_name`: name of the type to create
+/// - `value`: JSON object describing the ctor (see module description), or an array of the
+///   arguments to the ctor of `type_name`
+///
+/// # Returns
+/// - new object instance, or an error if the value does not describe a ctor, the ctor fails,
+///   or the object created is not of the named type (`DowncastFailed`)
+pub fn from_json(type_name: &str, value: &Value) -> Result<Box<dyn Any>, ReflectError> {
+    let itype = lookup_type(type_name)?;
+    let expr = match value {
+        Value::Array(args) => CtorExpr::Ctor { name: type_name.to_string(), args: arguments(args)? },
+        Value::Object(fields) if is_ctor(fields) => ctor(fields)?,
+        _ => return Err(ReflectError::ParseError(format!("expected a ctor of '{}' in JSON: {}", type_name, value)))
+    };
+
+    let obj = CTorParser::evaluate (&expr)?;
+    if (*obj).type_id() != itype.objtype {
+        return Err(ReflectError::DowncastFailed {
+            expected: itype.name.clone(),
+            actual: Conversions::type_name((*obj).type_id())
+        });
+    }
+    Ok(obj)
+}
+
+
+// determine whether a JSON object describes a ctor
+fn is_ctor(fields: &Map<String, Value>) -> bool {
+    fields.contains_key("type") || fields.contains_key("ctor")
+}
+
+
+// map a JSON object describing a ctor to its AST
+fn ctor(fields: &Map<String, Value>) -> Result<CtorExpr, ReflectError> {
+    match (fields.get("type"), fields.get("args"), fields.get("ctor")) {
+        (Some(Value::String(name)), None, None) =>
+            Ok(CtorExpr::Ctor { name: name.clone(), args: Vec::new() }),
+        (Some(Value::String(name)), Some(Value::Array(args)), None) =>
+            Ok(CtorExpr::Ctor { name: name.clone(), args: arguments(args)? }),
+        (None, None, Some(Value::String(expr))) =>
+            CTorParser::parse_expr (expr),
+        _ => Err(ReflectError::ParseError(format!("expected {{\"type\": name, \"args\": [...]}} or {{\"ctor\": expression}} in JSON: {}",
+            Value::Object(fields.clone()))))
+    }
+}
+
+
+// map JSON arguments to their ASTs
+fn arguments(args: &[Value]) -> Result<Vec<CtorExpr>, ReflectError> {
+    args.iter().map(argument).collect()
+}
+
+
+// map a JSON argument to its AST
+fn argument(value: &Value) -> Result<CtorExpr, ReflectError> {
+    match value {
+        Value::Null => Ok(CtorExpr::Null),
+        Value::Bool(v) => Ok(CtorExpr::Bool(*v)),
+        Value::Number(v) => match (v.as_i64(), v.as_f64()) {
+            (Some(i), _) => Ok(CtorExpr::Int(i)),
+            (None, Some(f)) => Ok(CtorExpr::Float(f)),
+            _ => Err(ReflectError::ParseError(format!("unsupported number in JSON: {}", v)))
+        },
+        Value::String(s) => Ok(CtorExpr::Str(s.clone())),
+        Value::Array(elements) => Ok(CtorExpr::List(arguments(elements)?)),
+        Value::Object(fields) if is_ctor(fields) => ctor(fields),
+        Value::Object(fields) => {
+            let entries = fields.iter()
+                .map(|(k, v)| argument(v).map(|v| (k.clone(), v)))
+                .collect::<Result<Vec<_>, _>>()?;
+            Ok(CtorExpr::Map(entries))
+        }
+    }
+}
@@ -3,6 +3,14 @@
 //! See main library lib.rs for a more comprehensive description
 
 
+mod ast;
+#[allow(clippy::module_inception)]
 mod parser;
+mod validation;
+#[cfg(feature = "reflect_serde")]
+mod json;
 
+pub use ast::CtorExpr;
 pub use parser::CTorParser;
+#[cfg(feature = "reflect_serde")]
+pub use json::from_json;
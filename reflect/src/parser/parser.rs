@@ -1,8 +1,13 @@
 use pest::{Parser};
 use pest_derive::Parser;
 use std::any::Any;
+use std::collections::HashMap;
 use std::vec::Vec;
 
+use crate::{Conversions, Null, TupleValue, ReflectError, create, find_type_by_id, call_function, lookup_type};
+use crate::core::downcast;
+use crate::parser::CtorExpr;
+
 
 // Define the parser struct using the grammar file
 #[derive(Parser)]
@@ -14,7 +19,7 @@ pub struct CTorParser;
 impl CTorParser {
 
     /// Create type based on ctor expression
-    /// ```
+    /// ```ignore
     ///    // create object based on ctor expression
     ///    let obj = CTorParser::create ("Resample(Momentum(SMA,[200,560,10],0.9), 300)");
     /// ```
@@ -23,118 +28,525 @@ impl CTorParser {
     /// - `expr`: ctor expression
     ///
     /// # Returns
-    /// - create object instance or None
-    pub fn create (expr: &str) -> Result<Box<dyn Any>,String> {
-        todo!()
+    /// - create object instance or an error describing why the expression could not be
+    ///   parsed or evaluated
+    pub fn create (expr: &str) -> Result<Box<dyn Any>,ReflectError> {
+        match Self::parse_expr (expr)? {
+            ctor @ CtorExpr::Ctor { .. } => Self::evaluate (&ctor),
+            _ => Err(ReflectError::ParseError(format!("not a ctor expression: '{}'", expr)))
+        }
+    }
+
+    /// Create type based on ctor expression and call a chain of methods on it
+    /// - each method is called on the result of the prior call
+    /// ```ignore
+    ///    // create object and call "value" on it
+    ///    let result = CTorParser::call ("Momentum(SMA,[200]).value(42)");
+    /// ```
+    ///
+    /// # Parameters
+    /// - `expr`: ctor expression followed by zero or more method calls
+    ///
+    /// # Returns
+    /// - result of the final method call (or the object if there are no calls) or an error
+    ///   describing why the expression could not be parsed or evaluated
+    pub fn call (expr: &str) -> Result<Box<dyn Any>,ReflectError> {
+        match Self::parse_expr (expr)? {
+            call @ (CtorExpr::Ctor { .. } | CtorExpr::Call { .. }) => Self::evaluate (&call),
+            _ => Err(ReflectError::ParseError(format!("not a call expression: '{}'", expr)))
+        }
+    }
+
+    /// Evaluate expression, downcasting the result to `T`
+    /// - the expression may be a ctor (or free function) expression, followed by zero or more
+    ///   method calls, or a literal
+    /// ```ignore
+    ///    let weight: f64 = CTorParser::eval ("blend(0.2, 0.8, 0.5)")?;
+    /// ```
+    ///
+    /// # Parameters
+    /// - `expr`: expression
+    ///
+    /// # Returns
+    /// - value of the expression or an error describing why the expression could not be parsed
+    ///   or evaluated, or `DowncastFailed` if the value is not a `T`
+    pub fn eval<T: 'static> (expr: &str) -> Result<T,ReflectError> {
+        let value = Self::evaluate (&Self::parse_expr (expr)?)?;
+        downcast::<T> (value).map(|v| *v)
+    }
+
+    /// Parse expression into its AST without evaluating it
+    /// ```ignore
+    ///    let ast = CTorParser::parse_expr ("Resample(Momentum(SMA,[200,560,10],0.9), 300)")?;
+    ///    assert_eq!(ast.type_names(), vec!["Resample", "Momentum"]);
+    /// ```
+    ///
+    /// # Parameters
+    /// - `expr`: ctor expression, call expression, or literal
+    ///
+    /// # Returns
+    /// - parsed expression or an error describing why the expression could not be parsed
+    pub fn parse_expr (expr: &str) -> Result<CtorExpr,ReflectError> {
+        Self::parse_rule (Rule::expression, expr)
+    }
+
+    /// Parse the value of an argument, such as the default value of a parameter
+    /// - as for an argument of a ctor, the value may be a ctor expression, list, tuple, map, or
+    ///   literal (evaluated with `evaluate`)
+    ///
+    /// # Parameters
+    /// - `expr`: argument expression
+    ///
+    /// # Returns
+    /// - parsed value or an error describing why the expression could not be parsed
+    pub(crate) fn parse_value (expr: &str) -> Result<CtorExpr,ReflectError> {
+        Self::parse_rule (Rule::value, expr)
     }
 
     // private implementation
 
-    /// Parse ctor
-    /// - parse each argument recursively
-    /// - create object
+    // parse expression according to the given rule of the grammar
+    fn parse_rule (rule: Rule, expr: &str) -> Result<CtorExpr,ReflectError> {
+        let tree = match CTorParser::parse(rule, expr) {
+            Ok(pairs) => pairs,
+            Err(e) => return Err(ReflectError::ParseError(Self::parse_error (&e)))
+        };
+
+        match tree.into_iter().next() {
+            Some(subtree) if subtree.as_rule() != Rule::EOI => Self::build (&subtree),
+            _ => Err(ReflectError::ParseError(format!("empty expression: '{}'", expr)))
+        }
+    }
+
+    /// Describe parse error
+    /// - position of the error as line / column
+    /// - the offending line with a caret under the character the parser choked on
     ///
     /// # Arguments
-    /// - `tree`: AST at current level
-    fn parse_ctor (tree: &pest::iterators::Pair<Rule>) -> Result<Box<dyn Any>,String> {
-        let mut argv_opt: Option<Vec::<Box<dyn Any>>> = None;
-        let mut ctor_opt: Option<&str> = None;
+    /// - `error`: pest parse error
+    fn parse_error (error: &pest::error::Error<Rule>) -> String {
+        let (line, column) = match error.line_col {
+            pest::error::LineColLocation::Pos(pos) => pos,
+            pest::error::LineColLocation::Span(start, _) => start,
+        };
+        Self::position_error ("failed to parse expression", line, column, &error.variant.message(), error.line())
+    }
 
-        for subtree in tree.clone().into_inner() {
-            match subtree.as_rule() {
-                Rule::identifier => {
-                    ctor_opt = Some(subtree.as_str());
+    /// Describe blank argument slot, such as in `Foo(1,,2)`
+    /// - position of the slot as line / column, as for parse errors
+    ///
+    /// # Arguments
+    /// - `tree`: (zero-width) parse tree of the slot
+    fn empty_slot_error (tree: &pest::iterators::Pair<Rule>) -> String {
+        let position = tree.as_span().start_pos();
+        let (line, column) = position.line_col();
+        Self::position_error ("empty argument", line, column, "expected an argument between commas",
+            position.line_of().trim_end_matches(['\r', '\n']))
+    }
+
+    // describe error at line / column, with the offending line and a caret under the column
+    fn position_error (what: &str, line: usize, column: usize, message: &str, text: &str) -> String {
+        let caret = format!("{}^", " ".repeat(column.saturating_sub(1)));
+        format!("{} at line {}, column {}: {}\n  {}\n  {}", what, line, column, message, text, caret)
+    }
+
+    /// Build AST
+    /// - build each sub-expression recursively
+    ///
+    /// # Arguments
+    /// - `tree`: parse tree at current level
+    fn build (tree: &pest::iterators::Pair<Rule>) -> Result<CtorExpr,ReflectError> {
+        match tree.as_rule() {
+            Rule::ctor_expression => {
+                // the ctor name is followed by the (flattened) argument list
+                let mut subtrees = tree.clone().into_inner();
+                let name = match subtrees.next() {
+                    Some(subtree) if matches!(subtree.as_rule(), Rule::identifier | Rule::qualified_name) => subtree.as_str().to_string(),
+                    _ => return Err(ReflectError::ParseError(format!("failed to parse ctor for: {}", tree.as_str())))
+                };
+                let args = subtrees.map(|arg| Self::build (&arg)).collect::<Result<Vec<_>,_>>()?;
+
+                // arguments by name follow the positional arguments
+                let positional = args.iter().rposition(|arg| !matches!(arg, CtorExpr::Keyword { .. }));
+                let keyword = args.iter().position(|arg| matches!(arg, CtorExpr::Keyword { .. }));
+                if let (Some(positional), Some(keyword)) = (positional, keyword) {
+                    if positional > keyword {
+                        return Err(ReflectError::ParseError(format!("positional argument follows argument by name in: {}", tree.as_str())));
+                    }
+                }
+                Ok(CtorExpr::Ctor { name, args })
+            }
+            Rule::call_expression => {
+                let mut subtrees = tree.clone().into_inner();
+                let mut expr = match subtrees.next() {
+                    Some(receiver) => Self::build (&receiver)?,
+                    None => return Err(ReflectError::ParseError(format!("missing receiver for: {}", tree.as_str())))
+                };
+
+                // each method is applied to the result of the prior expression
+                for call in subtrees {
+                    let mut parts = call.clone().into_inner();
+                    let method = match parts.next() {
+                        Some(name) => name.as_str().to_string(),
+                        None => return Err(ReflectError::ParseError(format!("failed to parse method call: {}", call.as_str())))
+                    };
+                    let args = parts.map(|arg| Self::build (&arg)).collect::<Result<Vec<_>,_>>()?;
+                    expr = CtorExpr::Call { target: Box::new(expr), method, args };
                 }
-                Rule::argument_list => {
-                    argv_opt = Self::parse_arguments (subtree.clone().into_inner());
+                Ok(expr)
+            }
+            Rule::keyword_argument => {
+                let mut parts = tree.clone().into_inner();
+                match (parts.next(), parts.next()) {
+                    (Some(name), Some(value)) => Ok(CtorExpr::Keyword {
+                        name: name.as_str().to_string(),
+                        value: Box::new(Self::build (&value)?)
+                    }),
+                    _ => Err(ReflectError::ParseError(format!("failed to parse argument: {}", tree.as_str())))
                 }
-                _ => ()
             }
+            Rule::identifier =>
+                Ok(CtorExpr::Ident(tree.as_str().to_string())),
+            Rule::string =>
+                Ok(CtorExpr::Str(Self::parse_string (tree))),
+            Rule::character =>
+                Ok(CtorExpr::Char(Self::parse_char (tree))),
+            Rule::integer => {
+                let s = tree.as_str();
+                match Self::parse_integer (s) {
+                    Ok(v) => Ok(CtorExpr::Int(v)),
+                    Err(e) => Err(ReflectError::ParseError(format!("invalid integer '{}': {}", s, e)))
+                }
+            }
+            Rule::float => {
+                let s = tree.as_str();
+                match str::parse::<f64>(&s.replace('_', "")) {
+                    Ok(v) => Ok(CtorExpr::Float(v)),
+                    Err(e) => Err(ReflectError::ParseError(format!("invalid float '{}': {}", s, e)))
+                }
+            }
+            Rule::boolean =>
+                Ok(CtorExpr::Bool(tree.as_str() == "true")),
+            Rule::null =>
+                Ok(CtorExpr::Null),
+            Rule::empty_slot =>
+                Err(ReflectError::ParseError(Self::empty_slot_error (tree))),
+            Rule::list => {
+                let elements = tree.clone().into_inner().map(|e| Self::build (&e)).collect::<Result<Vec<_>,_>>()?;
+                Ok(CtorExpr::List(elements))
+            }
+            Rule::tuple => {
+                let elements = tree.clone().into_inner().map(|e| Self::build (&e)).collect::<Result<Vec<_>,_>>()?;
+                Ok(CtorExpr::Tuple(elements))
+            }
+            Rule::map => {
+                let mut entries = Vec::new();
+                for entry in tree.clone().into_inner() {
+                    let mut parts = entry.into_inner();
+                    let (key, value) = match (parts.next(), parts.next()) {
+                        (Some(key), Some(value)) => (key, value),
+                        _ => return Err(ReflectError::ParseError(format!("failed to parse map: {}", tree.as_str())))
+                    };
+                    let key = match key.as_rule() {
+                        Rule::string => Self::parse_string (&key),
+                        _ => key.as_str().to_string()
+                    };
+                    entries.push((key, Self::build (&value)?));
+                }
+                Ok(CtorExpr::Map(entries))
+            }
+            rule =>
+                Err(ReflectError::ParseError(format!("unexpected {:?} in: {}", rule, tree.as_str())))
         }
+    }
+
 
-        match (ctor_opt, argv_opt) {
-            (None, _) =>
-                Err(format!("failed to parse ctor for: {}", tree.to_string())),
-            (_, None) =>
-                Err(format!("failed to parse arguments for: {}", tree.to_string())),
-            (Some(ctor), Some(argv)) => {
-                todo!()
+    /// Parse quoted string
+    /// - strips the quotes and resolves escape sequences
+    ///
+    /// # Arguments
+    /// - `tree`: AST for string
+    fn parse_string (tree: &pest::iterators::Pair<Rule>) -> String {
+        Self::unescape (tree.clone().into_inner().as_str())
+    }
+
+
+    /// Parse quoted character
+    /// - strips the quotes and resolves an escape sequence
+    ///
+    /// # Arguments
+    /// - `tree`: AST for character
+    fn parse_char (tree: &pest::iterators::Pair<Rule>) -> char {
+        // the grammar admits exactly one (possibly escaped) character
+        Self::unescape (tree.clone().into_inner().as_str()).chars().next().unwrap_or_default()
+    }
+
+
+    // Resolve escape sequences: \n and \t, otherwise the escaped character itself
+    fn unescape (raw: &str) -> String {
+        let mut s = String::with_capacity(raw.len());
+
+        let mut chars = raw.chars();
+        while let Some(ch) = chars.next() {
+            if ch != '\\' {
+                s.push(ch);
+                continue;
+            }
+            match chars.next() {
+                Some('n') => s.push('\n'),
+                Some('t') => s.push('\t'),
+                Some(escaped) => s.push(escaped),
+                None => ()
             }
         }
+
+        s
     }
 
 
-    /// Parse arguments
-    /// - parse each argument recursively
+    /// Parse integer literal
+    /// - decimal, or hexadecimal, octal, or binary given a `0x`, `0o`, or `0b` prefix, any of
+    ///   which may be negative
+    /// - digits may be separated by underscores, such as `1_000_000`
     ///
     /// # Arguments
-    /// - `tree`: AST at current level
-    fn parse_arguments (tree: pest::iterators::Pairs<Rule>) -> Option<Vec<Box<dyn Any>>> {
-        let mut argv = Vec::<Box<dyn Any>>::new();
-
-        for subtree in tree {
-            match subtree.as_rule() {
-                Rule::ctor_expression => {
-                    match Self::parse_ctor (&subtree) {
-                        Ok(obj) => argv.push(obj),
-                        Err(_) => return None
+    /// - `literal`: integer literal as matched by the grammar
+    fn parse_integer (literal: &str) -> Result<i64, std::num::ParseIntError> {
+        let (sign, digits) = match literal.strip_prefix('-') {
+            Some(digits) => ("-", digits),
+            None => ("", literal)
+        };
+        let (radix, digits) = match digits.get(..2) {
+            Some("0x") => (16, &digits[2..]),
+            Some("0o") => (8, &digits[2..]),
+            Some("0b") => (2, &digits[2..]),
+            _ => (10, digits)
+        };
+        i64::from_str_radix(&format!("{}{}", sign, digits.replace('_', "")), radix)
+    }
+
+
+    /// Evaluate AST
+    /// - evaluate each argument recursively
+    /// - create objects / call methods
+    /// - a ctor whose name is not a registered type is called as a free function
+    ///
+    /// # Arguments
+    /// - `expr`: AST at current level
+    pub(crate) fn evaluate (expr: &CtorExpr) -> Result<Box<dyn Any>,ReflectError> {
+        match expr {
+            CtorExpr::Ctor { name, args } => {
+                let mut argv = Vec::with_capacity(args.len());
+                let mut kwargv = Vec::new();
+                for arg in args {
+                    match arg {
+                        CtorExpr::Keyword { name, value } =>
+                            kwargv.push((name.clone(), Self::evaluate (value).map_err(|e| Self::within (arg, e))?)),
+                        _ => argv.push(Self::evaluate (arg).map_err(|e| Self::within (arg, e))?)
                     }
                 }
-                Rule::identifier => {
-                    argv.push(Box::new(subtree.to_string()) as Box<dyn Any>);
-                }
-                Rule::integer => {
-                    let s = subtree.as_str();
-                    let v: i64 = str::parse::<i64>(s).unwrap();
-                    argv.push (Box::new(v));
-                }
-                Rule::float => {
-                    let s = subtree.as_str();
-                    let v = str::parse::<f64>(s).unwrap();
-                    argv.push (Box::new(v));
+
+                // arguments by name are arranged by the parameter names of the ctors
+                if !kwargv.is_empty() {
+                    return lookup_type(name)?.create_named(&argv, &kwargv);
                 }
-                Rule::list => {
-                    argv.push (Self::parse_list (&subtree.into_inner()));
+                match create(name, &argv) {
+                    // fall back to a free function of that name
+                    Err(ReflectError::TypeNotFound(_)) => match call_function(name, &argv) {
+                        Err(ReflectError::MethodNotFound { .. }) => Err(ReflectError::TypeNotFound(name.clone())),
+                        result => result
+                    },
+                    result => result
                 }
-                _ => ()
             }
+            CtorExpr::Call { target, method, args } => {
+                let receiver = Self::evaluate (target).map_err(|e| Self::within (target, e))?;
+                let argv = Self::evaluate_arguments (args)?;
+                let itype = match find_type_by_id((*receiver).type_id()) {
+                    Some(t) => t,
+                    None => return Err(ReflectError::TypeNotFound(Conversions::type_name((*receiver).type_id())))
+                };
+                itype.call_consuming(receiver, method, &argv)
+            }
+            CtorExpr::Keyword { name, .. } =>
+                Err(ReflectError::ParseError(format!("argument '{}' given by name outside of a ctor", name))),
+            CtorExpr::Int(v) => Ok(Box::new(*v)),
+            CtorExpr::Float(v) => Ok(Box::new(*v)),
+            CtorExpr::Bool(v) => Ok(Box::new(*v)),
+            CtorExpr::Null => Ok(Box::new(Null)),
+            CtorExpr::Str(s) | CtorExpr::Ident(s) => Ok(Box::new(s.clone())),
+            CtorExpr::Char(c) => Ok(Box::new(*c)),
+            CtorExpr::List(elements) => Self::evaluate_list (elements),
+            CtorExpr::Tuple(elements) => Ok(Box::new(TupleValue(Self::evaluate_arguments (elements)?))),
+            CtorExpr::Map(entries) => Self::evaluate_map (entries),
         }
+    }
 
-        Some(argv)
+
+    /// Evaluate arguments
+    /// - evaluate each argument recursively
+    /// - errors identify the failing argument (see `within`)
+    ///
+    /// # Arguments
+    /// - `args`: argument ASTs
+    fn evaluate_arguments (args: &[CtorExpr]) -> Result<Vec<Box<dyn Any>>,ReflectError> {
+        args.iter().map(|arg| Self::evaluate (arg).map_err(|e| Self::within (arg, e))).collect()
     }
 
 
-    /// Parse arguments
-    /// - parse each argument recursively
+    /// Identify the sub-expression whose evaluation failed
+    /// - errors already identifying a (more deeply nested) sub-expression are retained
     ///
     /// # Arguments
-    /// - `tree`: AST at current level
-    fn parse_list (tree: &pest::iterators::Pairs<Rule>) -> Box<dyn Any> {
-        let mut fvec = Vec::<f64>::new();
-        let mut ivec = Vec::<i32>::new();
+    /// - `expr`: failing sub-expression
+    /// - `error`: error evaluating the sub-expression
+    pub(crate) fn within (expr: &CtorExpr, error: ReflectError) -> ReflectError {
+        match error {
+            ReflectError::InExpression { .. } => error,
+            _ => ReflectError::InExpression { expression: expr.to_string(), error: Box::new(error) }
+        }
+    }
 
-        for subtree in tree.clone() {
-            match subtree.as_rule() {
-                Rule::integer => {
-                    let s = subtree.as_str();
-                    let v = str::parse::<i32>(s).unwrap();
-                    ivec.push (v);
-                    fvec.push (v as f64);
-                }
-                Rule::float => {
-                    let s = subtree.as_str();
-                    let v = str::parse::<f64>(s).unwrap();
-                    fvec.push (v);
-                    ivec.clear();
-                }
-                _ => ()
+
+    /// Evaluate map
+    /// - infer the value type, yielding `HashMap<String, i32>`, `HashMap<String, f64>`, or
+    ///   `HashMap<String, String>` (for identifiers and/or strings), as for lists
+    ///
+    /// # Arguments
+    /// - `entries`: map keys and value ASTs
+    fn evaluate_map (entries: &[(String, CtorExpr)]) -> Result<Box<dyn Any>,ReflectError> {
+        let values: Vec<CtorExpr> = entries.iter().map(|(_, v)| v.clone()).collect();
+        let values = Self::evaluate_list (&values)?;
+
+        fn zip<T: 'static> (entries: &[(String, CtorExpr)], values: Vec<T>) -> Box<dyn Any> {
+            let map: HashMap<String, T> = entries.iter().map(|(k, _)| k.clone()).zip(values).collect();
+            Box::new(map)
+        }
+        let values = match values.downcast::<Vec<i32>>() {
+            Ok(ivec) => return Ok(zip (entries, *ivec)),
+            Err(values) => values
+        };
+        let values = match values.downcast::<Vec<f64>>() {
+            Ok(fvec) => return Ok(zip (entries, *fvec)),
+            Err(values) => values
+        };
+        match values.downcast::<Vec<String>>() {
+            Ok(svec) => Ok(zip (entries, *svec)),
+            Err(_) => Err(ReflectError::ParseError("maps may only contain numbers, identifiers, or strings".to_string()))
+        }
+    }
+
+
+    /// Evaluate list
+    /// - infer the element type, yielding `Vec<f64>` if any element is a float, otherwise
+    ///   `Vec<i32>`, or `Vec<i64>` if any element is beyond the range of `i32`
+    /// - a list of identifiers and/or strings yields `Vec<String>`
+    /// - a list of lists yields `Vec<Vec<i32>>`, `Vec<Vec<i64>>`, or `Vec<Vec<f64>>`
+    /// - a list of ctors yields the objects constructed, as `Vec<Box<dyn Any>>`
+    ///
+    /// # Arguments
+    /// - `elements`: list element ASTs
+    fn evaluate_list (elements: &[CtorExpr]) -> Result<Box<dyn Any>,ReflectError> {
+        let count = |f: fn(&CtorExpr) -> bool| elements.iter().filter(|e| f(e)).count();
+        let objects = count(|e| matches!(e, CtorExpr::Ctor { .. }));
+        let nested = count(|e| matches!(e, CtorExpr::List(_)));
+        let textual = count(|e| matches!(e, CtorExpr::Ident(_) | CtorExpr::Str(_)));
+        let numeric = count(|e| matches!(e, CtorExpr::Int(_) | CtorExpr::Float(_)));
+
+        if objects + nested + textual + numeric != elements.len() {
+            return Err(ReflectError::ParseError("lists may only contain numbers, identifiers, strings, ctors, or lists".to_string()));
+        }
+        if objects > 0 {
+            return if objects == elements.len() {
+                Ok(Box::new(Self::evaluate_arguments (elements)?) as Box<dyn Any>)
+            } else {
+                Err(ReflectError::ParseError("cannot mix ctors and other elements within a list".to_string()))
+            }
+        }
+        if nested > 0 {
+            return if nested == elements.len() {
+                Self::evaluate_nested_list (elements)
+            } else {
+                Err(ReflectError::ParseError("cannot mix lists and scalars within a list".to_string()))
+            }
+        }
+        if textual > 0 {
+            return if textual == elements.len() {
+                let svec: Vec<String> = elements.iter().filter_map(|e| match e {
+                    CtorExpr::Ident(s) | CtorExpr::Str(s) => Some(s.clone()),
+                    _ => None
+                }).collect();
+                Ok(Box::new(svec) as Box<dyn Any>)
+            } else {
+                Err(ReflectError::ParseError("cannot mix numbers and identifiers / strings within a list".to_string()))
+            }
+        }
+
+        // any float yields Vec<f64>, otherwise integers yield Vec<i32> unless beyond its range
+        if elements.iter().any(|e| matches!(e, CtorExpr::Float(_))) {
+            let fvec: Vec<f64> = elements.iter().filter_map(|e| match e {
+                CtorExpr::Int(v) => Some(*v as f64),
+                CtorExpr::Float(v) => Some(*v),
+                _ => None
+            }).collect();
+            return Ok(Box::new(fvec) as Box<dyn Any>);
+        }
+
+        let lvec: Vec<i64> = elements.iter().filter_map(|e| match e {
+            CtorExpr::Int(v) => Some(*v),
+            _ => None
+        }).collect();
+        match lvec.iter().map(|v| i32::try_from(*v)).collect::<Result<Vec<i32>,_>>() {
+            Ok(ivec) => Ok(Box::new(ivec) as Box<dyn Any>),
+            Err(_) => Ok(Box::new(lvec) as Box<dyn Any>)
+        }
+    }
+
+
+    /// Evaluate list of lists
+    /// - if all rows are `Vec<i32>` yields `Vec<Vec<i32>>`, otherwise if all rows are integers
+    ///   yields `Vec<Vec<i64>>`
+    /// - if any row is a `Vec<f64>`, all rows are promoted to `Vec<f64>`
+    ///
+    /// # Arguments
+    /// - `elements`: list element ASTs, each of which is a list
+    fn evaluate_nested_list (elements: &[CtorExpr]) -> Result<Box<dyn Any>,ReflectError> {
+        let mut rows = Vec::<Box<dyn Any>>::new();
+        for element in elements {
+            match element {
+                CtorExpr::List(row) => rows.push(Self::evaluate_list (row)?),
+                _ => return Err(ReflectError::ParseError("expected a list of lists".to_string()))
             }
         }
 
-        if fvec.len() > ivec.len() {
-            Box::new(fvec) as Box<dyn Any>
-        } else{
-            Box::new(ivec) as Box<dyn Any>
+        if rows.iter().all(|row| row.is::<Vec<i32>>()) {
+            let matrix: Vec<Vec<i32>> = rows.into_iter()
+                .map(|row| *row.downcast::<Vec<i32>>().unwrap())
+                .collect();
+            Ok(Box::new(matrix) as Box<dyn Any>)
+        } else if rows.iter().all(|row| row.is::<Vec<i32>>() || row.is::<Vec<i64>>()) {
+            let mut matrix = Vec::<Vec<i64>>::new();
+            for row in rows {
+                match row.downcast::<Vec<i64>>() {
+                    Ok(lrow) => matrix.push(*lrow),
+                    Err(row) => matrix.push(row.downcast_ref::<Vec<i32>>().unwrap().iter().map(|v| *v as i64).collect())
+                }
+            }
+            Ok(Box::new(matrix) as Box<dyn Any>)
+        } else {
+            let mut matrix = Vec::<Vec<f64>>::new();
+            for row in rows {
+                if let Some(irow) = row.downcast_ref::<Vec<i32>>() {
+                    matrix.push(irow.iter().map(|v| *v as f64).collect());
+                } else if let Some(lrow) = row.downcast_ref::<Vec<i64>>() {
+                    matrix.push(lrow.iter().map(|v| *v as f64).collect());
+                } else if let Some(frow) = row.downcast_ref::<Vec<f64>>() {
+                    matrix.push(frow.clone());
+                } else {
+                    return Err(ReflectError::ParseError("nested lists must contain numbers".to_string()));
+                }
+            }
+            Ok(Box::new(matrix) as Box<dyn Any>)
         }
     }
 
@@ -155,18 +567,44 @@ mod tests {
                     print_expression(inner_pair, indent + 1);
                 }
             }
-            Rule::identifier =>
+            Rule::identifier | Rule::qualified_name =>
                 println!("{}Identifier: {}", indent_str, pair.as_str()),
             Rule::integer =>
                 println!("{}Integer: {}", indent_str, pair.as_str()),
             Rule::float =>
                 println!("{}Float: {}", indent_str, pair.as_str()),
+            Rule::string =>
+                println!("{}String: {}", indent_str, pair.as_str()),
+            Rule::character =>
+                println!("{}Char: {}", indent_str, pair.as_str()),
+            Rule::boolean =>
+                println!("{}Boolean: {}", indent_str, pair.as_str()),
+            Rule::null =>
+                println!("{}Null", indent_str),
+            Rule::map | Rule::map_entry => {
+                println!("{}Map:", indent_str);
+                for inner_pair in pair.into_inner() {
+                    print_expression(inner_pair, indent + 1);
+                }
+            }
+            Rule::tuple => {
+                println!("{}Tuple:", indent_str);
+                for inner_pair in pair.into_inner() {
+                    print_expression(inner_pair, indent + 1);
+                }
+            }
             Rule::list => {
                 println!("{}List:", indent_str);
                 for inner_pair in pair.into_inner() {
                     print_expression(inner_pair, indent + 1);
                 }
             }
+            Rule::call_expression | Rule::method_call => {
+                println!("{}Call:", indent_str);
+                for inner_pair in pair.into_inner() {
+                    print_expression(inner_pair, indent + 1);
+                }
+            }
             Rule::argument_list => {
                 println!("{}Arguments:", indent_str);
                 for inner_pair in pair.into_inner() {
@@ -189,4 +627,247 @@ mod tests {
             Err(e) => eprintln!("Parsing error: {:?}", e),
         }
     }
+
+    // parse the arguments of a (top-level) ctor expression without evaluating the ctor
+    fn parse_ctor_arguments(input: &str) -> Vec<CtorExpr> {
+        match CTorParser::parse_expr(input).unwrap() {
+            CtorExpr::Ctor { args, .. } => args,
+            other => panic!("not a ctor: {:?}", other)
+        }
+    }
+
+    #[test]
+    fn test_parse_string() {
+        let argv = parse_ctor_arguments("LoadData(\"/tmp/my file.csv\", 100, SMA)");
+        assert_eq!(argv, vec![
+            CtorExpr::Str("/tmp/my file.csv".to_string()),
+            CtorExpr::Int(100),
+            CtorExpr::Ident("SMA".to_string())
+        ]);
+
+        let argv = parse_ctor_arguments(r#"Label("a, \"b\"\\c\n\td")"#);
+        assert_eq!(argv, vec![CtorExpr::Str("a, \"b\"\\c\n\td".to_string())]);
+    }
+
+    #[test]
+    fn test_parse_char() {
+        let argv = parse_ctor_arguments(r#"Split('x', '\t', '\'', '\\', '"', ' ')"#);
+        assert_eq!(argv, vec![
+            CtorExpr::Char('x'),
+            CtorExpr::Char('\t'),
+            CtorExpr::Char('\''),
+            CtorExpr::Char('\\'),
+            CtorExpr::Char('"'),
+            CtorExpr::Char(' ')
+        ]);
+
+        // exactly one character
+        assert!(CTorParser::parse_expr("Split('')").is_err());
+        assert!(CTorParser::parse_expr("Split('ab')").is_err());
+    }
+
+    #[test]
+    fn test_parse_numbers() {
+        let argv = parse_ctor_arguments("Shift(-5, 1.2e-3, -0.0, 3E10, .5, -2.5E+2)");
+        assert_eq!(argv, vec![
+            CtorExpr::Int(-5),
+            CtorExpr::Float(1.2e-3),
+            CtorExpr::Float(-0.0),
+            CtorExpr::Float(3E10),
+            CtorExpr::Float(0.5),
+            CtorExpr::Float(-250.0)
+        ]);
+
+        match argv[2] {
+            CtorExpr::Float(v) => assert!(v.is_sign_negative()),
+            _ => panic!("expected float")
+        }
+    }
+
+    #[test]
+    fn test_parse_digit_separators() {
+        let argv = parse_ctor_arguments("Window(1_000, 1_000.5, -1_000_000, 0xFF_FF, 1e1_0, 0.000_5)");
+        assert_eq!(argv, vec![
+            CtorExpr::Int(1000),
+            CtorExpr::Float(1000.5),
+            CtorExpr::Int(-1_000_000),
+            CtorExpr::Int(0xFFFF),
+            CtorExpr::Float(1e10),
+            CtorExpr::Float(0.0005)
+        ]);
+
+        // underscores only between digits
+        assert!(CTorParser::parse_expr("Window(1_)").is_err());
+        assert!(CTorParser::parse_expr("Window(1__000)").is_err());
+        assert!(CTorParser::parse_expr("Window(1_.5)").is_err());
+        assert!(CTorParser::parse_expr("Window(0x_FF)").is_err());
+    }
+
+    #[test]
+    fn test_parse_radix() {
+        let argv = parse_ctor_arguments("Mask(0xFF, 0x1f, 0o17, 0b1010, -0x10, 10)");
+        assert_eq!(argv, vec![
+            CtorExpr::Int(255),
+            CtorExpr::Int(31),
+            CtorExpr::Int(15),
+            CtorExpr::Int(10),
+            CtorExpr::Int(-16),
+            CtorExpr::Int(10)
+        ]);
+
+        // beyond i32, but within i64
+        assert_eq!(parse_ctor_arguments("Mask(0xDEADBEEF)"), vec![CtorExpr::Int(0xDEADBEEF)]);
+        assert!(CTorParser::parse_expr("Mask(0xFFFFFFFFFFFFFFFF)").is_err());
+
+        // digits outside of the radix
+        assert!(CTorParser::parse_expr("Mask(0b102)").is_err());
+        assert!(CTorParser::parse_expr("Mask(0o8)").is_err());
+        assert!(CTorParser::parse_expr("Mask(0x)").is_err());
+    }
+
+    #[test]
+    fn test_parse_boolean() {
+        let argv = parse_ctor_arguments("Smoother(true, false, trueish)");
+        assert_eq!(argv, vec![
+            CtorExpr::Bool(true),
+            CtorExpr::Bool(false),
+            CtorExpr::Ident("trueish".to_string())
+        ]);
+    }
+
+    #[test]
+    fn test_parse_no_arguments() {
+        assert_eq!(parse_ctor_arguments("Origin()"), vec![]);
+        assert_eq!(parse_ctor_arguments("Origin( )"), vec![]);
+
+        let ast = CTorParser::parse_expr("Origin().norm()").unwrap();
+        assert_eq!(ast, CtorExpr::Call {
+            target: Box::new(CtorExpr::Ctor { name: "Origin".to_string(), args: vec![] }),
+            method: "norm".to_string(),
+            args: vec![]
+        });
+    }
+
+    #[test]
+    fn test_parse_keyword_arguments() {
+        let argv = parse_ctor_arguments("Momentum(SMA, windows = [200, 50], decay=0.9)");
+        assert_eq!(argv, vec![
+            CtorExpr::Ident("SMA".to_string()),
+            CtorExpr::Keyword {
+                name: "windows".to_string(),
+                value: Box::new(CtorExpr::List(vec![CtorExpr::Int(200), CtorExpr::Int(50)]))
+            },
+            CtorExpr::Keyword { name: "decay".to_string(), value: Box::new(CtorExpr::Float(0.9)) }
+        ]);
+        assert_eq!(argv[1].to_string(), "windows=[200, 50]");
+
+        assert!(CTorParser::parse_expr("Momentum(decay=0.9, SMA)").is_err());
+        assert!(CTorParser::parse_expr("Momentum(SMA).value(x=1)").is_err());
+    }
+
+    #[test]
+    fn test_parse_null() {
+        let argv = parse_ctor_arguments("Smoother(null, nullable)");
+        assert_eq!(argv, vec![
+            CtorExpr::Null,
+            CtorExpr::Ident("nullable".to_string())
+        ]);
+    }
+
+    #[test]
+    fn test_parse_expr() {
+        let ast = CTorParser::parse_expr("Resample(Momentum(SMA,[200,560],0.9), 300).value(1)").unwrap();
+        assert_eq!(ast, CtorExpr::Call {
+            target: Box::new(CtorExpr::Ctor {
+                name: "Resample".to_string(),
+                args: vec![
+                    CtorExpr::Ctor {
+                        name: "Momentum".to_string(),
+                        args: vec![
+                            CtorExpr::Ident("SMA".to_string()),
+                            CtorExpr::List(vec![CtorExpr::Int(200), CtorExpr::Int(560)]),
+                            CtorExpr::Float(0.9)
+                        ]
+                    },
+                    CtorExpr::Int(300)
+                ]
+            }),
+            method: "value".to_string(),
+            args: vec![CtorExpr::Int(1)]
+        });
+        assert_eq!(ast.type_names(), vec!["Resample", "Momentum"]);
+
+        assert_eq!(CTorParser::parse_expr("42").unwrap(), CtorExpr::Int(42));
+        assert!(CTorParser::parse_expr("Momentum(SMA,").is_err());
+    }
+
+    #[test]
+    fn test_parse_error_position() {
+        let err = CTorParser::parse_expr("Momentum(SMA,[200,)").unwrap_err().to_string();
+        assert!(err.starts_with("failed to parse expression at line 1, column 19: expected"), "{}", err);
+        assert!(err.ends_with("\n  Momentum(SMA,[200,)\n                    ^"), "{}", err);
+
+        let err = CTorParser::parse_expr("Resample(\n  Momentum(1.5, =))").unwrap_err().to_string();
+        assert!(err.starts_with("failed to parse expression at line 2, column 17"), "{}", err);
+        assert!(err.ends_with("\n    Momentum(1.5, =))\n                  ^"), "{}", err);
+    }
+
+    #[test]
+    fn test_trailing_comma() {
+        assert_eq!(parse_ctor_arguments("Foo(1, 2,)"), vec![CtorExpr::Int(1), CtorExpr::Int(2)]);
+        assert_eq!(parse_ctor_arguments("Foo([1, 2,], (3, 4,), {a: 5,}, k=6,)"), vec![
+            CtorExpr::List(vec![CtorExpr::Int(1), CtorExpr::Int(2)]),
+            CtorExpr::Tuple(vec![CtorExpr::Int(3), CtorExpr::Int(4)]),
+            CtorExpr::Map(vec![("a".to_string(), CtorExpr::Int(5))]),
+            CtorExpr::Keyword { name: "k".to_string(), value: Box::new(CtorExpr::Int(6)) }
+        ]);
+        assert_eq!(CTorParser::parse_expr("Foo(1,).bar(2,)").unwrap(), CtorExpr::Call {
+            target: Box::new(CtorExpr::Ctor { name: "Foo".to_string(), args: vec![CtorExpr::Int(1)] }),
+            method: "bar".to_string(),
+            args: vec![CtorExpr::Int(2)]
+        });
+
+        // a comma alone is not an argument list
+        assert!(CTorParser::parse_expr("Foo(,)").is_err());
+        assert!(CTorParser::parse_expr("Foo(1,,)").is_err());
+    }
+
+    #[test]
+    fn test_empty_slot() {
+        let err = CTorParser::parse_expr("Foo(1,,2)").unwrap_err().to_string();
+        assert_eq!(err, "empty argument at line 1, column 7: expected an argument between commas\n  Foo(1,,2)\n        ^");
+
+        let err = CTorParser::parse_expr("Foo(1,\n  [2,,3])").unwrap_err().to_string();
+        assert!(err.contains("empty argument at line 2, column 6"), "{}", err);
+        assert!(err.ends_with("\n    [2,,3])\n       ^"), "{}", err);
+
+        let err = CTorParser::parse_expr("Foo(,1)").unwrap_err().to_string();
+        assert!(err.contains("empty argument at line 1, column 5"), "{}", err);
+        assert!(CTorParser::parse_expr("Foo(1).bar(2,,3)").unwrap_err().to_string().contains("empty argument"));
+    }
+
+    #[test]
+    fn test_evaluate_numeric_list() {
+        let list = |expr: &str| match CTorParser::parse_rule (Rule::value, expr).unwrap() {
+            CtorExpr::List(elements) => CTorParser::evaluate_list (&elements).unwrap(),
+            _ => panic!("expected list")
+        };
+
+        // a float anywhere yields floats
+        assert_eq!(list("[1.0, 2, 3]").downcast_ref::<Vec<f64>>(), Some(&vec![1.0, 2.0, 3.0]));
+        assert_eq!(list("[1, 2, 3.0]").downcast_ref::<Vec<f64>>(), Some(&vec![1.0, 2.0, 3.0]));
+        assert_eq!(list("[1, 2.5, 3]").downcast_ref::<Vec<f64>>(), Some(&vec![1.0, 2.5, 3.0]));
+
+        // integers within i32, otherwise i64
+        assert_eq!(list("[1, 2, 3]").downcast_ref::<Vec<i32>>(), Some(&vec![1, 2, 3]));
+        assert_eq!(list("[1, 2147483648]").downcast_ref::<Vec<i64>>(), Some(&vec![1, 2147483648]));
+        assert_eq!(list("[-2147483649, 0]").downcast_ref::<Vec<i64>>(), Some(&vec![-2147483649, 0]));
+    }
+
+    #[test]
+    fn test_parse_string_vs_identifier() {
+        let ctor = CTorParser::parse(Rule::expression, "F(\"SMA\", SMA)").unwrap().next().unwrap();
+        let rules: Vec<Rule> = ctor.into_inner().map(|p| p.as_rule()).collect();
+        assert_eq!(rules, vec![Rule::identifier, Rule::string, Rule::identifier]);
+    }
 }
\ No newline at end of file
@@ -0,0 +1,186 @@
+//! Validation of ctor expressions without evaluating them
+//! - each ctor is checked against the ctors of its type (or the free functions of its name),
+//!   and each method call against the methods of the type it would be called on
+//! - argument types are inferred: literals by their value, and ctors and method calls by the
+//!   return type of the function they would call
+//! - no ctor, method, or function is called, so validation is free of side effects
+//!
+
+use std::any::{Any, TypeId};
+
+use crate::{CTorParser, Conversions, ReflectError, find_type_by_id, lookup_type};
+use crate::core::free_functions;
+use crate::parser::CtorExpr;
+
+
+impl CTorParser {
+
+    /// Validate ctor expression without creating any object
+    /// - checks that each referenced type is registered and that some ctor of the type can
+    ///   be called with arguments of the (inferred) types given
+    /// - arguments convertible by type may still fail to convert by value, for example an
+    ///   integer out of range of its parameter type, so an expression validated may yet fail
+    /// ```ignore
+    ///    CTorParser::validate ("Resample(Momentum(SMA, [200, 50], 0.9), 300)")?;
+    /// ```
+    ///
+    /// # Parameters
+    /// - `expr`: ctor expression, optionally followed by method calls
+    ///
+    /// # Returns
+    /// - `Ok` if the expression is valid, otherwise each of the problems found, identifying the
+    ///   failing sub-expression as evaluation would (see `ReflectError::InExpression`)
+    pub fn validate (expr: &str) -> Result<(), Vec<ReflectError>> {
+        let ast = match Self::parse_expr (expr) {
+            Ok(ast @ (CtorExpr::Ctor { .. } | CtorExpr::Call { .. })) => ast,
+            Ok(_) => return Err(vec![ReflectError::ParseError(format!("not a call expression: '{}'", expr))]),
+            Err(error) => return Err(vec![error])
+        };
+
+        let mut problems = Vec::new();
+        Self::check (&ast, &mut problems);
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(problems)
+        }
+    }
+
+
+    /// Check AST, collecting problems
+    /// - arguments are checked recursively
+    ///
+    /// # Arguments
+    /// - `expr`: AST at current level
+    /// - `problems`: problems found so far
+    ///
+    /// # Returns
+    /// - type of the value of the expression, or `None` if not known (having found a problem)
+    fn check (expr: &CtorExpr, problems: &mut Vec<ReflectError>) -> Option<TypeId> {
+        match expr {
+            CtorExpr::Ctor { name, args } => {
+                let mut arg_types = Vec::with_capacity(args.len());
+                let mut kwarg_types = Vec::new();
+                for arg in args {
+                    match arg {
+                        CtorExpr::Keyword { name, value } =>
+                            kwarg_types.push((name.clone(), Self::check_argument (arg, value, problems))),
+                        _ => arg_types.push(Self::check_argument (arg, arg, problems))
+                    }
+                }
+
+                // the ctor cannot be checked unless the type of each argument is known
+                let arg_types: Option<Vec<TypeId>> = arg_types.into_iter().collect();
+                let kwarg_types: Option<Vec<(String, TypeId)>> = kwarg_types.into_iter()
+                    .map(|(name, arg_type)| arg_type.map(|t| (name, t)))
+                    .collect();
+                let (arg_types, kwarg_types) = (arg_types?, kwarg_types?);
+
+                let checked = match lookup_type(name) {
+                    Ok(itype) => itype.check_create (&arg_types, &kwarg_types),
+                    // fall back to a free function of that name
+                    Err(ReflectError::TypeNotFound(_)) if kwarg_types.is_empty() => {
+                        match free_functions().check_callstatic (name, &arg_types) {
+                            Err(ReflectError::MethodNotFound { .. }) => Err(ReflectError::TypeNotFound(name.clone())),
+                            checked => checked
+                        }
+                    }
+                    Err(error) => Err(error)
+                };
+                Self::checked (checked, problems)
+            }
+            CtorExpr::Call { target, method, args } => {
+                let receiver = Self::check_argument (target, target, problems);
+                let arg_types = Self::check_all (args, problems);
+                let (receiver, arg_types) = (receiver?, arg_types?);
+
+                let checked = match find_type_by_id(receiver) {
+                    Some(itype) => itype.check_call (method, &arg_types),
+                    None => Err(ReflectError::TypeNotFound(Conversions::type_name(receiver)))
+                };
+                Self::checked (checked, problems)
+            }
+            // lists of ctors would construct each element (see `evaluate_list`)
+            CtorExpr::List(elements) if !literal (expr) && elements.iter().all(|e| matches!(e, CtorExpr::Ctor { .. })) => {
+                Self::check_all (elements, problems).map(|_| TypeId::of::<Vec<Box<dyn Any>>>())
+            }
+            // other lists holding ctors fail to evaluate, their elements being checked nonetheless
+            CtorExpr::List(elements) if !literal (expr) => {
+                Self::check_all (elements, problems);
+                Self::checked (Err(Self::list_problem (elements)), problems)
+            }
+            // literals are evaluated, which has no side effects
+            _ => Self::checked (Self::evaluate (expr).map(|value| (*value).type_id()), problems)
+        }
+    }
+
+
+    /// Check argument, identifying the argument in problems found (see `within`)
+    ///
+    /// # Arguments
+    /// - `arg`: argument AST
+    /// - `value`: AST of the value of the argument (differing from `arg` if given by name)
+    /// - `problems`: problems found so far
+    fn check_argument (arg: &CtorExpr, value: &CtorExpr, problems: &mut Vec<ReflectError>) -> Option<TypeId> {
+        let first = problems.len();
+        let checked = Self::check (value, problems);
+        for problem in problems[first..].iter_mut() {
+            *problem = Self::within (arg, problem.clone());
+        }
+        checked
+    }
+
+
+    // Check each of the arguments, yielding their types if all are known
+    fn check_all (args: &[CtorExpr], problems: &mut Vec<ReflectError>) -> Option<Vec<TypeId>> {
+        let checked: Vec<Option<TypeId>> = args.iter().map(|arg| Self::check_argument (arg, arg, problems)).collect();
+        checked.into_iter().collect()
+    }
+
+
+    // Problem evaluating a list holding ctors (other than a list of ctors), as reported by
+    // `evaluate_list`, found without evaluating any ctor
+    fn list_problem (elements: &[CtorExpr]) -> ReflectError {
+        let listed = |e: &CtorExpr| matches!(e, CtorExpr::Ctor { .. } | CtorExpr::List(_) |
+            CtorExpr::Ident(_) | CtorExpr::Str(_) | CtorExpr::Int(_) | CtorExpr::Float(_));
+        let message = if !elements.iter().all(listed) {
+            "lists may only contain numbers, identifiers, strings, ctors, or lists"
+        } else if elements.iter().any(|e| matches!(e, CtorExpr::Ctor { .. })) {
+            "cannot mix ctors and other elements within a list"
+        } else if !elements.iter().all(|e| matches!(e, CtorExpr::List(_))) {
+            "cannot mix lists and scalars within a list"
+        } else {
+            // rows are evaluated in turn, failing at the first row failing
+            for row in elements {
+                match row {
+                    CtorExpr::List(_) if literal (row) => if let Err(error) = Self::evaluate (row) {
+                        return error;
+                    }
+                    CtorExpr::List(cells) if !cells.iter().all(|e| matches!(e, CtorExpr::Ctor { .. })) =>
+                        return Self::list_problem (cells),
+                    _ => ()
+                }
+            }
+            "nested lists must contain numbers"
+        };
+        ReflectError::ParseError(message.to_string())
+    }
+
+
+    // Record the problem, if any, yielding the type checked
+    fn checked (checked: Result<TypeId, ReflectError>, problems: &mut Vec<ReflectError>) -> Option<TypeId> {
+        checked.map_err(|error| problems.push(error)).ok()
+    }
+}
+
+
+// Determine whether the expression is a literal, holding no ctor or method call (so evaluated
+// without side effects)
+// - tuples and maps hold literals only (see the grammar)
+fn literal (expr: &CtorExpr) -> bool {
+    match expr {
+        CtorExpr::Ctor { .. } | CtorExpr::Call { .. } => false,
+        CtorExpr::List(elements) => elements.iter().all(literal),
+        _ => true
+    }
+}
@@ -0,0 +1,220 @@
+//! Python integration (feature `pyo3`)
+//! - the `PyReflect` python module creates reflected types and calls their methods by name
+//! - python arguments are converted as the equivalent literals of a ctor expression: `int` to
+//!   `i64`, `float` to `f64`, `list` to `Vec`, `str` to `String`, `tuple` to a tuple, `dict`
+//!   to a map, and `None` to `Null`, such that the usual conversions apply
+//! - objects are returned to python as an opaque `Handle`, which may be passed as an argument
+//!   to other ctors and methods, whereas primitive results (numbers, strings, and vectors of
+//!   these) are returned as python values
+//!
+//! The module is added to an extension module (or an embedded interpreter) as:
+//! ```ignore
+//!    #[pymodule]
+//!    fn strategies (m: &Bound<'_, PyModule>) -> PyResult<()> {
+//!        m.add_wrapped(wrap_pymodule!(reflect::python::py_reflect))
+//!    }
+//! ```
+//! and used from python as:
+//! ```text
+//!    from strategies import PyReflect
+//!    momentum = PyReflect.create("Momentum", "SMA", [200, 50, 20], 0.9)
+//!    value = PyReflect.call(momentum, "value", 42)
+//! ```
+//!
+
+// the code generated by pyo3 for functions returning `PyResult` trips this lint
+#![allow(clippy::useless_conversion)]
+
+use std::any::Any;
+
+use pyo3::prelude::*;
+use pyo3::exceptions::PyValueError;
+use pyo3::types::{PyBool, PyDict, PyFloat, PyList, PyLong, PyModule, PyString, PyTuple};
+
+use crate::{Conversions, CTorParser, CtorExpr, ReflectError, find_type_by_id, lookup_type};
+
+
+/// Handle to an object created through reflection
+/// - the object is held by the handle, and may only be used on the thread creating it
+#[pyclass(unsendable, module = "PyReflect")]
+pub struct Handle {
+    obj: Option<Box<dyn Any>>,
+}
+
+
+#[pymethods]
+impl Handle {
+
+    /// Name of the type of the object
+    fn type_name (&self) -> PyResult<String> {
+        Ok(Conversions::type_name((**self.object()?).type_id()))
+    }
+
+    fn __repr__ (&self) -> PyResult<String> {
+        Ok(format!("<{} handle>", self.type_name()?))
+    }
+}
+
+
+impl Handle {
+
+    // the object held, unless taken while passed as an argument
+    fn object (&self) -> PyResult<&Box<dyn Any>> {
+        self.obj.as_ref().ok_or_else(|| PyValueError::new_err("handle is in use, or was consumed by a method taking self"))
+    }
+}
+
+
+impl From<ReflectError> for PyErr {
+    fn from(error: ReflectError) -> Self {
+        PyValueError::new_err(error.to_string())
+    }
+}
+
+
+/// Create an instance of a named type
+///
+/// # Arguments
+/// - `name`: name of type
+/// - `args`: arguments to ctor
+///
+/// # Returns
+/// - handle to the new object instance
+#[pyfunction]
+#[pyo3(signature = (name, *args))]
+pub fn create (name: &str, args: &Bound<'_, PyTuple>) -> PyResult<Handle> {
+    let itype = lookup_type(name)?;
+    let obj = with_arguments(args, |argv| itype.create(argv))?;
+    Ok(Handle { obj: Some(obj) })
+}
+
+
+/// Call a method of an object
+///
+/// # Arguments
+/// - `obj`: handle to the object (which is emptied if the method takes `self`)
+/// - `method`: method name
+/// - `args`: arguments to method
+///
+/// # Returns
+/// - result of the method, as a python value if a number, string, or vector of these, or
+///   otherwise as a handle
+#[pyfunction]
+#[pyo3(signature = (obj, method, *args))]
+pub fn call (py: Python<'_>, obj: &Bound<'_, Handle>, method: &str, args: &Bound<'_, PyTuple>) -> PyResult<PyObject> {
+    obj.borrow().object()?;
+    let result = with_arguments(args, |argv| {
+        let mut handle = obj.try_borrow_mut().map_err(|_| ReflectError::InvalidArgument { index: 0 })?;
+        let receiver = handle.obj.as_mut().ok_or(ReflectError::InvalidArgument { index: 0 })?;
+        let itype = match find_type_by_id((**receiver).type_id()) {
+            Some(itype) => itype,
+            None => return Err(ReflectError::TypeNotFound(Conversions::type_name((**receiver).type_id())))
+        };
+
+        // a method taking `self` consumes the object, leaving the handle empty
+        match itype.methods.get(method) {
+            Some(m) if m.iter().all(|m| m.is_consuming()) => itype.call_consuming(handle.obj.take().unwrap(), method, argv),
+            _ => itype.call_mut(receiver, method, argv)
+        }
+    })?;
+    Ok(to_python(py, result))
+}
+
+
+/// The `PyReflect` python module
+/// - `create(name, *args)`: create an instance of a named type, returning a `Handle`
+/// - `call(handle, method, *args)`: call a method of an object
+#[pymodule]
+#[pyo3(name = "PyReflect")]
+pub fn py_reflect (m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<Handle>()?;
+    m.add_function(wrap_pyfunction!(create, m)?)?;
+    m.add_function(wrap_pyfunction!(call, m)?)?;
+    Ok(())
+}
+
+
+// Convert python arguments and apply a reflected call to them
+// - objects of handles passed as arguments are lent to the call, then returned to the handles
+fn with_arguments<F> (args: &Bound<'_, PyTuple>, f: F) -> Result<Box<dyn Any>, ReflectError>
+    where F: FnOnce(&[Box<dyn Any>]) -> Result<Box<dyn Any>, ReflectError> {
+    let mut argv: Vec<Box<dyn Any>> = Vec::with_capacity(args.len());
+    let mut lent = Vec::new();
+
+    let mut converted = Ok(());
+    for (index, arg) in args.iter().enumerate() {
+        let value = match arg.downcast::<Handle>() {
+            Ok(handle) => match handle.try_borrow_mut().ok().and_then(|mut h| h.obj.take()) {
+                Some(obj) => {
+                    lent.push((index, handle.clone()));
+                    Ok(obj)
+                }
+                None => Err(ReflectError::InvalidArgument { index })
+            },
+            Err(_) => to_expr(&arg).and_then(|expr| CTorParser::evaluate (&expr))
+        };
+        match value {
+            Ok(value) => argv.push(value),
+            Err(error) => {
+                converted = Err(error);
+                break;
+            }
+        }
+    }
+
+    let result = converted.and_then(|_| f(&argv));
+    for (index, handle) in lent {
+        handle.borrow_mut().obj = Some(std::mem::replace(&mut argv[index], Box::new(())));
+    }
+    result
+}
+
+
+// Convert a python value to the equivalent literal of a ctor expression
+fn to_expr (value: &Bound<'_, PyAny>) -> Result<CtorExpr, ReflectError> {
+    let unsupported = || ReflectError::ConversionFailed {
+        from: value.get_type().name().map(|n| n.to_string()).unwrap_or_default(),
+        to: "argument".to_string()
+    };
+
+    // bool must precede int, of which it is a subclass
+    if value.is_none() {
+        Ok(CtorExpr::Null)
+    } else if value.is_instance_of::<PyBool>() {
+        value.extract::<bool>().map(CtorExpr::Bool).map_err(|_| unsupported())
+    } else if value.is_instance_of::<PyLong>() {
+        value.extract::<i64>().map(CtorExpr::Int).map_err(|_| unsupported())
+    } else if value.is_instance_of::<PyFloat>() {
+        value.extract::<f64>().map(CtorExpr::Float).map_err(|_| unsupported())
+    } else if value.is_instance_of::<PyString>() {
+        value.extract::<String>().map(CtorExpr::Str).map_err(|_| unsupported())
+    } else if let Ok(list) = value.downcast::<PyList>() {
+        list.iter().map(|v| to_expr(&v)).collect::<Result<Vec<_>, _>>().map(CtorExpr::List)
+    } else if let Ok(tuple) = value.downcast::<PyTuple>() {
+        tuple.iter().map(|v| to_expr(&v)).collect::<Result<Vec<_>, _>>().map(CtorExpr::Tuple)
+    } else if let Ok(dict) = value.downcast::<PyDict>() {
+        dict.iter()
+            .map(|(k, v)| Ok((k.extract::<String>().map_err(|_| unsupported())?, to_expr(&v)?)))
+            .collect::<Result<Vec<_>, _>>()
+            .map(CtorExpr::Map)
+    } else {
+        Err(unsupported())
+    }
+}
+
+
+// Convert the result of a reflected call to a python value, or a handle if not a number,
+// string, or vector of these
+fn to_python (py: Python<'_>, value: Box<dyn Any>) -> PyObject {
+    macro_rules! convert {
+        ($($t:ty),*) => {$(
+            if let Some(v) = value.downcast_ref::<$t>() {
+                return v.clone().into_py(py);
+            }
+        )*};
+    }
+
+    convert!(i64, i32, u32, u64, f64, f32, bool, String, (),
+        Vec<i64>, Vec<i32>, Vec<f64>, Vec<bool>, Vec<String>);
+    Py::new(py, Handle { obj: Some(value) }).map(|h| h.into_py(py)).unwrap_or_else(|_| py.None())
+}
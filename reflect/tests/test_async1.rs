@@ -0,0 +1,64 @@
+#![cfg(feature = "async")]
+
+use reflect::{TypeInfo, ReflectError};
+use reflect_macros::reflect_impl;
+use std::any::Any;
+
+
+struct Loader {
+    scale: f64
+}
+
+#[reflect_impl]
+impl Loader {
+    fn new (scale: f64) -> Self {
+        Loader { scale }
+    }
+
+    async fn load (&self, path: String) -> Vec<f64> {
+        tokio::task::yield_now().await;
+        vec![self.scale * path.len() as f64]
+    }
+
+    async fn load_checked (&self, path: &str) -> Result<f64, String> {
+        match path.is_empty() {
+            true => Err("empty path".to_string()),
+            false => Ok(self.scale)
+        }
+    }
+
+    fn scale (&self) -> f64 {
+        self.scale
+    }
+}
+
+
+#[tokio::test]
+async fn test_call_async() {
+    let itype = TypeInfo::find_type("Loader").expect("could not find type");
+    let obj = itype.create(&[Box::new(2.0) as Box<dyn Any>]).expect("failed to create");
+
+    let args = vec![Box::new("a.csv".to_string()) as Box<dyn Any>];
+    let result = itype.call_async(&obj, "load", &args).await.expect("failed to call");
+    assert_eq!(*result.downcast_ref::<Vec<f64>>().unwrap(), vec![10.0]);
+
+    let args = vec![Box::new(String::new()) as Box<dyn Any>];
+    let err = itype.call_async(&obj, "load_checked", &args).await.unwrap_err();
+    assert_eq!(err, ReflectError::CallFailed("empty path".to_string()));
+
+    // methods other than async methods may be called as well
+    let result = itype.call_async(&obj, "scale", &[]).await.expect("failed to call");
+    assert_eq!(*result.downcast_ref::<f64>().unwrap(), 2.0);
+}
+
+
+#[test]
+fn test_call_async_synchronously() {
+    let itype = TypeInfo::find_type("Loader").expect("could not find type");
+    let obj = itype.create(&[Box::new(2.0) as Box<dyn Any>]).expect("failed to create");
+
+    let args = vec![Box::new("a.csv".to_string()) as Box<dyn Any>];
+    let err = itype.call(&obj, "load", &args).unwrap_err();
+    assert_eq!(err, ReflectError::AsyncReceiver { method: "load".to_string() });
+    assert!(itype.methods["load"][0].is_async());
+}
@@ -0,0 +1,283 @@
+use reflect::{TypeInfo, ReflectError};
+use reflect_macros::reflect_impl;
+use std::any::{Any, TypeId};
+
+
+struct Counter {
+    count: i32
+}
+
+#[reflect_impl]
+impl Counter {
+    /// Counter starting at the given count
+    fn new (count: i32) -> Self {
+        Counter { count }
+    }
+
+    /// Current count
+    ///
+    /// Unaffected by `add`
+    fn count (&self) -> i32 {
+        self.count
+    }
+
+    /// Count plus the given value
+    #[reflect(rename = "add")]
+    fn add_to_count (&self, x: i32) -> i32 {
+        self.count + x
+    }
+
+    #[reflect(rename = "zero")]
+    fn make_zero () -> i32 {
+        0
+    }
+
+    #[reflect(skip)]
+    fn unchecked_count (&self) -> i32 {
+        self.count
+    }
+}
+
+
+#[test]
+fn test_skip() {
+    let itype = TypeInfo::find_type("Counter").expect("could not find type");
+    let obj = itype.create(&[Box::new(5i32) as Box<dyn Any>]).expect("failed to call ctor");
+
+    let result = itype.call(&obj, "count", &[]).expect("failed to call");
+    assert_eq!(*result.downcast_ref::<i32>().unwrap(), 5);
+
+    // skipped method is not reflected, but is still callable from rust
+    let err = itype.call(&obj, "unchecked_count", &[]).unwrap_err();
+    assert_eq!(err, ReflectError::MethodNotFound { type_name: "Counter".to_string(), method: "unchecked_count".to_string() });
+    assert_eq!(obj.downcast_ref::<Counter>().unwrap().unchecked_count(), 5);
+}
+
+
+#[test]
+fn test_rename() {
+    let itype = TypeInfo::find_type("Counter").expect("could not find type");
+    let obj = itype.create(&[Box::new(5i32) as Box<dyn Any>]).expect("failed to call ctor");
+
+    let result = itype.call(&obj, "add", &[Box::new(2i32) as Box<dyn Any>]).expect("failed to call");
+    assert_eq!(*result.downcast_ref::<i32>().unwrap(), 7);
+    assert!(itype.call(&obj, "add_to_count", &[Box::new(2i32) as Box<dyn Any>]).is_err());
+
+    let result = itype.callstatic("zero", &[]).expect("failed to call");
+    assert_eq!(*result.downcast_ref::<i32>().unwrap(), 0);
+    assert!(itype.callstatic("make_zero", &[]).is_err());
+}
+
+
+struct Series {
+    values: Vec<f64>
+}
+
+#[reflect_impl]
+impl Series {
+    fn new (x: f64) -> Self {
+        Series { values: vec![x] }
+    }
+
+    fn push (&mut self, x: f64) -> usize {
+        self.values.push(x);
+        self.values.len()
+    }
+
+    fn sum (&self) -> f64 {
+        self.values.iter().sum()
+    }
+
+    fn get (&self, i: i32) -> Option<f64> {
+        self.values.get(i as usize).copied()
+    }
+
+    #[reflect(none_as_error)]
+    fn at (&self, i: i32) -> Option<f64> {
+        self.values.get(i as usize).copied()
+    }
+}
+
+
+#[test]
+fn test_call_mut() {
+    let itype = TypeInfo::find_type("Series").expect("could not find type");
+    let mut obj = itype.create(&[Box::new(1.5f64) as Box<dyn Any>]).expect("failed to call ctor");
+
+    let result = itype.call_mut(&mut obj, "push", &[Box::new(2i32) as Box<dyn Any>]).expect("failed to call");
+    assert_eq!(*result.downcast_ref::<usize>().unwrap(), 2);
+    assert_eq!(obj.downcast_ref::<Series>().unwrap().values, vec![1.5, 2.0]);
+
+    // immutable methods may also be called through call_mut
+    let result = itype.call_mut(&mut obj, "sum", &[]).expect("failed to call");
+    assert_eq!(*result.downcast_ref::<f64>().unwrap(), 3.5);
+
+    // mutating methods cannot be called through call
+    let err = itype.call(&obj, "push", &[Box::new(1.0f64) as Box<dyn Any>]).unwrap_err();
+    assert_eq!(err, ReflectError::MutableReceiver { method: "push".to_string(), required: true });
+}
+
+
+#[test]
+fn test_wrong_receiver_type() {
+    let itype = TypeInfo::find_type("Series").expect("could not find type");
+    let counter = TypeInfo::find_type("Counter").expect("could not find type");
+    let mut obj = counter.create(&[Box::new(5i32) as Box<dyn Any>]).expect("failed to call ctor");
+
+    // calling a method of Series on a Counter fails cleanly, however called
+    let expected = ReflectError::WrongReceiverType { expected: "Series".to_string() };
+    assert_eq!(itype.call(&obj, "sum", &[]).unwrap_err(), expected);
+    assert_eq!(itype.call_mut(&mut obj, "push", &[Box::new(1.0f64) as Box<dyn Any>]).unwrap_err(), expected);
+    assert_eq!(itype.call_consuming(obj, "sum", &[]).unwrap_err(), expected);
+}
+
+
+#[test]
+fn test_option_return() {
+    let itype = TypeInfo::find_type("Series").expect("could not find type");
+    let obj = itype.create(&[Box::new(1.5f64) as Box<dyn Any>]).expect("failed to call ctor");
+
+    // option is boxed as is, though the reported return type is the inner type
+    let result = itype.call(&obj, "get", &[Box::new(0i32) as Box<dyn Any>]).expect("failed to call");
+    assert_eq!(*result.downcast_ref::<Option<f64>>().unwrap(), Some(1.5));
+    let result = itype.call(&obj, "get", &[Box::new(1i32) as Box<dyn Any>]).expect("failed to call");
+    assert_eq!(*result.downcast_ref::<Option<f64>>().unwrap(), None);
+    assert_eq!(itype.signature("get").unwrap().return_type, TypeId::of::<f64>());
+
+    // with none_as_error, the value is unwrapped
+    let result = itype.call(&obj, "at", &[Box::new(0i32) as Box<dyn Any>]).expect("failed to call");
+    assert_eq!(*result.downcast_ref::<f64>().unwrap(), 1.5);
+    let err = itype.call(&obj, "at", &[Box::new(1i32) as Box<dyn Any>]).unwrap_err();
+    assert_eq!(err, ReflectError::CallFailed("at returned None".to_string()));
+}
+
+
+#[test]
+fn test_doc() {
+    let itype = TypeInfo::find_type("Counter").expect("could not find type");
+
+    assert_eq!(itype.signature("count").unwrap().doc, "Current count\n\nUnaffected by `add`");
+    assert_eq!(itype.signature("add").unwrap().doc, "Count plus the given value");
+    assert_eq!(itype.signature("zero").unwrap().doc, "");
+    assert_eq!(itype.constructors[0].doc(), "Counter starting at the given count");
+}
+
+
+struct Smoother {
+    alpha: f64,
+    beta: f64,
+    windows: Vec<i32>
+}
+
+#[reflect_impl]
+impl Smoother {
+    fn new (alpha: f64, #[reflect(default = "1.0")] beta: f64, #[reflect(default = "[20, 5]")] windows: &[i32]) -> Self {
+        Smoother { alpha, beta, windows: windows.to_vec() }
+    }
+
+    fn blend (&self, x: f64, #[reflect(default = "0.5")] weight: f64) -> f64 {
+        self.alpha * x * weight + self.beta * x * (1.0 - weight)
+    }
+
+    fn broken (&self, #[reflect(default = "[1, ")] _x: i32) -> i32 {
+        0
+    }
+}
+
+
+#[test]
+fn test_defaults() {
+    let itype = TypeInfo::find_type("Smoother").expect("could not find type");
+
+    // with all arguments
+    let args: Vec<Box<dyn Any>> = vec![Box::new(0.5f64), Box::new(2.0f64), Box::new(vec![10i32])];
+    let full = itype.create(&args).expect("failed to call ctor");
+    let smoother = full.downcast_ref::<Smoother>().unwrap();
+    assert_eq!((smoother.alpha, smoother.beta, smoother.windows.clone()), (0.5, 2.0, vec![10]));
+
+    // without defaulted arguments, whose defaults are converted to the parameter types
+    let obj = reflect::CTorParser::create("Smoother(0.5)").expect("failed to create");
+    let smoother = obj.downcast_ref::<Smoother>().unwrap();
+    assert_eq!((smoother.alpha, smoother.beta, smoother.windows.clone()), (0.5, 1.0, vec![20, 5]));
+
+    let obj = reflect::CTorParser::create("Smoother(1, windows=[3])").expect("failed to create");
+    let smoother = obj.downcast_ref::<Smoother>().unwrap();
+    assert_eq!((smoother.alpha, smoother.beta, smoother.windows.clone()), (1.0, 1.0, vec![3]));
+
+    // methods too
+    let result = itype.call_as::<f64>(&full, "blend", &[Box::new(2.0f64) as Box<dyn Any>]).expect("failed to call");
+    assert_eq!(*result, 2.5);
+    let result = itype.call_as::<f64>(&full, "blend", &[Box::new(2.0f64) as Box<dyn Any>, Box::new(1.0f64)]).expect("failed to call");
+    assert_eq!(*result, 1.0);
+    assert_eq!(itype.methods["blend"][0].arg_defaults(), &[None, Some("0.5")]);
+
+    // arguments are still required for parameters without a default
+    let err = itype.create(&[]).unwrap_err();
+    assert!(matches!(err, ReflectError::NoMatchingConstructor { .. }), "{}", err);
+
+    // a default failing to evaluate is reported when used
+    let err = itype.call(&obj, "broken", &[]).unwrap_err();
+    assert!(matches!(err, ReflectError::InExpression { ref expression, .. } if expression == "[1, "), "{}", err);
+    assert_eq!(*itype.call_as::<i32>(&obj, "broken", &[Box::new(1i32) as Box<dyn Any>]).expect("failed to call"), 0);
+}
+
+
+#[test]
+fn test_defaults_of_ctor_variants() {
+    let itype = TypeInfo::find_type("Smoother").expect("could not find type");
+    let args = || vec![Box::new(1i32) as Box<dyn Any>];
+    let fields = |obj: Box<dyn Any>| {
+        let smoother = obj.downcast::<Smoother>().unwrap();
+        (smoother.alpha, smoother.beta, smoother.windows)
+    };
+
+    // each variant of create resolves the ctor and its defaults as create does
+    assert_eq!(fields(itype.create_owned(args()).expect("failed to create")), (1.0, 1.0, vec![20, 5]));
+    assert_eq!(fields(itype.create_strict(&args()).expect("failed to create")), (1.0, 1.0, vec![20, 5]));
+    let (obj, selected) = itype.create_selected(&args()).expect("failed to create");
+    assert_eq!(fields(obj), (1.0, 1.0, vec![20, 5]));
+    assert_eq!(selected.arg_type_names, vec!["f64", "f64", "&[i32]"]);
+
+    let (obj, trace) = itype.create_traced(&args()).expect("failed to create");
+    assert_eq!(fields(obj), (1.0, 1.0, vec![20, 5]));
+    assert_eq!(trace.len(), 3);
+    assert!(trace[0].is_converted());
+    assert!(!trace[1].is_converted() && !trace[2].is_converted());
+}
+
+
+struct Dflt {
+    a: f64,
+    b: f64
+}
+
+#[reflect_impl]
+impl Dflt {
+    fn new (a: f64, #[reflect(default = "1.0")] b: f64) -> Self {
+        Dflt { a, b }
+    }
+
+    fn other (a: i32, #[reflect(default = "Nope(1)")] c: f64) -> Self {
+        Dflt { a: a as f64, b: c }
+    }
+}
+
+
+#[test]
+fn test_defaults_of_other_candidates() {
+    let itype = TypeInfo::find_type("Dflt").expect("could not find type");
+
+    // defaults are evaluated for the ctor chosen only, such that a broken default elsewhere is moot
+    let obj = itype.create(&[Box::new(2.0f64) as Box<dyn Any>]).expect("failed to call ctor");
+    let dflt = obj.downcast_ref::<Dflt>().unwrap();
+    assert_eq!((dflt.a, dflt.b), (2.0, 1.0));
+
+    let obj = reflect::CTorParser::create("Dflt(2.0)").expect("failed to create");
+    let dflt = obj.downcast_ref::<Dflt>().unwrap();
+    assert_eq!((dflt.a, dflt.b), (2.0, 1.0));
+    assert!(reflect::CTorParser::validate("Dflt(2.0)").is_ok());
+
+    // and reported when the ctor chosen
+    let err = itype.create(&[Box::new(2i32) as Box<dyn Any>]).unwrap_err();
+    assert!(matches!(err, ReflectError::InExpression { ref expression, .. } if expression == "Nope(1)"), "{}", err);
+}
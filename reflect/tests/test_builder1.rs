@@ -0,0 +1,167 @@
+use reflect::{CTorParser, Constructor, Function, Method, ReflectError, StaticFunction, TypeInfo, TypeInfoBuilder, with_scope};
+use std::any::{Any, TypeId};
+use std::marker::PhantomData;
+
+
+// a record whose fields are given by a schema at runtime, typed by the schema it belongs to
+struct Record<S> {
+    values: Vec<f64>,
+    _schema: PhantomData<S>
+}
+
+struct Trade;
+struct Quote;
+
+struct RecordConstructor<S> {
+    arg_types: Vec<TypeId>,
+    _schema: PhantomData<S>
+}
+
+impl<S> Clone for RecordConstructor<S> {
+    fn clone(&self) -> Self {
+        RecordConstructor { arg_types: self.arg_types.clone(), _schema: PhantomData }
+    }
+}
+
+impl<S> RecordConstructor<S> {
+    fn new (fields: &[&str]) -> Self {
+        RecordConstructor { arg_types: vec![TypeId::of::<f64>(); fields.len()], _schema: PhantomData }
+    }
+}
+
+impl<S: Send + Sync + 'static> Function for RecordConstructor<S> {
+    fn name(&self) -> &str {
+        "*"
+    }
+
+    fn arg_types(&self) -> &[TypeId] {
+        &self.arg_types
+    }
+
+    fn return_type(&self) -> TypeId {
+        TypeId::of::<Record<S>>()
+    }
+}
+
+impl<S: Send + Sync + 'static> Constructor for RecordConstructor<S> {
+    fn create(&self, args: &[&dyn Any]) -> Result<Box<dyn Any>, ReflectError> {
+        let values = args.iter().enumerate()
+            .map(|(index, arg)| arg.downcast_ref::<f64>().copied().ok_or(ReflectError::InvalidArgument { index }))
+            .collect::<Result<Vec<f64>, ReflectError>>()?;
+        Ok(Box::new(Record::<S> { values, _schema: PhantomData }))
+    }
+
+    fn clone_boxed(&self) -> Box<dyn Constructor> {
+        Box::new(self.clone())
+    }
+}
+
+
+struct FieldGetter<S> {
+    name: String,
+    index: usize,
+    _schema: PhantomData<S>
+}
+
+impl<S> Clone for FieldGetter<S> {
+    fn clone(&self) -> Self {
+        FieldGetter { name: self.name.clone(), index: self.index, _schema: PhantomData }
+    }
+}
+
+impl<S: Send + Sync + 'static> Function for FieldGetter<S> {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn arg_types(&self) -> &[TypeId] {
+        &[]
+    }
+
+    fn return_type(&self) -> TypeId {
+        TypeId::of::<f64>()
+    }
+}
+
+impl<S: Send + Sync + 'static> Method for FieldGetter<S> {
+    fn call(&self, obj: &Box<dyn Any>, _args: &[&dyn Any]) -> Result<Box<dyn Any>, ReflectError> {
+        Ok(Box::new(obj.downcast_ref::<Record<S>>().unwrap().values[self.index]))
+    }
+
+    fn clone_boxed(&self) -> Box<dyn Method> {
+        Box::new(self.clone())
+    }
+}
+
+
+#[derive(Clone)]
+struct FieldCount {
+    count: usize
+}
+
+impl Function for FieldCount {
+    fn name(&self) -> &str {
+        "field_count"
+    }
+
+    fn arg_types(&self) -> &[TypeId] {
+        &[]
+    }
+
+    fn return_type(&self) -> TypeId {
+        TypeId::of::<usize>()
+    }
+}
+
+impl StaticFunction for FieldCount {
+    fn call(&self, _args: &[&dyn Any]) -> Result<Box<dyn Any>, ReflectError> {
+        Ok(Box::new(self.count))
+    }
+
+    fn clone_boxed(&self) -> Box<dyn StaticFunction> {
+        Box::new(self.clone())
+    }
+}
+
+
+// build the type of a record from its schema
+fn record_type<S: Send + Sync + 'static> (name: &str, fields: &[&str]) -> TypeInfoBuilder<Record<S>> {
+    let mut builder = TypeInfoBuilder::<Record<S>>::new()
+        .name(name)
+        .constructor(Box::new(RecordConstructor::<S>::new(fields)))
+        .function(Box::new(FieldCount { count: fields.len() }));
+    for (index, field) in fields.iter().enumerate() {
+        builder = builder.method(Box::new(FieldGetter::<S> { name: field.to_string(), index, _schema: PhantomData }));
+    }
+    builder
+}
+
+
+#[test]
+fn test_build_type() {
+    let itype = record_type::<Trade>("schema::Trade", &["price", "quantity"]).register();
+    assert_eq!(itype.name, "schema::Trade");
+    assert_eq!(itype.constructors.len(), 1);
+    assert_eq!(itype.methods.len(), 2);
+
+    let itype = TypeInfo::find_type("schema::Trade").expect("could not find type");
+    let args = vec![Box::new(101.5) as Box<dyn Any>, Box::new(200.0) as Box<dyn Any>];
+    let obj = itype.create(&args).expect("failed to create");
+    assert_eq!(obj.downcast_ref::<Record<Trade>>().unwrap().values, vec![101.5, 200.0]);
+
+    let price = CTorParser::eval::<f64>("Trade(101.5, 200.0).price()").expect("failed to evaluate");
+    assert_eq!(price, 101.5);
+    let count = itype.callstatic("field_count", &[]).expect("failed to call");
+    assert_eq!(*count.downcast_ref::<usize>().unwrap(), 2);
+}
+
+
+#[test]
+fn test_build_scoped_type() {
+    with_scope(|scope| {
+        let itype = record_type::<Quote>("Quote", &["bid", "ask"]).register_in(scope);
+        assert_eq!(itype.methods.len(), 2);
+        assert_eq!(CTorParser::eval::<f64>("Quote(1.0, 1.5).ask()").expect("failed to evaluate"), 1.5);
+    });
+    assert!(TypeInfo::find_type("Quote").is_none());
+}
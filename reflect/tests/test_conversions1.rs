@@ -0,0 +1,651 @@
+use reflect::{TypeInfo, CTorParser, Conversions, ReflectError};
+use reflect_macros::{reflect_impl, reflect_newtype};
+use std::any::{Any, TypeId};
+use std::collections::{BTreeSet, HashSet, VecDeque};
+
+
+struct Scaler {
+    factor: f64
+}
+
+#[reflect_impl]
+impl Scaler {
+    fn new (factor: f64) -> Self {
+        Scaler { factor }
+    }
+
+    fn scale(&self, x: f64) -> f64 {
+        x * self.factor
+    }
+
+    fn total(&self, v: &[f64]) -> f64 {
+        v.iter().sum::<f64>() * self.factor
+    }
+
+    fn rounded(&self, v: Vec<i64>) -> i64 {
+        v.iter().sum::<i64>() * self.factor as i64
+    }
+}
+
+
+#[test]
+fn test_i64_to_f64() {
+    let itype = TypeInfo::find_type("Scaler").expect("could not find type");
+
+    // i64 ctor argument converted to f64
+    let obj = itype.create(&[Box::new(2i64) as Box<dyn Any>]).expect("failed to call ctor");
+    assert_eq!(obj.downcast_ref::<Scaler>().unwrap().factor, 2.0);
+
+    // large i64 value must survive the conversion (no truncation to i32)
+    let args = vec![Box::new(5_000_000_000i64) as Box<dyn Any>];
+    let result = itype.call(&obj, "scale", &args).expect("failed to call method");
+    assert_eq!(*result.downcast_ref::<f64>().unwrap(), 10_000_000_000.0);
+}
+
+
+#[test]
+fn test_integer_to_f64() {
+    let itype = TypeInfo::find_type("Scaler").expect("could not find type");
+    let obj = itype.create(&[Box::new(1.5f64) as Box<dyn Any>]).expect("failed to call ctor");
+
+    let inputs: Vec<Box<dyn Any>> = vec![
+        Box::new(4i32),
+        Box::new(4u32),
+        Box::new(4i64),
+        Box::new(4u64),
+    ];
+    for input in inputs {
+        let result = itype.call(&obj, "scale", &[input]).expect("failed to call method");
+        assert_eq!(*result.downcast_ref::<f64>().unwrap(), 6.0);
+    }
+}
+
+
+struct Steps {
+    n: i64
+}
+
+#[reflect_impl]
+impl Steps {
+    fn new (n: i64) -> Self {
+        Steps { n }
+    }
+
+    fn plus(&self, x: i32) -> i64 {
+        self.n + i64::from(x)
+    }
+
+    fn times(&self, x: u64) -> i64 {
+        self.n * x as i64
+    }
+}
+
+
+#[test]
+fn test_non_finite_to_integer() {
+    let itype = TypeInfo::find_type("Steps").expect("could not find type");
+    let obj = itype.create(&[Box::new(2.0f64) as Box<dyn Any>]).expect("failed to call ctor");
+    assert_eq!(obj.downcast_ref::<Steps>().unwrap().n, 2);
+
+    // NaN and infinite values fail to convert, rather than becoming 0 or saturating
+    for x in [f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+        let err = itype.create(&[Box::new(x) as Box<dyn Any>]).unwrap_err();
+        assert_eq!(err, ReflectError::ConversionFailed { from: "f64".to_string(), to: "i64".to_string() });
+
+        assert!(itype.call(&obj, "plus", &[Box::new(x) as Box<dyn Any>]).is_err());
+        assert!(itype.call(&obj, "times", &[Box::new(x) as Box<dyn Any>]).is_err());
+
+        let window = TypeInfo::find_type("Window").expect("could not find type");
+        assert!(window.create(&[Box::new(x) as Box<dyn Any>, Box::new(1i64) as Box<dyn Any>]).is_err());
+    }
+}
+
+
+struct Gain {
+    k: f32
+}
+
+#[reflect_impl]
+impl Gain {
+    fn new (k: f32) -> Self {
+        Gain { k }
+    }
+
+    fn scale(&self, k: f32) -> f32 {
+        k * self.k
+    }
+
+    fn total(&self, v: &[f32]) -> f32 {
+        v.iter().sum::<f32>() * self.k
+    }
+}
+
+
+#[test]
+fn test_f32() {
+    // parsed float literal (f64) converted to f32
+    let obj = CTorParser::create("Gain(0.5)").expect("failed to create");
+    assert_eq!(obj.downcast_ref::<Gain>().unwrap().k, 0.5f32);
+
+    let itype = TypeInfo::find_type("Gain").expect("could not find type");
+    let result = itype.call(&obj, "scale", &[Box::new(0.5f64) as Box<dyn Any>]).expect("failed to call method");
+    assert_eq!(*result.downcast_ref::<f32>().unwrap(), 0.25f32);
+
+    let result = itype.call(&obj, "scale", &[Box::new(3i64) as Box<dyn Any>]).expect("failed to call method");
+    assert_eq!(*result.downcast_ref::<f32>().unwrap(), 1.5f32);
+
+    let result = itype.call(&obj, "total", &[Box::new(vec![1.0f64, 3.0]) as Box<dyn Any>]).expect("failed to call method");
+    assert_eq!(*result.downcast_ref::<f32>().unwrap(), 2.0f32);
+}
+
+
+struct Window {
+    size: u8,
+    offset: i16
+}
+
+#[reflect_impl]
+impl Window {
+    fn new (size: u8, offset: i16) -> Self {
+        Window { size, offset }
+    }
+}
+
+
+#[test]
+fn test_small_integers() {
+    let obj = CTorParser::create("Window(20, -3)").expect("failed to create");
+    let window = obj.downcast_ref::<Window>().unwrap();
+    assert_eq!(window.size, 20u8);
+    assert_eq!(window.offset, -3i16);
+
+    let obj = CTorParser::create("Window(12.0, 300)").expect("failed to create");
+    let window = obj.downcast_ref::<Window>().unwrap();
+    assert_eq!(window.size, 12u8);
+    assert_eq!(window.offset, 300i16);
+
+    // out of range for u8
+    let err = CTorParser::create("Window(300, 1)").unwrap_err();
+    assert_eq!(err, ReflectError::ConversionFailed { from: "i64".to_string(), to: "u8".to_string() });
+    assert!(CTorParser::create("Window(-1, 1)").is_err());
+}
+
+
+struct Smoother {
+    enabled: bool,
+    alpha: f64
+}
+
+#[reflect_impl]
+impl Smoother {
+    fn new (enabled: bool, alpha: f64) -> Self {
+        Smoother { enabled, alpha }
+    }
+}
+
+
+#[test]
+fn test_bool() {
+    let obj = CTorParser::create("Smoother(true, 0.9)").expect("failed to create");
+    let smoother = obj.downcast_ref::<Smoother>().unwrap();
+    assert!(smoother.enabled);
+    assert_eq!(smoother.alpha, 0.9);
+
+    // nonzero integers are true
+    let obj = CTorParser::create("Smoother(0, 0.5)").expect("failed to create");
+    assert!(!obj.downcast_ref::<Smoother>().unwrap().enabled);
+    let obj = CTorParser::create("Smoother(2, 0.5)").expect("failed to create");
+    assert!(obj.downcast_ref::<Smoother>().unwrap().enabled);
+}
+
+
+struct Tag {
+    prefix: String
+}
+
+#[reflect_impl]
+impl Tag {
+    fn new (prefix: &str) -> Self {
+        Tag { prefix: prefix.to_string() }
+    }
+
+    fn label(&self, s: &str) -> String {
+        format!("{}:{}", self.prefix, s)
+    }
+}
+
+
+#[test]
+fn test_str() {
+    let obj = CTorParser::create("Tag(\"px\")").expect("failed to create");
+    assert_eq!(obj.downcast_ref::<Tag>().unwrap().prefix, "px");
+
+    let result = CTorParser::call("Tag(\"px\").label(\"last\")").expect("failed to call");
+    assert_eq!(result.downcast_ref::<String>().unwrap(), "px:last");
+
+    // as are &String and &str arguments
+    let itype = TypeInfo::find_type("Tag").expect("could not find type");
+    let name: &'static String = Box::leak(Box::new("mid".to_string()));
+    let result = itype.call(&obj, "label", &[Box::new(name) as Box<dyn Any>]).expect("failed to call");
+    assert_eq!(result.downcast_ref::<String>().unwrap(), "px:mid");
+    let result = itype.call(&obj, "label", &[Box::new("first") as Box<dyn Any>]).expect("failed to call");
+    assert_eq!(result.downcast_ref::<String>().unwrap(), "px:first");
+}
+
+
+struct Named {
+    name: String
+}
+
+#[reflect_impl]
+impl Named {
+    fn new (name: String) -> Self {
+        Named { name }
+    }
+}
+
+
+#[test]
+fn test_to_string() {
+    let obj = CTorParser::create("Named(42)").expect("failed to create");
+    assert_eq!(obj.downcast_ref::<Named>().unwrap().name, "42");
+
+    let obj = CTorParser::create("Named(2.5)").expect("failed to create");
+    assert_eq!(obj.downcast_ref::<Named>().unwrap().name, "2.5");
+
+    let obj = CTorParser::create("Named(true)").expect("failed to create");
+    assert_eq!(obj.downcast_ref::<Named>().unwrap().name, "true");
+}
+
+
+#[test]
+fn test_string_identity() {
+    let obj = CTorParser::create("Named(\"alpha\")").expect("failed to create");
+    assert_eq!(obj.downcast_ref::<Named>().unwrap().name, "alpha");
+
+    // arguments are boxed as owned strings
+    let tstr = TypeId::of::<String>();
+    let arg = Box::new("beta".to_string()) as Box<dyn Any>;
+    let identity = Conversions::find(tstr, tstr).expect("no identity conversion");
+    assert!(identity.is_equivalent());
+    let converted = identity.convert(&arg).expect("failed to convert");
+    assert_eq!(converted.downcast_ref::<String>().unwrap(), "beta");
+
+    // likewise when parsed to a number
+    let parsed = Conversions::find(tstr, TypeId::of::<i64>()).unwrap()
+        .convert(&(Box::new("42".to_string()) as Box<dyn Any>)).expect("failed to parse");
+    assert_eq!(*parsed.downcast_ref::<i64>().unwrap(), 42);
+    assert!(Conversions::find(tstr, TypeId::of::<i64>()).unwrap().convert(&arg).is_none());
+}
+
+
+struct Splitter {
+    sep: char
+}
+
+#[reflect_impl]
+impl Splitter {
+    fn new (sep: char) -> Self {
+        Splitter { sep }
+    }
+
+    fn separator(&self) -> String {
+        self.sep.to_string()
+    }
+}
+
+
+#[test]
+fn test_char() {
+    let obj = CTorParser::create("Splitter('\\t')").expect("failed to create");
+    assert_eq!(obj.downcast_ref::<Splitter>().unwrap().sep, '\t');
+
+    let obj = CTorParser::create("Splitter(',')").expect("failed to create");
+    assert_eq!(obj.downcast_ref::<Splitter>().unwrap().sep, ',');
+
+    // strings of a single character convert to char
+    let obj = CTorParser::create("Splitter(\"|\")").expect("failed to create");
+    assert_eq!(obj.downcast_ref::<Splitter>().unwrap().sep, '|');
+    assert!(CTorParser::create("Splitter(\"||\")").is_err());
+    assert!(CTorParser::create("Splitter(\"\")").is_err());
+
+    // and char to String
+    let obj = CTorParser::create("Named(';')").expect("failed to create");
+    assert_eq!(obj.downcast_ref::<Named>().unwrap().name, ";");
+}
+
+
+#[derive(Debug, PartialEq)]
+struct Meters(f64);
+
+#[derive(Debug, PartialEq)]
+struct Feet(f64);
+
+
+#[test]
+fn test_add_bidirectional() {
+    Conversions::add_bidirectional(TypeId::of::<Meters>(), TypeId::of::<Feet>(), 120,
+        |v| Some(Box::new(Feet(v.downcast_ref::<Meters>()?.0 / 0.3048)) as Box<dyn Any>),
+        |v| Some(Box::new(Meters(v.downcast_ref::<Feet>()?.0 * 0.3048)) as Box<dyn Any>));
+
+    let to_feet = Conversions::find(TypeId::of::<Meters>(), TypeId::of::<Feet>()).expect("no conversion");
+    let feet = to_feet.convert(&(Box::new(Meters(3.048)) as Box<dyn Any>)).unwrap();
+    assert_eq!(*feet.downcast_ref::<Feet>().unwrap(), Feet(10.0));
+
+    let to_meters = Conversions::find(TypeId::of::<Feet>(), TypeId::of::<Meters>()).expect("no conversion");
+    let meters = to_meters.convert(&(Box::new(Feet(10.0)) as Box<dyn Any>)).unwrap();
+    assert_eq!(*meters.downcast_ref::<Meters>().unwrap(), Meters(3.048));
+}
+
+
+#[test]
+fn test_numeric_family() {
+    // conversions among all primitive numeric types are registered at startup
+    let family = [TypeId::of::<i16>(), TypeId::of::<u8>(), TypeId::of::<f32>(), TypeId::of::<Meters>()];
+    assert_eq!(Conversions::add_numeric_family(&family), 0);
+
+    // narrowing rejects values out of range
+    let narrowing = Conversions::find(TypeId::of::<i16>(), TypeId::of::<u8>()).expect("no conversion");
+    let converted = narrowing.convert(&(Box::new(200i16) as Box<dyn Any>)).unwrap();
+    assert_eq!(*converted.downcast_ref::<u8>().unwrap(), 200u8);
+    assert!(narrowing.convert(&(Box::new(300i16) as Box<dyn Any>)).is_none());
+    assert!(narrowing.convert(&(Box::new(-1i16) as Box<dyn Any>)).is_none());
+
+    // widening
+    let widening = Conversions::find(TypeId::of::<u8>(), TypeId::of::<f32>()).expect("no conversion");
+    let converted = widening.convert(&(Box::new(200u8) as Box<dyn Any>)).unwrap();
+    assert_eq!(*converted.downcast_ref::<f32>().unwrap(), 200.0);
+    assert!(Conversions::find(TypeId::of::<f32>(), TypeId::of::<i16>()).is_some());
+}
+
+
+#[test]
+fn test_list_conversions() {
+    let conversions = Conversions::list();
+    assert!(conversions.contains(&(TypeId::of::<i64>(), TypeId::of::<f64>(), 100)));
+    assert!(conversions.windows(2).all(|w| w[0] <= w[1]));
+
+    assert!(Conversions::can_convert(TypeId::of::<i64>(), TypeId::of::<f64>()));
+    assert!(Conversions::can_convert(TypeId::of::<Scaler>(), TypeId::of::<Scaler>()));
+    assert!(!Conversions::can_convert(TypeId::of::<Scaler>(), TypeId::of::<f64>()));
+
+    assert_eq!(Conversions::score_of(TypeId::of::<i64>(), TypeId::of::<f64>()), Some(100));
+    assert_eq!(Conversions::score_of(TypeId::of::<Vec<f64>>(), TypeId::of::<&[f64]>()), Some(200));
+    assert_eq!(Conversions::score_of(TypeId::of::<bool>(), TypeId::of::<Scaler>()), None);
+}
+
+
+#[test]
+fn test_conversions_dot() {
+    let dot = Conversions::to_dot();
+    assert!(dot.starts_with("digraph conversions {\n"), "{}", dot);
+    assert!(dot.ends_with("}\n"), "{}", dot);
+
+    let score = Conversions::score_of(TypeId::of::<i32>(), TypeId::of::<f64>()).expect("no conversion");
+    assert!(dot.contains(&format!("    \"i32\" -> \"f64\" [label=\"{}\"];\n", score)), "{}", dot);
+    assert!(dot.contains("    \"Vec<f64>\" -> \"&[f64]\" [label=\"200\"];\n"), "{}", dot);
+}
+
+
+#[test]
+fn test_vec_conversions() {
+    // integer list (Vec<i32>) to &[f64]
+    let result = CTorParser::call("Scaler(2).total([1, 2, 3])").expect("failed to call");
+    assert_eq!(*result.downcast_ref::<f64>().unwrap(), 12.0);
+
+    // float list (Vec<f64>) to Vec<i64>, rounding each element
+    let result = CTorParser::call("Scaler(1).rounded([1.4, 2.6])").expect("failed to call");
+    assert_eq!(*result.downcast_ref::<i64>().unwrap(), 4);
+
+    // element conversions score as the scalar conversion
+    assert_eq!(Conversions::score_of(TypeId::of::<Vec<i64>>(), TypeId::of::<&[f64]>()), Some(100));
+    assert_eq!(Conversions::score_of(TypeId::of::<Vec<i32>>(), TypeId::of::<Vec<f64>>()), Some(150));
+    assert!(Conversions::score_of(TypeId::of::<Vec<f64>>(), TypeId::of::<&[u32]>()).is_some());
+}
+
+
+struct Decay {
+    alpha: Option<f64>
+}
+
+#[reflect_impl]
+impl Decay {
+    fn new (alpha: Option<f64>) -> Self {
+        Decay { alpha }
+    }
+
+    fn alpha_or(&self, default: Option<f64>) -> f64 {
+        self.alpha.or(default).unwrap_or(0.0)
+    }
+}
+
+
+#[test]
+fn test_option() {
+    let obj = CTorParser::create("Decay(0.9)").expect("failed to create");
+    assert_eq!(obj.downcast_ref::<Decay>().unwrap().alpha, Some(0.9));
+
+    let obj = CTorParser::create("Decay(null)").expect("failed to create");
+    assert_eq!(obj.downcast_ref::<Decay>().unwrap().alpha, None);
+
+    let result = CTorParser::call("Decay(null).alpha_or(0.5)").expect("failed to call");
+    assert_eq!(*result.downcast_ref::<f64>().unwrap(), 0.5);
+    let result = CTorParser::call("Decay(null).alpha_or(null)").expect("failed to call");
+    assert_eq!(*result.downcast_ref::<f64>().unwrap(), 0.0);
+
+    // an option may also be passed as is
+    let itype = TypeInfo::find_type("Decay").expect("could not find type");
+    let obj = itype.create(&[Box::new(Some(0.25f64)) as Box<dyn Any>]).expect("failed to call ctor");
+    assert_eq!(obj.downcast_ref::<Decay>().unwrap().alpha, Some(0.25));
+    assert!(itype.create(&[Box::new(true) as Box<dyn Any>]).is_err());
+}
+
+
+#[reflect_newtype]
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Bps(f64);
+
+#[reflect_newtype]
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Lots(u32);
+
+struct Quoter {
+    spread: Bps,
+    size: Lots
+}
+
+#[reflect_impl]
+impl Quoter {
+    fn new (spread: Bps, size: Lots) -> Self {
+        Quoter { spread, size }
+    }
+
+    fn spread (&self) -> Bps {
+        self.spread
+    }
+}
+
+
+#[test]
+fn test_newtype() {
+    let obj = CTorParser::create("Quoter(0.0005, 10)").expect("failed to create");
+    let quoter = obj.downcast_ref::<Quoter>().unwrap();
+    assert_eq!(quoter.spread, Bps(0.0005));
+    assert_eq!(quoter.size, Lots(10));
+
+    // integers are converted to the field type, then wrapped
+    let obj = CTorParser::create("Quoter(5, 1)").expect("failed to create");
+    assert_eq!(obj.downcast_ref::<Quoter>().unwrap().spread, Bps(5.0));
+    assert!(CTorParser::create("Quoter(0.0005, -1)").is_err());
+
+    // and unwrapped
+    let spread = CTorParser::call("Quoter(0.0005, 10).spread()").expect("failed to call");
+    let conversion = Conversions::find(TypeId::of::<Bps>(), TypeId::of::<f64>()).expect("no conversion");
+    assert_eq!(*conversion.convert(&spread).unwrap().downcast_ref::<f64>().unwrap(), 0.0005);
+}
+
+
+struct Color {
+    rgb: [f64; 3]
+}
+
+#[reflect_impl]
+impl Color {
+    fn new (rgb: [f64; 3]) -> Self {
+        Color { rgb }
+    }
+
+    fn channels (&self) -> [f64; 3] {
+        self.rgb
+    }
+}
+
+
+#[test]
+fn test_array() {
+    let obj = CTorParser::create("Color([1.0, 0.5, 0.0])").expect("failed to create");
+    assert_eq!(obj.downcast_ref::<Color>().unwrap().rgb, [1.0, 0.5, 0.0]);
+
+    // integer literals are converted element-wise
+    let obj = CTorParser::create("Color([1, 0, 0])").expect("failed to create");
+    assert_eq!(obj.downcast_ref::<Color>().unwrap().rgb, [1.0, 0.0, 0.0]);
+
+    // as is an array given as is
+    let itype = TypeInfo::find_type("Color").expect("could not find type");
+    let obj = itype.create(&[Box::new([0.0, 0.0, 1.0]) as Box<dyn Any>]).expect("failed to call ctor");
+    let channels = itype.call_as::<[f64; 3]>(&obj, "channels", &[]).expect("failed to call");
+    assert_eq!(*channels, [0.0, 0.0, 1.0]);
+
+    // lists of other lengths fail to convert
+    let err = itype.create(&[Box::new(vec![1.0, 0.5]) as Box<dyn Any>]).unwrap_err();
+    assert_eq!(err, ReflectError::ConversionFailed { from: "Vec<f64>".to_string(), to: "[f64; 3]".to_string() });
+    assert!(CTorParser::create("Color([1, 0, 0, 1])").is_err());
+}
+
+
+struct RingBuffer {
+    buffer: VecDeque<f64>,
+    ids: HashSet<i32>,
+    names: BTreeSet<String>
+}
+
+#[reflect_impl]
+impl RingBuffer {
+    fn new (buffer: VecDeque<f64>, ids: HashSet<i32>) -> Self {
+        RingBuffer { buffer, ids, names: BTreeSet::new() }
+    }
+
+    fn named (names: BTreeSet<String>) -> Self {
+        RingBuffer { buffer: VecDeque::new(), ids: HashSet::new(), names }
+    }
+}
+
+
+#[test]
+fn test_collections() {
+    let obj = CTorParser::create("RingBuffer([1.0, 2.5], [3, 1, 3])").expect("failed to create");
+    let window = obj.downcast_ref::<RingBuffer>().unwrap();
+    assert_eq!(window.buffer, VecDeque::from([1.0, 2.5]));
+    assert_eq!(window.ids, HashSet::from([1, 3]));
+
+    // elements are converted as for vectors
+    let obj = CTorParser::create("RingBuffer([1, 2], [7])").expect("failed to create");
+    let window = obj.downcast_ref::<RingBuffer>().unwrap();
+    assert_eq!(window.buffer, VecDeque::from([1.0, 2.0]));
+    assert_eq!(window.ids, HashSet::from([7]));
+    assert!(CTorParser::create("RingBuffer([1.0], [x])").is_err());
+
+    let obj = CTorParser::create("RingBuffer([b, a, b])").expect("failed to create");
+    let names: Vec<&String> = obj.downcast_ref::<RingBuffer>().unwrap().names.iter().collect();
+    assert_eq!(names, vec!["a", "b"]);
+}
+
+
+#[derive(Clone, Debug, PartialEq)]
+struct Price(f64);
+
+struct LimitOrder {
+    price: Price
+}
+
+#[reflect_impl]
+impl LimitOrder {
+    fn new (price: Price) -> Self {
+        LimitOrder { price }
+    }
+}
+
+
+#[test]
+fn test_closure_conversion() {
+    // prices are rounded down to a tick size, as configured at registration
+    let tick = 0.25;
+    Conversions::add_fn(TypeId::of::<f64>(), TypeId::of::<Price>(), 150, move |v: &Box<dyn Any>| {
+        v.downcast_ref::<f64>().map(|x| Box::new(Price((x / tick).floor() * tick)) as Box<dyn Any>)
+    });
+
+    let obj = CTorParser::create("LimitOrder(101.37)").expect("failed to create");
+    assert_eq!(obj.downcast_ref::<LimitOrder>().unwrap().price, Price(101.25));
+
+    let conversion = Conversions::find(TypeId::of::<f64>(), TypeId::of::<Price>()).expect("no conversion");
+    let price = conversion.convert(&(Box::new(3.9) as Box<dyn Any>)).expect("failed to convert");
+    assert_eq!(*price.downcast_ref::<Price>().unwrap(), Price(3.75));
+}
+
+
+#[derive(Clone)]
+struct Point {
+    x: f64,
+    y: f64
+}
+
+#[reflect_impl]
+impl Point {
+    fn new (x: f64, y: f64) -> Self {
+        Point { x, y }
+    }
+}
+
+#[derive(Clone)]
+struct Anchor {
+    point: Point
+}
+
+#[reflect_impl(auto_convert)]
+impl Anchor {
+    fn new (point: Point) -> Self {
+        Anchor { point }
+    }
+
+    fn at_x (x: f64) -> Self {
+        Anchor { point: Point { x, y: 0.0 } }
+    }
+}
+
+struct Label {
+    anchor: Anchor,
+    text: String
+}
+
+#[reflect_impl]
+impl Label {
+    fn new (anchor: Anchor, text: String) -> Self {
+        Label { anchor, text }
+    }
+}
+
+
+#[test]
+fn test_auto_convert() {
+    // a bare Point is wrapped as an Anchor by the ctor of Anchor
+    let obj = CTorParser::create("Label(Point(1.0, 2.0), origin)").expect("failed to create");
+    let label = obj.downcast_ref::<Label>().unwrap();
+    assert_eq!((label.anchor.point.x, label.anchor.point.y, label.text.as_str()), (1.0, 2.0, "origin"));
+
+    let score = Conversions::score_of(TypeId::of::<Point>(), TypeId::of::<Anchor>());
+    assert_eq!(score, Some(Conversions::AUTO));
+
+    // ctors of primitives are not conversions
+    assert!(!Conversions::can_convert(TypeId::of::<f64>(), TypeId::of::<Anchor>()));
+    assert!(CTorParser::create("Label(1.0, origin)").is_err());
+}
@@ -0,0 +1,44 @@
+use reflect::{Conversions, CTorParser, ReflectError};
+use reflect_macros::reflect_impl;
+
+
+// strict mode is global, so is tested in its own test binary (tests within a binary run in
+// parallel)
+
+struct Lots {
+    count: i32,
+    ratio: f32
+}
+
+#[reflect_impl]
+impl Lots {
+    fn new (count: i32, ratio: f32) -> Self {
+        Lots { count, ratio }
+    }
+}
+
+
+#[test]
+fn test_strict() {
+    // lossy by default: out of range values saturate
+    assert!(!Conversions::is_strict());
+    let obj = CTorParser::create("Lots(1e20, 0.5)").expect("failed to create");
+    assert_eq!(obj.downcast_ref::<Lots>().unwrap().count, i32::MAX);
+    let obj = CTorParser::create("Lots(1, 1e300)").expect("failed to create");
+    assert!(obj.downcast_ref::<Lots>().unwrap().ratio.is_infinite());
+
+    // strict: out of range values fail to convert
+    Conversions::set_strict(true);
+    let err = CTorParser::create("Lots(1e20, 0.5)").unwrap_err();
+    assert_eq!(err, ReflectError::ConversionFailed { from: "f64".to_string(), to: "i32".to_string() });
+    let err = CTorParser::create("Lots(1, 1e300)").unwrap_err();
+    assert_eq!(err, ReflectError::ConversionFailed { from: "f64".to_string(), to: "f32".to_string() });
+
+    // values within range still convert
+    let obj = CTorParser::create("Lots(-2147483648.0, 0.5)").expect("failed to create");
+    let lots = obj.downcast_ref::<Lots>().unwrap();
+    assert_eq!(lots.count, i32::MIN);
+    assert_eq!(lots.ratio, 0.5);
+
+    Conversions::set_strict(false);
+}
@@ -0,0 +1,56 @@
+use reflect::{Conversions, CTorParser};
+use reflect_macros::reflect_impl;
+use std::any::{Any, TypeId};
+
+
+// chaining of conversions is global, so is tested in its own test binary (tests within a binary
+// run in parallel)
+
+#[derive(Clone)]
+struct Celsius(f64);
+
+#[derive(Clone)]
+struct Kelvin(f64);
+
+struct Thermostat {
+    setpoint: Kelvin
+}
+
+#[reflect_impl]
+impl Thermostat {
+    fn new (setpoint: Kelvin) -> Self {
+        Thermostat { setpoint }
+    }
+}
+
+
+#[test]
+fn test_transitive() {
+    // i64 -> Celsius -> Kelvin, as well as Kelvin -> Celsius (a cycle)
+    Conversions::add(TypeId::of::<i64>(), TypeId::of::<Celsius>(), 100,
+        |v: &Box<dyn Any>| Some(Box::new(Celsius(*v.downcast_ref::<i64>()? as f64)) as Box<dyn Any>));
+    Conversions::add(TypeId::of::<Celsius>(), TypeId::of::<Kelvin>(), 150,
+        |v: &Box<dyn Any>| Some(Box::new(Kelvin(v.downcast_ref::<Celsius>()?.0 + 273.15)) as Box<dyn Any>));
+    Conversions::add(TypeId::of::<Kelvin>(), TypeId::of::<Celsius>(), 150,
+        |v: &Box<dyn Any>| Some(Box::new(Celsius(v.downcast_ref::<Kelvin>()?.0 - 273.15)) as Box<dyn Any>));
+
+    // no direct conversion
+    assert!(!Conversions::is_transitive());
+    assert!(Conversions::find(TypeId::of::<i64>(), TypeId::of::<Kelvin>()).is_none());
+    assert!(CTorParser::create("Thermostat(20)").is_err());
+
+    // two hop chain, scoring 100 * 150 / 200 less the hop penalty
+    Conversions::set_transitive(true);
+    let chain = Conversions::find(TypeId::of::<i64>(), TypeId::of::<Kelvin>()).expect("no chain");
+    assert!(!chain.is_equivalent());
+    assert_eq!(Conversions::score(&[TypeId::of::<Kelvin>()], &[Box::new(20i64) as Box<dyn Any>]), 75 - Conversions::HOP_PENALTY);
+
+    let obj = CTorParser::create("Thermostat(20)").expect("failed to create");
+    assert_eq!(obj.downcast_ref::<Thermostat>().unwrap().setpoint.0, 293.15);
+
+    // bool -> String -> i64 -> Celsius -> Kelvin exceeds the hop limit
+    assert!(CTorParser::create("Thermostat(true)").is_err());
+
+    Conversions::set_transitive(false);
+    assert!(CTorParser::create("Thermostat(20)").is_err());
+}
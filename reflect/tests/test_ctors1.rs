@@ -1,7 +1,9 @@
+#![allow(clippy::approx_constant)]
 
-use reflect::{TypeInfo, Constructor, Method };
+
+use reflect::{TypeInfo, CTorParser, Conversions, ConvertedArg, ReflectError, Selected, registered_types, registry_summary};
 use reflect_macros::reflect_impl;
-use std::any::Any;
+use std::any::{Any, TypeId};
 
 
 struct Test1 {
@@ -12,14 +14,14 @@ struct Test1 {
 #[reflect_impl]
 impl Test1 {
     fn create1 (a: i32) -> Self {
-        return Test1 { alpha: a, beta: f64::from(a) * f64::from(a) };
+        Test1 { alpha: a, beta: f64::from(a) * f64::from(a) }
     }
     fn create2 (a: i32, b: f64) -> Self {
-        return Test1 { alpha: a, beta: b };
+        Test1 { alpha: a, beta: b }
     }
 
     fn f(&self, x: i32) -> i32 {
-        return x * self.alpha;
+        x * self.alpha
     }
 
     fn g(&self, vec: &[f64]) -> f64 {
@@ -29,6 +31,10 @@ impl Test1 {
         }
         cum
     }
+
+    fn square (x: i32) -> i32 {
+        x * x
+    }
 }
 
 
@@ -38,7 +44,7 @@ fn test_ctors1() {
         Box::new(3i32) as Box<dyn Any>,
         Box::new(3.1415926f64) as Box<dyn Any>
     ];
-    let itype = TypeInfo::find_type(&String::from("Test1")).expect("could not find type");
+    let itype = TypeInfo::find_type("Test1").expect("could not find type");
 
     // create object
     let rawobj = itype.create(&args).expect("failed to call ctor");
@@ -60,7 +66,7 @@ fn test_method1() {
         Box::new(4i32) as Box<dyn Any>
     ];
 
-    let itype = TypeInfo::find_type(&String::from("Test1")).expect("could not find type");
+    let itype = TypeInfo::find_type("Test1").expect("could not find type");
 
     // create object
     let rawobj = itype.create(&args_ctor).expect("failed to call ctor");
@@ -84,7 +90,7 @@ fn test_method2() {
         Box::new(vec![3.1, 4.2, 5.1]) as Box<dyn Any>
     ];
 
-    let itype = TypeInfo::find_type(&String::from("Test1")).expect("could not find type");
+    let itype = TypeInfo::find_type("Test1").expect("could not find type");
 
     // create object
     let rawobj = itype.create(&args_ctor).expect("failed to call ctor");
@@ -97,3 +103,411 @@ fn test_method2() {
 
     assert_eq!(*result, 12.4);
 }
+
+
+#[test]
+fn test_no_matching_signatures() {
+    let args = vec![
+        Box::new(true) as Box<dyn Any>,
+        Box::new(true) as Box<dyn Any>,
+        Box::new(true) as Box<dyn Any>
+    ];
+    let itype = TypeInfo::find_type("Test1").expect("could not find type");
+
+    // error lists the signature of each ctor
+    let err = itype.create(&args).unwrap_err().to_string();
+    assert!(err.starts_with("could not find ctor of 'Test1' for 3 arguments, expected "), "{}", err);
+    assert!(err.contains("(i32, f64)") && err.contains("(i32)") && err.contains(" or "), "{}", err);
+
+    // as well as the signature of the method
+    let rawobj = itype.create(&[Box::new(3i32) as Box<dyn Any>]).expect("failed to call ctor");
+    let err = itype.call(&rawobj, "g", &args).unwrap_err().to_string();
+    assert!(err.ends_with("expected (&[f64])"), "{}", err);
+}
+
+
+#[test]
+fn test_introspection() {
+    let itype = TypeInfo::find_type("Test1").expect("could not find type");
+
+    assert_eq!(itype.method_names(), vec!["f", "g"]);
+    assert_eq!(itype.static_names(), vec!["square"]);
+
+    let mut arities = itype.constructor_arities();
+    arities.sort();
+    assert_eq!(arities, vec![1, 2]);
+
+    let signature = itype.signature("g").expect("could not find signature");
+    assert_eq!(signature.arg_types, vec![TypeId::of::<&[f64]>()]);
+    assert_eq!(signature.arg_type_names, vec!["&[f64]"]);
+    assert_eq!(signature.return_type, TypeId::of::<f64>());
+    assert_eq!(signature.return_type_name, "f64");
+    assert_eq!(signature.to_string(), "(&[f64])");
+
+    let signature = itype.signature("square").expect("could not find signature");
+    assert_eq!(signature.arg_type_names, vec!["i32"]);
+
+    // return type names are recorded by the functions themselves
+    assert_eq!(itype.methods["g"][0].return_type_name(), "f64");
+    assert_eq!(itype.constructors[0].return_type_name(), "Test1");
+    assert_eq!(itype.constructors[0].signature().return_type_name, "Test1");
+
+    assert!(itype.signature("unknown").is_none());
+}
+
+
+#[test]
+fn test_registered_types() {
+    let names = registered_types();
+    assert!(names.contains(&"Test1".to_string()));
+    assert!(names.windows(2).all(|w| w[0] <= w[1]));
+
+    let summary = registry_summary();
+    assert!(summary.contains(&("Test1".to_string(), 2, 2)));
+}
+
+
+struct Probability {
+    p: f64
+}
+
+#[reflect_impl]
+impl Probability {
+    fn new (p: f64) -> Result<Self, String> {
+        if (0.0..=1.0).contains(&p) {
+            Ok(Probability { p })
+        } else {
+            Err(format!("probability {} not within [0, 1]", p))
+        }
+    }
+
+    fn odds (&self) -> Result<f64, String> {
+        if self.p < 1.0 {
+            Ok(self.p / (1.0 - self.p))
+        } else {
+            Err("odds are infinite".to_string())
+        }
+    }
+}
+
+
+#[test]
+fn test_result_ctor() {
+    let itype = TypeInfo::find_type("Probability").expect("could not find type");
+
+    let obj = itype.create(&[Box::new(0.25f64) as Box<dyn Any>]).expect("failed to call ctor");
+    assert_eq!(obj.downcast_ref::<Probability>().unwrap().p, 0.25);
+
+    let err = itype.create(&[Box::new(1.5f64) as Box<dyn Any>]).unwrap_err();
+    assert_eq!(err, ReflectError::CallFailed("probability 1.5 not within [0, 1]".to_string()));
+
+    // methods returning Result yield the value or the error
+    let odds = itype.call(&obj, "odds", &[]).expect("failed to call");
+    assert_eq!(*odds.downcast_ref::<f64>().unwrap(), 1.0 / 3.0);
+    assert_eq!(itype.signature("odds").unwrap().return_type, TypeId::of::<f64>());
+
+    let certain = itype.create(&[Box::new(1i32) as Box<dyn Any>]).expect("failed to call ctor");
+    let err = itype.call(&certain, "odds", &[]).unwrap_err();
+    assert_eq!(err.to_string(), "odds are infinite");
+}
+
+
+#[test]
+fn test_create_as() {
+    let itype = TypeInfo::find_type("Test1").expect("could not find type");
+
+    let obj = itype.create_as::<Test1>(&[Box::new(3i32) as Box<dyn Any>]).expect("failed to call ctor");
+    assert_eq!(obj.alpha, 3);
+    assert_eq!(obj.beta, 9.0);
+
+    let err = itype.create_as::<f64>(&[Box::new(3i32) as Box<dyn Any>]).unwrap_err();
+    assert_eq!(err, ReflectError::DowncastFailed { expected: "f64".to_string(), actual: "Test1".to_string() });
+
+    let rawobj = itype.create(&[Box::new(3i32) as Box<dyn Any>]).expect("failed to call ctor");
+    let result = itype.call_as::<i32>(&rawobj, "f", &[Box::new(4i32) as Box<dyn Any>]).expect("failed to call");
+    assert_eq!(*result, 12);
+    assert!(itype.call_as::<f64>(&rawobj, "f", &[Box::new(4i32) as Box<dyn Any>]).is_err());
+}
+
+
+#[test]
+fn test_create_by_name() {
+    let obj = reflect::create("Test1", &[Box::new(3i32) as Box<dyn Any>]).expect("failed to call ctor");
+    assert_eq!(obj.downcast_ref::<Test1>().unwrap().alpha, 3);
+
+    let err = reflect::create("Unknown", &[]).unwrap_err();
+    assert_eq!(err, ReflectError::TypeNotFound("Unknown".to_string()));
+}
+
+
+#[test]
+fn test_concurrent_find_type() {
+    const THREADS: usize = 8;
+    const LOOKUPS: usize = 1_000;
+
+    // lookups proceed in parallel (the registry is read-locked)
+    let handles: Vec<_> = (0..THREADS).map(|_| std::thread::spawn(|| {
+        (0..LOOKUPS).filter(|_| TypeInfo::find_type("Test1").is_some()).count()
+    })).collect();
+    let found: usize = handles.into_iter().map(|h| h.join().unwrap()).sum();
+    assert_eq!(found, THREADS * LOOKUPS);
+}
+
+
+// cargo test --release --test test_ctors1 -- --ignored --nocapture
+#[test]
+#[ignore]
+fn bench_concurrent_find_type() {
+    const THREADS: usize = 8;
+    const LOOKUPS: usize = 100_000;
+
+    // time lookups on several threads, each taking the given lock around its lookups
+    fn lookups (lock: std::sync::Arc<std::sync::Mutex<()>>, serialized: bool) -> std::time::Duration {
+        let start = std::time::Instant::now();
+        let handles: Vec<_> = (0..THREADS).map(|_| {
+            let lock = lock.clone();
+            std::thread::spawn(move || (0..LOOKUPS).filter(|_| {
+                let _guard = serialized.then(|| lock.lock().unwrap());
+                TypeInfo::find_type("Test1").is_some()
+            }).count())
+        }).collect();
+        let found: usize = handles.into_iter().map(|h| h.join().unwrap()).sum();
+        assert_eq!(found, THREADS * LOOKUPS);
+        start.elapsed()
+    }
+
+    // lookups serialized by a mutex, as when the registry was a `Mutex<HashMap>`, versus
+    // lookups in parallel under the read lock
+    let lock = std::sync::Arc::new(std::sync::Mutex::new(()));
+    let serialized = lookups(lock.clone(), true);
+    let parallel = lookups(lock, false);
+
+    println!("{} lookups on {} threads: serialized {:?}, parallel {:?}", THREADS * LOOKUPS, THREADS, serialized, parallel);
+    assert!(parallel < serialized);
+}
+
+
+#[test]
+fn test_repeated_create() {
+    const CREATES: usize = 1_000;
+    let itype = TypeInfo::find_type("Test1").expect("could not find type");
+
+    // (i64, i64) requires conversion to (i32, f64), planned once and reused thereafter
+    for i in 0..CREATES {
+        let args = vec![Box::new(i as i64) as Box<dyn Any>, Box::new(2i64) as Box<dyn Any>];
+        let obj = itype.create_as::<Test1>(&args).expect("failed to call ctor");
+        assert_eq!(obj.alpha, i as i32);
+        assert_eq!(obj.beta, 2.0);
+    }
+}
+
+
+// cargo test --release --test test_ctors1 -- --ignored --nocapture
+#[test]
+#[ignore]
+fn bench_repeated_create() {
+    const CREATES: u32 = 100_000;
+    let itype = TypeInfo::find_type("Test1").expect("could not find type");
+    let args = vec![Box::new(3i64) as Box<dyn Any>, Box::new(2i64) as Box<dyn Any>];
+
+    // create uses the conversion plan cached for (i64, i64)
+    let start = std::time::Instant::now();
+    for _ in 0..CREATES {
+        itype.create(&args).expect("failed to call ctor");
+    }
+    let cached = start.elapsed();
+
+    // whereas the plan is made anew on every call once the conversions change, here setting
+    // transitivity as it is (which invalidates plans all the same)
+    let transitive = Conversions::is_transitive();
+    let mut uncached = std::time::Duration::ZERO;
+    for _ in 0..CREATES {
+        Conversions::set_transitive(transitive);
+        let start = std::time::Instant::now();
+        itype.create(&args).expect("failed to call ctor");
+        uncached += start.elapsed();
+    }
+
+    println!("create: {:?} / call cached, {:?} / call uncached", cached / CREATES, uncached / CREATES);
+    assert!(cached < uncached);
+}
+
+
+struct Origin {
+    x: f64
+}
+
+#[reflect_impl]
+impl Origin {
+    fn new () -> Self {
+        Origin { x: 0.0 }
+    }
+
+    fn at (x: f64) -> Self {
+        Origin { x }
+    }
+
+    fn norm (&self) -> f64 {
+        self.x.abs()
+    }
+}
+
+
+#[test]
+fn test_no_arguments() {
+    let itype = TypeInfo::find_type("Origin").expect("could not find type");
+
+    // no arguments matches the no-arg ctor
+    let obj = itype.create(&[]).expect("failed to call ctor");
+    assert_eq!(obj.downcast_ref::<Origin>().unwrap().x, 0.0);
+    let obj = itype.create(&[Box::new(-2.0f64) as Box<dyn Any>]).expect("failed to call ctor");
+    assert_eq!(obj.downcast_ref::<Origin>().unwrap().x, -2.0);
+
+    // as well as no-arg methods
+    let result = itype.call(&obj, "norm", &[]).expect("failed to call");
+    assert_eq!(*result.downcast_ref::<f64>().unwrap(), 2.0);
+    assert!(itype.call(&obj, "norm", &[Box::new(1i32) as Box<dyn Any>]).is_err());
+
+    // both from expressions
+    let obj = CTorParser::create("Origin()").expect("failed to create");
+    assert_eq!(obj.downcast_ref::<Origin>().unwrap().x, 0.0);
+    let result = CTorParser::call("Origin(-3.0).norm()").expect("failed to call");
+    assert_eq!(*result.downcast_ref::<f64>().unwrap(), 3.0);
+
+    assert_eq!(reflect::Conversions::score(&[], &[]), 200);
+    assert!(itype.create_strict(&[]).is_ok());
+}
+
+
+struct Filter {
+    alpha: f64,
+    window: i32,
+    label: String
+}
+
+#[reflect_impl]
+impl Filter {
+    fn new () -> Self {
+        Filter { alpha: 0.5, window: 10, label: String::new() }
+    }
+
+    fn with_alpha (self, alpha: f64) -> Self {
+        Filter { alpha, ..self }
+    }
+
+    fn with_window (mut self, window: i32) -> Self {
+        self.window = window;
+        self
+    }
+
+    fn with_label (self, label: &str) -> Self {
+        Filter { label: label.to_string(), ..self }
+    }
+
+    fn alpha (&self) -> f64 {
+        self.alpha
+    }
+}
+
+
+#[test]
+fn test_builder() {
+    let itype = TypeInfo::find_type("Filter").expect("could not find type");
+
+    // builder methods consume the object
+    let obj = itype.create(&[]).expect("failed to call ctor");
+    let obj = itype.call_consuming(obj, "with_alpha", &[Box::new(0.9) as Box<dyn Any>]).expect("failed to call");
+    let obj = itype.call_consuming(obj, "with_window", &[Box::new(20i64) as Box<dyn Any>]).expect("failed to call");
+    let filter = obj.downcast_ref::<Filter>().unwrap();
+    assert_eq!((filter.alpha, filter.window), (0.9, 20));
+
+    // so cannot be called by reference
+    let err = itype.call(&obj, "with_alpha", &[Box::new(0.1) as Box<dyn Any>]).unwrap_err();
+    assert_eq!(err, ReflectError::ConsumingReceiver { method: "with_alpha".to_string() });
+
+    // other methods can be called consuming the object
+    let alpha = itype.call_consuming(obj, "alpha", &[]).expect("failed to call");
+    assert_eq!(*alpha.downcast_ref::<f64>().unwrap(), 0.9);
+
+    // builder chain from an expression
+    let obj = CTorParser::create("Filter()").expect("failed to create");
+    assert_eq!(obj.downcast_ref::<Filter>().unwrap().window, 10);
+    let obj = CTorParser::call("Filter().with_window(5).with_label(\"fast\").with_alpha(0.25)").expect("failed to call");
+    let filter = obj.downcast_ref::<Filter>().unwrap();
+    assert_eq!((filter.alpha, filter.window, filter.label.as_str()), (0.25, 5, "fast"));
+}
+
+
+#[test]
+fn test_find_type_by_str() {
+    // both lookups take a plain &str, to which a String derefs
+    let itype = TypeInfo::find_type("Test1").expect("could not find type");
+    let found = reflect::find_type("Test1").expect("could not find type");
+    assert!(std::sync::Arc::ptr_eq(&itype, &found));
+
+    let name = String::from("Test1");
+    assert!(TypeInfo::find_type(&name).is_some());
+    assert!(TypeInfo::find_type(&name[..4]).is_none());
+}
+
+
+#[test]
+fn test_create_traced() {
+    let itype = TypeInfo::find_type("Test1").expect("could not find type");
+    let args = vec![Box::new(3i32) as Box<dyn Any>, Box::new(3i64) as Box<dyn Any>];
+    let (obj, trace) = itype.create_traced(&args).expect("failed to call ctor");
+    assert_eq!(obj.downcast_ref::<Test1>().unwrap().beta, 3.0);
+
+    // the first argument is passed as is, the second coerced to f64
+    let coercion = Conversions::score_of(TypeId::of::<i64>(), TypeId::of::<f64>()).unwrap();
+    assert_eq!(trace, vec![
+        ConvertedArg { from: "i32".to_string(), to: "i32".to_string(), score: 200 },
+        ConvertedArg { from: "i64".to_string(), to: "f64".to_string(), score: coercion },
+    ]);
+    assert!(!trace[0].is_converted());
+    assert!(trace[1].is_converted());
+
+    let err = itype.create_traced(&[Box::new(()) as Box<dyn Any>]).unwrap_err();
+    assert!(matches!(err, ReflectError::NoMatchingConstructor { .. }));
+}
+
+
+#[test]
+fn test_create_selected() {
+    let itype = TypeInfo::find_type("Test1").expect("could not find type");
+
+    let (obj, selected) = itype.create_selected(&[Box::new(3i32) as Box<dyn Any>]).expect("failed to call ctor");
+    assert_eq!(obj.downcast_ref::<Test1>().unwrap().beta, 9.0);
+    assert_eq!(selected, Selected { name: "*".to_string(), arg_type_names: vec!["i32".to_string()], score: 200 });
+
+    let args = vec![Box::new(3i32) as Box<dyn Any>, Box::new(2.5f64) as Box<dyn Any>];
+    let (obj, selected) = itype.create_selected(&args).expect("failed to call ctor");
+    assert_eq!(obj.downcast_ref::<Test1>().unwrap().beta, 2.5);
+    assert_eq!(selected.arg_type_names, vec!["i32".to_string(), "f64".to_string()]);
+    assert_eq!(selected.score, 400);
+
+    // converted arguments score lower
+    let args = vec![Box::new(3i64) as Box<dyn Any>, Box::new(2i64) as Box<dyn Any>];
+    let (_, selected) = itype.create_selected(&args).expect("failed to call ctor");
+    assert_eq!(selected.arg_type_names, vec!["i32".to_string(), "f64".to_string()]);
+    assert!(selected.score < 400);
+
+    let (result, selected) = itype.call_selected(&obj, "f", &[Box::new(2i32) as Box<dyn Any>]).expect("failed to call");
+    assert_eq!(*result.downcast_ref::<i32>().unwrap(), 6);
+    assert_eq!(selected.name, "f");
+}
+
+
+#[test]
+fn test_callstatic() {
+    let itype = TypeInfo::find_type("Test1").expect("could not find type");
+    assert_eq!(itype.signature("square").unwrap().return_type_name, "i32");
+
+    // the result is boxed as returned, whether or not the argument is converted
+    let result = itype.callstatic("square", &[Box::new(4i32) as Box<dyn Any>]).expect("failed to call");
+    assert_eq!(*result.downcast_ref::<i32>().expect("not an i32"), 16);
+    let result = itype.callstatic("square", &[Box::new(5i64) as Box<dyn Any>]).expect("failed to call");
+    assert_eq!(*result.downcast_ref::<i32>().expect("not an i32"), 25);
+
+    assert!(matches!(itype.callstatic("cube", &[]).unwrap_err(), ReflectError::MethodNotFound { .. }));
+}
@@ -0,0 +1,138 @@
+use reflect::{CTorParser, TypeInfo};
+use std::any::Any;
+use reflect_macros::{reflect_enum, reflect_impl};
+use std::str::FromStr;
+
+
+#[reflect_enum(case_insensitive)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Smoothing {
+    Simple,
+    Exponential
+}
+
+#[reflect_enum]
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Side {
+    Buy,
+    Sell
+}
+
+
+#[reflect_enum]
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[allow(clippy::upper_case_acronyms)]
+enum MAType {
+    #[reflect(alias = "Simple")]
+    SMA,
+    #[reflect(alias = "Exponential", alias = "ExpMA")]
+    EMA,
+    #[reflect(rename = "Kaufman")]
+    KAMA
+}
+
+
+#[reflect_enum(from_repr)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[repr(i32)]
+enum Priority {
+    Low,
+    Normal,
+    High = 10
+}
+
+
+struct Order {
+    side: Side,
+    smoothing: Smoothing
+}
+
+#[reflect_impl]
+impl Order {
+    fn new (side: Side, smoothing: Smoothing) -> Self {
+        Order { side, smoothing }
+    }
+
+    fn priority (priority: Priority) -> Priority {
+        priority
+    }
+
+    fn level (level: i64) -> i64 {
+        level
+    }
+
+    fn label (name: String) -> String {
+        format!("<{}>", name)
+    }
+}
+
+
+#[test]
+fn test_case_insensitive() {
+    assert_eq!(Smoothing::from_str("simple"), Ok(Smoothing::Simple));
+    assert_eq!(Smoothing::from_str("SIMPLE"), Ok(Smoothing::Simple));
+    assert_eq!(Smoothing::from_str("Exponential"), Ok(Smoothing::Exponential));
+    assert!(Smoothing::from_str("Linear").is_err());
+
+    // strict by default
+    assert_eq!(Side::from_str("Buy"), Ok(Side::Buy));
+    assert!(Side::from_str("buy").is_err());
+
+    // the String -> enum conversion uses the same matching
+    let obj = CTorParser::create("Order(Sell, exponential)").expect("failed to create");
+    let order = obj.downcast_ref::<Order>().unwrap();
+    assert_eq!(order.side, Side::Sell);
+    assert_eq!(order.smoothing, Smoothing::Exponential);
+
+    assert!(CTorParser::create("Order(sell, exponential)").is_err());
+}
+
+
+#[test]
+fn test_alias() {
+    assert_eq!(MAType::from_str("EMA"), Ok(MAType::EMA));
+    assert_eq!(MAType::from_str("Exponential"), Ok(MAType::EMA));
+    assert_eq!(MAType::from_str("ExpMA"), Ok(MAType::EMA));
+    assert_eq!(MAType::from_str("Simple"), Ok(MAType::SMA));
+    assert!(MAType::from_str("exponential").is_err());
+}
+
+
+#[test]
+fn test_display() {
+    assert_eq!(MAType::EMA.to_string(), "EMA");
+    assert_eq!(MAType::from_str(&MAType::EMA.to_string()), Ok(MAType::EMA));
+
+    // renamed variants display and parse by the new name
+    assert_eq!(MAType::KAMA.to_string(), "Kaufman");
+    assert_eq!(MAType::from_str("Kaufman"), Ok(MAType::KAMA));
+    assert!(MAType::from_str("KAMA").is_err());
+
+    // enum -> String conversion
+    let itype = TypeInfo::find_type("Order").expect("could not find type");
+    let result = itype.callstatic("label", &[Box::new(MAType::EMA) as Box<dyn Any>]).expect("failed to call");
+    assert_eq!(result.downcast_ref::<String>().unwrap(), "<EMA>");
+}
+
+
+#[test]
+fn test_from_repr() {
+    let itype = TypeInfo::find_type("Order").expect("could not find type");
+
+    let result = itype.callstatic("priority", &[Box::new(0i64) as Box<dyn Any>]).expect("failed to call");
+    assert_eq!(*result.downcast_ref::<Priority>().unwrap(), Priority::Low);
+    let result = itype.callstatic("priority", &[Box::new(10i32) as Box<dyn Any>]).expect("failed to call");
+    assert_eq!(*result.downcast_ref::<Priority>().unwrap(), Priority::High);
+    assert!(itype.callstatic("priority", &[Box::new(2i64) as Box<dyn Any>]).is_err());
+
+    // names still apply
+    let result = itype.callstatic("priority", &[Box::new("Normal".to_string()) as Box<dyn Any>]).expect("failed to call");
+    assert_eq!(*result.downcast_ref::<Priority>().unwrap(), Priority::Normal);
+
+    // and the reverse
+    let result = itype.callstatic("level", &[Box::new(Priority::High) as Box<dyn Any>]).expect("failed to call");
+    assert_eq!(*result.downcast_ref::<i64>().unwrap(), 10);
+
+    // not enabled by default
+    assert!(CTorParser::create("Order(0, Simple)").is_err());
+}
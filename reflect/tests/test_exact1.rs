@@ -0,0 +1,104 @@
+use reflect::{CTorParser, ReflectError, TypeInfo, is_exact_mode, set_exact_mode};
+use reflect_macros::reflect_impl;
+use std::any::Any;
+
+
+struct Gauge {
+    level: f64
+}
+
+#[reflect_impl]
+impl Gauge {
+    fn new (level: f64) -> Self {
+        Gauge { level }
+    }
+
+    fn scaled (&self, k: f64) -> f64 {
+        self.level * k
+    }
+
+    fn total (&self, xs: &[f64]) -> f64 {
+        xs.iter().sum::<f64>() + self.level
+    }
+
+    fn half (x: f64) -> f64 {
+        x / 2.0
+    }
+}
+
+
+struct Dial {
+    level: f64,
+    gain: f64
+}
+
+#[reflect_impl]
+impl Dial {
+    fn new (level: f64, #[reflect(default = "1.0")] gain: f64) -> Self {
+        Dial { level, gain }
+    }
+}
+
+
+// the mode is global, so is exercised by a single test
+#[test]
+fn test_exact_mode() {
+    let itype = TypeInfo::find_type("Gauge").expect("could not find type");
+    let int_args = [Box::new(4i64) as Box<dyn Any>];
+    let float_args = [Box::new(4.0) as Box<dyn Any>];
+
+    // fuzzy by default, converting i64 to f64
+    assert!(!is_exact_mode());
+    let obj = itype.create(&int_args).expect("failed to create");
+    assert_eq!(*itype.call(&obj, "scaled", &int_args).unwrap().downcast_ref::<f64>().unwrap(), 16.0);
+    assert_eq!(*itype.callstatic("half", &int_args).unwrap().downcast_ref::<f64>().unwrap(), 2.0);
+    assert!(CTorParser::create("Gauge(4)").is_ok());
+    let dial = TypeInfo::find_type("Dial").expect("could not find type");
+    assert!(dial.create_kwargs(&[("level".to_string(), Box::new(4i64) as Box<dyn Any>)]).is_ok());
+    assert!(CTorParser::create("Gauge(level=4)").is_ok());
+
+    set_exact_mode(true);
+
+    // the same arguments fail, naming the types given
+    let err = itype.create(&int_args).unwrap_err();
+    assert_eq!(err, ReflectError::NoExactMatch {
+        type_name: "Gauge".to_string(),
+        method: "*".to_string(),
+        arg_types: vec!["i64".to_string()],
+        candidates: vec!["(f64)".to_string()]
+    });
+    assert_eq!(err.to_string(), "no ctor of 'Gauge' takes (i64) exactly, expected (f64)");
+    let err = itype.call(&obj, "scaled", &int_args).unwrap_err();
+    assert_eq!(err.to_string(), "method 'scaled' of 'Gauge' does not take (i64) exactly, expected (f64)");
+    assert!(matches!(itype.callstatic("half", &int_args).unwrap_err(), ReflectError::NoExactMatch { .. }));
+    assert!(matches!(itype.create_strict(&int_args).unwrap_err(), ReflectError::NoExactMatch { .. }));
+    assert!(CTorParser::create("Gauge(4)").is_err());
+
+    // as do arguments by name, whether converted or leaving a parameter to its default
+    let err = dial.create_kwargs(&[("level".to_string(), Box::new(4i64) as Box<dyn Any>), ("gain".to_string(), Box::new(2.0) as Box<dyn Any>)]).unwrap_err();
+    assert_eq!(err.to_string(), "no ctor of 'Dial' takes (level: i64, gain: f64) exactly, expected (f64, f64)");
+    assert!(matches!(dial.create_kwargs(&[("level".to_string(), Box::new(4.0) as Box<dyn Any>)]).unwrap_err(), ReflectError::NoExactMatch { .. }));
+    assert!(CTorParser::create("Gauge(level=4)").is_err());
+    assert!(CTorParser::create("Dial(4.0)").is_err());
+
+    // validation agrees with creation
+    for expr in ["Gauge(4)", "Gauge(level=4)", "Dial(4.0)", "Gauge(4.0).scaled(2)"] {
+        assert!(CTorParser::validate(expr).is_err(), "{}", expr);
+    }
+    assert_eq!(CTorParser::validate("Gauge(4.0).scaled(2.0)"), Ok(()));
+    assert_eq!(CTorParser::validate("Dial(gain=2.0, level=4.0)"), Ok(()));
+
+    // whereas arguments of the parameter types (or equivalent) succeed
+    let obj = itype.create(&float_args).expect("failed to create");
+    assert_eq!(*itype.call(&obj, "scaled", &float_args).unwrap().downcast_ref::<f64>().unwrap(), 16.0);
+    assert_eq!(*itype.callstatic("half", &float_args).unwrap().downcast_ref::<f64>().unwrap(), 2.0);
+    let total = itype.call(&obj, "total", &[Box::new(vec![1.0, 2.0]) as Box<dyn Any>]).unwrap();
+    assert_eq!(*total.downcast_ref::<f64>().unwrap(), 7.0);
+    assert!(CTorParser::create("Gauge(4.0)").is_ok());
+    let obj = CTorParser::create("Dial(gain=2.0, level=4.0)").expect("failed to create");
+    let created = obj.downcast_ref::<Dial>().unwrap();
+    assert_eq!((created.level, created.gain), (4.0, 2.0));
+
+    set_exact_mode(false);
+    assert!(itype.create(&int_args).is_ok());
+}
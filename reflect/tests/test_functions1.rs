@@ -0,0 +1,95 @@
+use reflect::{CTorParser, ReflectError, call_function};
+use reflect_macros::{reflect_fn, reflect_impl};
+use std::any::Any;
+
+
+#[reflect_fn]
+fn blend (a: f64, b: f64, w: f64) -> f64 {
+    a * (1.0 - w) + b * w
+}
+
+#[reflect_fn]
+#[reflect(rename = "clamp01")]
+fn clamp_to_unit (x: f64) -> f64 {
+    x.clamp(0.0, 1.0)
+}
+
+#[reflect_fn]
+fn reciprocal (x: f64) -> Option<f64> {
+    (x != 0.0).then(|| 1.0 / x)
+}
+
+
+struct Weight {
+    w: f64
+}
+
+#[reflect_impl]
+impl Weight {
+    fn new (w: f64) -> Self {
+        Weight { w }
+    }
+}
+
+
+#[test]
+fn test_call_function() {
+    let args = vec![
+        Box::new(1.0f64) as Box<dyn Any>,
+        Box::new(2i32) as Box<dyn Any>,
+        Box::new(0.25f64) as Box<dyn Any>
+    ];
+    let result = call_function("blend", &args).expect("failed to call");
+    assert_eq!(*result.downcast_ref::<f64>().unwrap(), 1.25);
+
+    let result = call_function("clamp01", &[Box::new(1.5f64) as Box<dyn Any>]).expect("failed to call");
+    assert_eq!(*result.downcast_ref::<f64>().unwrap(), 1.0);
+
+    // still callable from rust
+    assert_eq!(clamp_to_unit(-1.0), 0.0);
+
+    let err = call_function("unknown", &[]).unwrap_err();
+    assert!(matches!(err, ReflectError::MethodNotFound { .. }));
+}
+
+
+#[test]
+fn test_parse_function() {
+    let result = CTorParser::create("blend(1.0, 2.0, 0.5)").expect("failed to call");
+    assert_eq!(*result.downcast_ref::<f64>().unwrap(), 1.5);
+
+    // nested within a ctor
+    let obj = CTorParser::create("Weight(clamp01(blend(0.0, 4.0, 0.5)))").expect("failed to create");
+    assert_eq!(obj.downcast_ref::<Weight>().unwrap().w, 1.0);
+
+    let err = CTorParser::create("unknown(1.0)").unwrap_err();
+    assert_eq!(err, ReflectError::TypeNotFound("unknown".to_string()));
+}
+
+
+#[test]
+fn test_eval() {
+    let weight: f64 = CTorParser::eval("blend(0.2, 0.8, 0.5)").expect("failed to evaluate");
+    assert_eq!(weight, 0.5);
+
+    // literals and objects
+    assert_eq!(CTorParser::eval::<i64>("42").expect("failed to evaluate"), 42);
+    assert_eq!(CTorParser::eval::<Weight>("Weight(0.3)").expect("failed to evaluate").w, 0.3);
+
+    let err = CTorParser::eval::<i32>("blend(0.2, 0.8, 0.5)").unwrap_err();
+    assert_eq!(err, ReflectError::DowncastFailed { expected: "i32".to_string(), actual: "f64".to_string() });
+    assert!(CTorParser::eval::<f64>("blend(0.2,").is_err());
+}
+
+
+#[test]
+fn test_option_return() {
+    // the Option itself is returned
+    let result = call_function("reciprocal", &[Box::new(4.0) as Box<dyn Any>]).expect("failed to call");
+    assert_eq!(*result.downcast_ref::<Option<f64>>().unwrap(), Some(0.25));
+
+    // which validation takes as the type of the argument, as does creation
+    assert!(CTorParser::create("Weight(reciprocal(4.0))").is_err());
+    assert!(CTorParser::validate("Weight(reciprocal(4.0))").is_err());
+    assert!(CTorParser::validate("Weight(clamp01(4.0))").is_ok());
+}
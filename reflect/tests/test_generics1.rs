@@ -0,0 +1,126 @@
+use reflect::{TypeInfo, registered_name, registered_types};
+use reflect_macros::reflect_impl;
+use std::any::Any;
+use std::borrow::Cow;
+
+
+struct Buffer<T> {
+    values: Vec<T>
+}
+
+#[reflect_impl]
+impl Buffer<f64> {
+    fn new (values: &[f64]) -> Self {
+        Buffer { values: values.to_vec() }
+    }
+
+    fn total (&self) -> f64 {
+        self.values.iter().sum()
+    }
+}
+
+#[reflect_impl]
+impl Buffer<i32> {
+    fn new (values: &[i32]) -> Self {
+        Buffer { values: values.to_vec() }
+    }
+
+    fn total (&self) -> i32 {
+        self.values.iter().sum()
+    }
+}
+
+
+#[test]
+fn test_generic_instantiations() {
+    let names = registered_types();
+    assert!(names.contains(&"Buffer<f64>".to_string()), "{:?}", names);
+    assert!(names.contains(&"Buffer<i32>".to_string()), "{:?}", names);
+
+    let ftype = TypeInfo::find_type("Buffer<f64>").expect("could not find type");
+    let obj = ftype.create(&[Box::new(vec![1.5, 2.0]) as Box<dyn Any>]).expect("failed to call ctor");
+    assert_eq!(obj.downcast_ref::<Buffer<f64>>().unwrap().values, vec![1.5, 2.0]);
+    let total = ftype.call(&obj, "total", &[]).expect("failed to call");
+    assert_eq!(*total.downcast_ref::<f64>().unwrap(), 3.5);
+
+    let itype = TypeInfo::find_type("Buffer<i32>").expect("could not find type");
+    let obj = itype.create(&[Box::new(vec![1, 2]) as Box<dyn Any>]).expect("failed to call ctor");
+    assert_eq!(obj.downcast_ref::<Buffer<i32>>().unwrap().values, vec![1, 2]);
+    let total = itype.call(&obj, "total", &[]).expect("failed to call");
+    assert_eq!(*total.downcast_ref::<i32>().unwrap(), 3);
+}
+
+
+// a type with a lifetime parameter, reflected bound to 'static
+struct Tokens<'a> {
+    text: Cow<'a, str>
+}
+
+#[reflect_impl]
+impl Tokens<'static> where Self: Send {
+    fn new (text: String) -> Self {
+        Tokens { text: Cow::Owned(text) }
+    }
+
+    fn count (&self) -> usize {
+        self.text.split_whitespace().count()
+    }
+}
+
+
+#[test]
+fn test_lifetime_bound_to_static() {
+    let itype = TypeInfo::find_type("Tokens").expect("could not find type");
+    let obj = itype.create(&[Box::new("buy 100 AAPL".to_string()) as Box<dyn Any>]).expect("failed to call ctor");
+    let count = itype.call(&obj, "count", &[]).expect("failed to call");
+    assert_eq!(*count.downcast_ref::<usize>().unwrap(), 3);
+}
+
+
+// types with several lifetime parameters, each dropped from the registered name
+struct Span<'a, 'b> {
+    left: &'a str,
+    right: &'b str
+}
+
+#[reflect_impl]
+impl Span<'static, 'static> {
+    fn new () -> Self {
+        Span { left: "bid", right: "ask" }
+    }
+
+    fn joined (&self) -> String {
+        format!("{}/{}", self.left, self.right)
+    }
+}
+
+struct Labelled<'a, T> {
+    label: &'a str,
+    value: T
+}
+
+#[reflect_impl]
+impl Labelled<'static, f64> {
+    fn new (value: f64) -> Self {
+        Labelled { label: "px", value }
+    }
+
+    fn describe (&self) -> String {
+        format!("{}={}", self.label, self.value)
+    }
+}
+
+
+#[test]
+fn test_several_lifetimes() {
+    assert_eq!(registered_name::<Span<'static, 'static>>(), "Span");
+    assert_eq!(registered_name::<Labelled<'static, f64>>(), "Labelled<f64>");
+
+    let itype = TypeInfo::find_type("Span").expect("could not find type");
+    let obj = itype.create(&[]).expect("failed to call ctor");
+    assert_eq!(itype.call(&obj, "joined", &[]).unwrap().downcast_ref::<String>().unwrap(), "bid/ask");
+
+    let itype = TypeInfo::find_type("Labelled<f64>").expect("could not find type");
+    let obj = itype.create(&[Box::new(1.5) as Box<dyn Any>]).expect("failed to call ctor");
+    assert_eq!(itype.call(&obj, "describe", &[]).unwrap().downcast_ref::<String>().unwrap(), "px=1.5");
+}
@@ -0,0 +1,184 @@
+use reflect::{TypeInfo, CTorParser, ReflectError, registered_types, registered_name, find_type_by_id};
+use reflect_macros::reflect_impl;
+use std::any::{Any, TypeId};
+
+
+// the trait is not in scope where it is implemented
+mod shapes {
+    pub trait Shape {
+        fn area (&self) -> f64;
+        fn scaled (&self, factor: f64) -> Self;
+
+        fn describe (&self) -> String {
+            format!("shape of area {}", self.area())
+        }
+
+        fn doubled (&self) -> Self where Self: Sized {
+            self.scaled(2.0)
+        }
+    }
+}
+
+
+struct Square {
+    side: f64
+}
+
+#[reflect_impl]
+impl Square {
+    fn new (side: f64) -> Self {
+        Square { side }
+    }
+
+    fn side (&self) -> f64 {
+        self.side
+    }
+}
+
+#[reflect_impl(include = "fn describe(&self) -> String; fn doubled(&self) -> Self")]
+impl shapes::Shape for Square {
+    fn area (&self) -> f64 {
+        self.side * self.side
+    }
+
+    fn scaled (&self, factor: f64) -> Self {
+        Square { side: self.side * factor }
+    }
+}
+
+
+struct Circle {
+    radius: f64
+}
+
+#[reflect_impl(namespace = "shapes")]
+impl Circle {
+    fn new (radius: f64) -> Self {
+        Circle { radius }
+    }
+}
+
+// merges with the namespaced type, although not given the namespace itself
+#[reflect_impl]
+impl shapes::Shape for Circle {
+    fn area (&self) -> f64 {
+        3.0 * self.radius * self.radius
+    }
+
+    fn scaled (&self, factor: f64) -> Self {
+        Circle { radius: self.radius * factor }
+    }
+}
+
+
+struct Rect {
+    width: f64,
+    height: f64
+}
+
+#[reflect_impl(rename = "Rectangle", namespace = "shapes")]
+impl Rect {
+    fn new (width: f64, height: f64) -> Self {
+        Rect { width, height }
+    }
+
+    fn area (&self) -> f64 {
+        self.width * self.height
+    }
+}
+
+
+#[test]
+fn test_inherent_and_trait_impl() {
+    // both impl blocks accumulate into the one type
+    let itype = TypeInfo::find_type("Square").expect("could not find type");
+    assert_eq!(itype.constructors.len(), 1);
+    assert!(itype.methods.contains_key("side"));
+    assert!(itype.methods.contains_key("area"));
+    assert!(itype.methods.contains_key("scaled"));
+
+    let obj = itype.create(&[Box::new(3.0) as Box<dyn Any>]).expect("failed to call ctor");
+    let side = itype.call(&obj, "side", &[]).expect("failed to call inherent method");
+    assert_eq!(*side.downcast_ref::<f64>().unwrap(), 3.0);
+    let area = itype.call(&obj, "area", &[]).expect("failed to call trait method");
+    assert_eq!(*area.downcast_ref::<f64>().unwrap(), 9.0);
+
+    // chained through the parser, mixing trait and inherent methods
+    let side = CTorParser::eval::<f64>("Square(2).scaled(1.5).side()").expect("failed to evaluate");
+    assert_eq!(side, 3.0);
+}
+
+
+#[test]
+fn test_default_methods() {
+    let itype = TypeInfo::find_type("Square").expect("could not find type");
+    let obj = itype.create(&[Box::new(2.0) as Box<dyn Any>]).expect("failed to call ctor");
+    let text = itype.call(&obj, "describe", &[]).expect("failed to call default method");
+    assert_eq!(text.downcast_ref::<String>().unwrap(), "shape of area 4");
+
+    let area = CTorParser::eval::<f64>("Square(2).doubled().area()").expect("failed to evaluate");
+    assert_eq!(area, 16.0);
+}
+
+
+#[test]
+fn test_rename_and_namespace() {
+    let names = registered_types();
+    assert!(names.contains(&"shapes::Circle".to_string()), "{:?}", names);
+    assert!(names.contains(&"shapes::Rectangle".to_string()), "{:?}", names);
+    assert!(!names.contains(&"Circle".to_string()), "{:?}", names);
+    assert!(!names.contains(&"Rect".to_string()), "{:?}", names);
+    assert_eq!(registered_name::<Rect>(), "shapes::Rectangle");
+
+    let itype = TypeInfo::find_type("shapes::Circle").expect("could not find type");
+    assert_eq!(itype.name, "shapes::Circle");
+    assert!(itype.methods.contains_key("area"));
+
+    let area = CTorParser::eval::<f64>("shapes::Circle(2).scaled(0.5).area()").expect("failed to evaluate");
+    assert_eq!(area, 3.0);
+    let area = CTorParser::eval::<f64>("shapes::Rectangle(2, 3).area()").expect("failed to evaluate");
+    assert_eq!(area, 6.0);
+    assert!(CTorParser::create("Rect(2, 3)").is_err());
+}
+
+
+// ctors returning `impl Trait` create an object of the concrete type behind the trait
+struct ShapeFactory;
+
+#[reflect_impl]
+impl ShapeFactory {
+    fn unit () -> impl shapes::Shape {
+        Square { side: 1.0 }
+    }
+
+    fn square (side: f64) -> Result<impl shapes::Shape, String> {
+        if side < 0.0 {
+            return Err(format!("negative side {}", side));
+        }
+        Ok(Square { side })
+    }
+}
+
+
+#[test]
+fn test_impl_trait_ctor() {
+    let itype = TypeInfo::find_type("ShapeFactory").expect("could not find type");
+    for ctor in &itype.constructors {
+        assert_eq!(ctor.return_type(), TypeId::of::<Square>());
+        assert_eq!(ctor.signature().return_type_name, "Square");
+    }
+
+    // methods of the concrete type are resolved on the result
+    let obj = itype.create(&[Box::new(2.0) as Box<dyn Any>]).expect("failed to call ctor");
+    assert!(obj.is::<Square>());
+    let square = find_type_by_id((*obj).type_id()).expect("could not find concrete type");
+    let area = square.call(&obj, "area", &[]).expect("failed to call trait method");
+    assert_eq!(*area.downcast_ref::<f64>().unwrap(), 4.0);
+
+    let area = CTorParser::eval::<f64>("ShapeFactory().doubled().area()").expect("failed to evaluate");
+    assert_eq!(area, 4.0);
+    assert_eq!(CTorParser::validate("ShapeFactory(3).side()"), Ok(()));
+
+    let err = CTorParser::create("ShapeFactory(-1.0)").unwrap_err();
+    assert_eq!(err, ReflectError::CallFailed("negative side -1".to_string()));
+}
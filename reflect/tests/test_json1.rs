@@ -0,0 +1,97 @@
+#![cfg(feature = "reflect_serde")]
+
+use reflect::{from_json, ReflectError};
+use reflect_macros::{reflect_enum, reflect_impl};
+use serde_json::json;
+
+
+#[reflect_enum]
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[allow(clippy::upper_case_acronyms)]
+enum MAType {
+    SMA,
+    EMA
+}
+
+#[derive(Clone)]
+struct Momentum {
+    ma: MAType,
+    windows: Vec<i32>,
+    weights: Vec<f64>
+}
+
+#[reflect_impl]
+impl Momentum {
+    fn new (ma: MAType, windows: &[i32], weights: &[f64]) -> Self {
+        Momentum { ma, windows: windows.to_vec(), weights: weights.to_vec() }
+    }
+}
+
+struct Resample {
+    signal: Momentum,
+    window: i32
+}
+
+#[reflect_impl]
+impl Resample {
+    fn new (signal: Momentum, window: i32) -> Self {
+        Resample { signal, window }
+    }
+}
+
+
+#[test]
+fn test_type_and_args() {
+    let config = json!({ "type": "Momentum", "args": ["SMA", [200, 50, 20], [0.2, 0.3, 0.5]] });
+    let obj = from_json("Momentum", &config).expect("failed to create");
+    let momentum = obj.downcast_ref::<Momentum>().unwrap();
+    assert_eq!(momentum.ma, MAType::SMA);
+    assert_eq!(momentum.windows, vec![200, 50, 20]);
+    assert_eq!(momentum.weights, vec![0.2, 0.3, 0.5]);
+
+    // arguments only
+    let obj = from_json("Momentum", &json!(["EMA", [10], [1]])).expect("failed to create");
+    assert_eq!(obj.downcast_ref::<Momentum>().unwrap().weights, vec![1.0]);
+}
+
+
+#[test]
+fn test_nested() {
+    // nested as type and arguments, and as a ctor expression
+    let config = json!({ "type": "Resample", "args": [
+        { "type": "Momentum", "args": ["EMA", [100], [1.0]] },
+        900
+    ]});
+    let obj = from_json("Resample", &config).expect("failed to create");
+    let resample = obj.downcast_ref::<Resample>().unwrap();
+    assert_eq!(resample.signal.ma, MAType::EMA);
+    assert_eq!(resample.window, 900);
+
+    let config = json!({ "type": "Resample", "args": [{ "ctor": "Momentum(SMA, [200, 50], [0.5, 0.5])" }, 300] });
+    let obj = from_json("Resample", &config).expect("failed to create");
+    assert_eq!(obj.downcast_ref::<Resample>().unwrap().signal.windows, vec![200, 50]);
+
+    let config = json!({ "ctor": "Resample(Momentum(SMA, [200], [1.0]), 60)" });
+    let obj = from_json("Resample", &config).expect("failed to create");
+    assert_eq!(obj.downcast_ref::<Resample>().unwrap().window, 60);
+}
+
+
+#[test]
+fn test_errors() {
+    // not a ctor
+    let err = from_json("Momentum", &json!({ "windows": [200] })).unwrap_err();
+    assert!(matches!(err, ReflectError::ParseError(_)), "{}", err);
+
+    // not of the named type
+    let config = json!({ "type": "Momentum", "args": ["SMA", [200], [1.0]] });
+    let err = from_json("Resample", &config).unwrap_err();
+    assert!(matches!(err, ReflectError::DowncastFailed { .. }), "{}", err);
+
+    // failing nested ctor is identified
+    let config = json!({ "type": "Resample", "args": [{ "type": "Momentum", "args": ["WMA", [200], [1.0]] }, 300] });
+    let err = from_json("Resample", &config).unwrap_err();
+    assert!(err.to_string().ends_with("in 'Momentum(\"WMA\", [200], [1.0])'"), "{}", err);
+
+    assert_eq!(from_json("Unknown", &json!([])).err(), Some(ReflectError::TypeNotFound("Unknown".to_string())));
+}
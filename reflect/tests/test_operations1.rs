@@ -0,0 +1,107 @@
+use reflect::{TypeInfo, ReflectError};
+use reflect_macros::reflect_impl;
+use std::any::Any;
+
+
+#[derive(Clone, Debug, PartialEq)]
+struct Portfolio {
+    weights: Vec<f64>
+}
+
+#[reflect_impl(clone, debug, eq)]
+impl Portfolio {
+    fn new (weights: &[f64]) -> Self {
+        Portfolio { weights: weights.to_vec() }
+    }
+
+    fn weights (&self) -> Vec<f64> {
+        self.weights.clone()
+    }
+
+    fn set_weight (&mut self, index: i32, weight: f64) {
+        self.weights[index as usize] = weight;
+    }
+}
+
+
+struct Ledger {
+    entries: i32
+}
+
+#[reflect_impl]
+impl Ledger {
+    fn new (entries: i32) -> Self {
+        Ledger { entries }
+    }
+
+    fn entries (&self) -> i32 {
+        self.entries
+    }
+}
+
+
+#[test]
+fn test_clone_object() {
+    let itype = TypeInfo::find_type("Portfolio").expect("could not find type");
+    let obj = itype.create(&[Box::new(vec![0.5f64, 0.5]) as Box<dyn Any>]).expect("failed to call ctor");
+
+    // the copy is mutated independently of the original
+    let mut copy = itype.clone_object(&obj).expect("failed to clone");
+    let args: Vec<Box<dyn Any>> = vec![Box::new(0i32), Box::new(0.9f64)];
+    itype.call_mut(&mut copy, "set_weight", &args).expect("failed to call");
+    assert_eq!(*itype.call_as::<Vec<f64>>(&copy, "weights", &[]).unwrap(), vec![0.9, 0.5]);
+    assert_eq!(*itype.call_as::<Vec<f64>>(&obj, "weights", &[]).unwrap(), vec![0.5, 0.5]);
+
+    // the object must be of the type
+    let other = Box::new(42i32) as Box<dyn Any>;
+    assert!(matches!(itype.clone_object(&other), Err(ReflectError::DowncastFailed { .. })));
+}
+
+
+#[test]
+fn test_clone_unsupported() {
+    let itype = TypeInfo::find_type("Ledger").expect("could not find type");
+    let obj = itype.create(&[Box::new(3i32) as Box<dyn Any>]).expect("failed to call ctor");
+    assert_eq!(*itype.call_as::<i32>(&obj, "entries", &[]).unwrap(), 3);
+
+    let err = itype.clone_object(&obj).unwrap_err();
+    assert_eq!(err, ReflectError::Unsupported { type_name: "Ledger".to_string(), operation: "clone".to_string() });
+    assert_eq!(err.to_string(), "type 'Ledger' does not support clone (see #[reflect_impl(clone)])");
+}
+
+
+#[test]
+fn test_debug_string() {
+    let itype = TypeInfo::find_type("Portfolio").expect("could not find type");
+    let obj = itype.create(&[Box::new(vec![0.25f64, 0.75]) as Box<dyn Any>]).expect("failed to call ctor");
+    assert_eq!(itype.debug_string(&obj).expect("failed to format"), "Portfolio { weights: [0.25, 0.75] }");
+
+    let other = Box::new(42i32) as Box<dyn Any>;
+    assert!(matches!(itype.debug_string(&other), Err(ReflectError::DowncastFailed { .. })));
+
+    let itype = TypeInfo::find_type("Ledger").expect("could not find type");
+    let obj = itype.create(&[Box::new(3i32) as Box<dyn Any>]).expect("failed to call ctor");
+    let err = itype.debug_string(&obj).unwrap_err();
+    assert_eq!(err, ReflectError::Unsupported { type_name: "Ledger".to_string(), operation: "debug".to_string() });
+}
+
+
+#[test]
+fn test_objects_equal() {
+    let itype = TypeInfo::find_type("Portfolio").expect("could not find type");
+    let a = reflect::CTorParser::create("Portfolio([0.5, 0.5])").expect("failed to create");
+    let b = reflect::CTorParser::create("Portfolio([0.5, 0.5])").expect("failed to create");
+    let c = reflect::CTorParser::create("Portfolio([0.4, 0.6])").expect("failed to create");
+    assert!(itype.objects_equal(&a, &b).unwrap());
+    assert!(!itype.objects_equal(&a, &c).unwrap());
+
+    // objects of another type are an error, not unequal
+    let ledger = reflect::CTorParser::create("Ledger(3)").expect("failed to create");
+    let err = itype.objects_equal(&a, &ledger).unwrap_err();
+    assert_eq!(err, ReflectError::DowncastFailed { expected: "Portfolio".to_string(), actual: "Ledger".to_string() });
+    assert!(itype.objects_equal(&ledger, &a).is_err());
+
+    let ledgers = TypeInfo::find_type("Ledger").expect("could not find type");
+    let err = ledgers.objects_equal(&ledger, &ledger).unwrap_err();
+    assert_eq!(err, ReflectError::Unsupported { type_name: "Ledger".to_string(), operation: "eq".to_string() });
+}
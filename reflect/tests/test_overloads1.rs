@@ -0,0 +1,383 @@
+use reflect::{CTorParser, Conversions, Score, TypeInfo, ReflectError, duplicate_constructors};
+use reflect_macros::reflect_impl;
+use std::any::{Any, TypeId};
+
+
+struct Pair {
+    ctor: &'static str
+}
+
+#[reflect_impl]
+impl Pair {
+    fn from_float_int (_a: f64, _b: i32) -> Self {
+        Pair { ctor: "(f64, i32)" }
+    }
+
+    fn from_int_float (_a: i32, _b: f64) -> Self {
+        Pair { ctor: "(i32, f64)" }
+    }
+}
+
+
+struct Widen {
+    ctor: &'static str
+}
+
+#[reflect_impl]
+impl Widen {
+    fn from_unsigned (_a: u32, _b: u32) -> Self {
+        Widen { ctor: "(u32, u32)" }
+    }
+
+    fn from_mixed (_a: i32, _b: u64) -> Self {
+        Widen { ctor: "(i32, u64)" }
+    }
+}
+
+
+fn ints (a: i32, b: i32) -> Vec<Box<dyn Any>> {
+    vec![Box::new(a) as Box<dyn Any>, Box::new(b) as Box<dyn Any>]
+}
+
+
+// ctors with the same signature, such that one shadows the other
+struct Twin {
+    n: i32
+}
+
+#[reflect_impl]
+impl Twin {
+    fn new (n: i32) -> Self {
+        Twin { n }
+    }
+
+    fn doubled (n: i32) -> Self {
+        Twin { n: 2 * n }
+    }
+}
+
+
+#[test]
+fn test_tie_broken_by_signature() {
+    // both ctors score equally, so the lexically first signature is chosen
+    let itype = TypeInfo::find_type("Pair").expect("could not find type");
+    for _ in 0..10 {
+        let obj = itype.create(&ints(1, 2)).expect("failed to call ctor");
+        assert_eq!(obj.downcast_ref::<Pair>().unwrap().ctor, "(f64, i32)");
+    }
+}
+
+
+#[test]
+fn test_tie_broken_by_equivalent_arguments() {
+    // both ctors score equally, but (i32, u64) passes one argument without conversion
+    let itype = TypeInfo::find_type("Widen").expect("could not find type");
+    let obj = itype.create(&ints(1, 2)).expect("failed to call ctor");
+    assert_eq!(obj.downcast_ref::<Widen>().unwrap().ctor, "(i32, u64)");
+}
+
+
+
+#[test]
+fn test_resolve() {
+    let itype = TypeInfo::find_type("Widen").expect("could not find type");
+    let (ctor, argv) = Conversions::resolve(&itype.constructors, ints(1, 2)).expect("failed to resolve").expect("no match");
+    assert_eq!(ctor.arg_types(), &[TypeId::of::<i32>(), TypeId::of::<u64>()]);
+    assert_eq!(*argv[0].downcast_ref::<i32>().unwrap(), 1);
+    assert_eq!(*argv[1].downcast_ref::<u64>().unwrap(), 2);
+
+    let refs: Vec<&dyn Any> = argv.iter().map(|arg| arg.as_ref()).collect();
+    let obj = ctor.create(&refs).expect("failed to call ctor");
+    assert_eq!(obj.downcast_ref::<Widen>().unwrap().ctor, "(i32, u64)");
+
+    let args = vec![Box::new("one".to_string()) as Box<dyn Any>];
+    assert!(Conversions::resolve(&itype.constructors, args).expect("failed to resolve").is_none());
+
+    // parameters without an argument take their default value
+    let gauge = TypeInfo::find_type("Gauge").expect("could not find type");
+    let args = vec![Box::new(2i32) as Box<dyn Any>];
+    let (_, argv) = Conversions::resolve(&gauge.methods["offset"], args).expect("failed to resolve").expect("no match");
+    assert_eq!(argv.len(), 2);
+    assert_eq!(*argv[1].downcast_ref::<i32>().unwrap(), 1);
+
+    // arguments failing to convert are reported
+    let twin = TypeInfo::find_type("Twin").expect("could not find type");
+    let args = vec![Box::new(i64::MAX) as Box<dyn Any>];
+    let resolved = Conversions::resolve(&twin.constructors, args);
+    assert!(matches!(resolved, Err(ReflectError::ConversionFailed { .. })));
+}
+
+
+fn score (target: &[TypeId], args: &[Box<dyn Any>]) -> Score {
+    Conversions::score_detail(target, args)
+}
+
+
+#[test]
+fn test_score_detail() {
+    let target = [TypeId::of::<i32>(), TypeId::of::<f64>(), TypeId::of::<String>()];
+    let fit = score(&target, &[Box::new(1i32), Box::new(2i32), Box::new("a".to_string())]);
+    let near_miss = score(&target, &[Box::new(1i32), Box::new(2i32), Box::new(vec![1.0])]);
+    let arity_miss = score(&target, &[Box::new(1i32), Box::new(2.0)]);
+    let mismatch = score(&target, &[Box::new(vec![1.0]), Box::new(vec![2.0]), Box::new(vec![3.0])]);
+    let unrelated = score(&target, &[Box::new(vec![1.0])]);
+
+    assert!(fit.is_fit());
+    assert_eq!((fit.matched, fit.equivalent, fit.converted()), (3, 2, 1));
+    assert!(!near_miss.is_fit());
+    assert_eq!((near_miss.matched, near_miss.unmatched()), (2, 1));
+    assert_eq!((arity_miss.matched, arity_miss.unmatched()), (2, 0));
+    assert_eq!((mismatch.matched, mismatch.unmatched()), (0, 3));
+
+    // near misses rank above total mismatches, though scoring alike as a single value
+    assert!(fit > near_miss && fit > arity_miss);
+    assert!(near_miss > arity_miss);
+    assert!(arity_miss > mismatch && near_miss > mismatch);
+    assert!(mismatch > unrelated);
+    assert_eq!(near_miss.value(), mismatch.value());
+    assert_eq!(arity_miss.value(), unrelated.value());
+    assert_eq!(fit.value(), Conversions::score(&target, &[Box::new(1i32), Box::new(2i32), Box::new("a".to_string())]));
+}
+
+
+#[test]
+fn test_find_nearest() {
+    // no ctor of Widen takes a list, but (i32, u64) matches the first argument exactly
+    let itype = TypeInfo::find_type("Widen").expect("could not find type");
+    let args = vec![Box::new(1i32) as Box<dyn Any>, Box::new(vec![2.0]) as Box<dyn Any>];
+    assert!(Conversions::find_best_match(&itype.constructors, &args).is_none());
+
+    let (nearest, score) = Conversions::find_nearest(&itype.constructors, &args).expect("no candidates");
+    assert_eq!(nearest.signature().to_string(), "(i32, u64)");
+    assert_eq!((score.matched, score.unmatched(), score.equivalent), (1, 1, 1));
+}
+
+struct Count {
+    n: i64
+}
+
+#[reflect_impl]
+impl Count {
+    fn from_signed (n: i32) -> Self {
+        Count { n: i64::from(n) }
+    }
+
+    fn from_unsigned (n: u32) -> Self {
+        Count { n: i64::from(n) }
+    }
+
+    fn from_pair (a: i32, b: i32) -> Self {
+        Count { n: i64::from(a + b) }
+    }
+}
+
+
+#[test]
+fn test_strict_ambiguity() {
+    let itype = TypeInfo::find_type("Count").expect("could not find type");
+
+    // an i64 converts equally well to i32 and u32
+    let args = vec![Box::new(5i64) as Box<dyn Any>];
+    let obj = itype.create(&args).expect("failed to call ctor");
+    assert_eq!(obj.downcast_ref::<Count>().unwrap().n, 5);
+
+    let err = itype.create_strict(&args).unwrap_err();
+    match &err {
+        ReflectError::AmbiguousMatch { type_name, candidates, .. } => {
+            assert_eq!(type_name, "Count");
+            let mut candidates = candidates.clone();
+            candidates.sort();
+            assert_eq!(candidates, vec!["(i32)", "(u32)"]);
+        }
+        _ => panic!("expected ambiguity, got: {}", err)
+    }
+    assert!(err.to_string().starts_with("ambiguous ctor of 'Count', candidates "), "{}", err);
+
+    // an i32 is an unambiguous match
+    let obj = itype.create_strict(&[Box::new(5i32) as Box<dyn Any>]).expect("failed to call ctor");
+    assert_eq!(obj.downcast_ref::<Count>().unwrap().n, 5);
+    let obj = itype.create_strict(&ints(2, 3)).expect("failed to call ctor");
+    assert_eq!(obj.downcast_ref::<Count>().unwrap().n, 5);
+}
+
+
+#[test]
+fn test_duplicate_constructors() {
+    assert_eq!(duplicate_constructors(), vec![("Twin".to_string(), "(i32)".to_string())]);
+
+    // one of the two is called
+    let itype = TypeInfo::find_type("Twin").expect("could not find type");
+    let obj = itype.create(&[Box::new(3i32) as Box<dyn Any>]).expect("failed to call ctor");
+    assert!([3, 6].contains(&obj.downcast_ref::<Twin>().unwrap().n));
+}
+
+
+// methods of the same name and parameters, differing in return type
+struct Gauge {
+    level: i32
+}
+
+#[reflect_impl]
+impl Gauge {
+    fn new (level: i32) -> Self {
+        Gauge { level }
+    }
+
+    #[reflect(rename = "read")]
+    fn read_int (&self, scale: i32) -> i32 {
+        self.level * scale
+    }
+
+    #[reflect(rename = "read")]
+    fn read_float (&self, scale: i32) -> f64 {
+        f64::from(self.level * scale) / 2.0
+    }
+
+    #[reflect(rename = "ratio")]
+    fn ratio (&self, divisor: i32) -> f64 {
+        f64::from(self.level) / f64::from(divisor)
+    }
+
+    #[reflect(rename = "ratio")]
+    fn checked_ratio (&self, divisor: i32) -> Option<f64> {
+        (divisor != 0).then(|| f64::from(self.level) / f64::from(divisor))
+    }
+
+    fn peek (&self) -> Option<Gauge> {
+        Some(Gauge { level: self.level })
+    }
+
+    #[reflect(rename = "offset")]
+    fn offset_int (&self, by: i32, #[reflect(default = "1")] scale: i32) -> i32 {
+        self.level + by * scale
+    }
+
+    #[reflect(rename = "offset")]
+    fn offset_float (&self, by: i32, #[reflect(default = "1")] scale: i32) -> f64 {
+        f64::from(self.level + by * scale) / 2.0
+    }
+}
+
+
+#[test]
+fn test_tie_broken_by_return_type() {
+    let itype = TypeInfo::find_type("Gauge").expect("could not find type");
+    assert_eq!(itype.methods["read"].len(), 2);
+    let obj = itype.create(&[Box::new(3i32) as Box<dyn Any>]).expect("failed to call ctor");
+
+    // the overload returning the requested type is chosen, with arguments converted as needed
+    for _ in 0..2 {
+        let args = vec![Box::new(2i32) as Box<dyn Any>];
+        assert_eq!(*itype.call_as::<i32>(&obj, "read", &args).expect("failed to call"), 6);
+        assert_eq!(*itype.call_as::<f64>(&obj, "read", &args).expect("failed to call"), 3.0);
+
+        let args = vec![Box::new(2i64) as Box<dyn Any>];
+        assert_eq!(*itype.call_as::<f64>(&obj, "read", &args).expect("failed to call"), 3.0);
+        assert_eq!(*itype.call_as::<i32>(&obj, "read", &args).expect("failed to call"), 6);
+    }
+
+    // otherwise either may be chosen
+    let value = itype.call(&obj, "read", &[Box::new(2i32) as Box<dyn Any>]).expect("failed to call");
+    assert!(value.is::<i32>() || value.is::<f64>());
+
+    // including where a parameter takes its default value
+    for _ in 0..2 {
+        let args = vec![Box::new(1i32) as Box<dyn Any>];
+        assert_eq!(*itype.call_as::<i32>(&obj, "offset", &args).expect("failed to call"), 4);
+        assert_eq!(*itype.call_as::<f64>(&obj, "offset", &args).expect("failed to call"), 2.0);
+    }
+
+    let err = itype.call_as::<i32>(&obj, "read", &[]).unwrap_err();
+    match err {
+        ReflectError::NoMatchingMethod { candidates, .. } => assert_eq!(candidates, vec!["(i32)", "(i32)"]),
+        _ => panic!("expected no matching method, got: {}", err)
+    }
+}
+
+
+#[test]
+fn test_tie_broken_by_option_return_type() {
+    let itype = TypeInfo::find_type("Gauge").expect("could not find type");
+    let obj = itype.create(&[Box::new(3i32) as Box<dyn Any>]).expect("failed to call ctor");
+
+    // an `Option<f64>` is boxed as is, though its return type is reported as `f64`
+    let boxed: Vec<(TypeId, TypeId)> = itype.methods["ratio"].iter().map(|m| (m.return_type(), m.boxed_type())).collect();
+    assert_eq!(boxed.len(), 2);
+    assert!(boxed.contains(&(TypeId::of::<f64>(), TypeId::of::<f64>())));
+    assert!(boxed.contains(&(TypeId::of::<f64>(), TypeId::of::<Option<f64>>())));
+
+    // so the overload boxing the requested type is chosen
+    let args = vec![Box::new(2i32) as Box<dyn Any>];
+    assert_eq!(*itype.call_as::<f64>(&obj, "ratio", &args).expect("failed to call"), 1.5);
+    assert_eq!(*itype.call_as::<Option<f64>>(&obj, "ratio", &args).expect("failed to call"), Some(1.5));
+    let args = vec![Box::new(0i32) as Box<dyn Any>];
+    assert_eq!(*itype.call_as::<Option<f64>>(&obj, "ratio", &args).expect("failed to call"), None);
+
+    // and checking a call reports the type boxed, an `Option<Gauge>` having no methods to call
+    assert!(CTorParser::validate("Gauge(3).ratio(2)").is_ok());
+    assert!(CTorParser::validate("Gauge(3).peek().ratio(2)").is_err());
+    assert!(CTorParser::call("Gauge(3).peek().ratio(2)").is_err());
+}
+
+
+// methods and static functions of the same name, differing in arity
+struct Span {
+    start: i32
+}
+
+#[reflect_impl]
+impl Span {
+    fn new (start: i32) -> Self {
+        Span { start }
+    }
+
+    #[reflect(rename = "shift")]
+    fn shift_by (&self, n: i32) -> i32 {
+        self.start + n
+    }
+
+    #[reflect(rename = "shift")]
+    fn shift_scaled (&self, n: i32, scale: i32) -> i32 {
+        self.start + n * scale
+    }
+
+    #[reflect(rename = "make")]
+    fn make_at (start: i32) -> Span {
+        Span { start }
+    }
+
+    #[reflect(rename = "make")]
+    fn make_between (start: i32, end: i32) -> Span {
+        Span { start: (start + end) / 2 }
+    }
+}
+
+
+#[test]
+fn test_overloads_by_arity() {
+    let itype = TypeInfo::find_type("Span").expect("could not find type");
+    assert_eq!(itype.method_names(), vec!["shift"]);
+    assert_eq!(itype.static_names(), vec!["make"]);
+    let obj = itype.create(&[Box::new(10i32) as Box<dyn Any>]).expect("failed to call ctor");
+
+    assert_eq!(*itype.call_as::<i32>(&obj, "shift", &[Box::new(1i32) as Box<dyn Any>]).expect("failed to call"), 11);
+    assert_eq!(*itype.call_as::<i32>(&obj, "shift", &ints(1, 3)).expect("failed to call"), 13);
+
+    // arguments are converted as for ctors
+    let args = vec![Box::new(2i64) as Box<dyn Any>, Box::new(3u8) as Box<dyn Any>];
+    assert_eq!(*itype.call_as::<i32>(&obj, "shift", &args).expect("failed to call"), 16);
+
+    let span = itype.callstatic("make", &[Box::new(4i32) as Box<dyn Any>]).expect("failed to call");
+    assert_eq!(span.downcast_ref::<Span>().unwrap().start, 4);
+    let span = itype.callstatic("make", &ints(4, 8)).expect("failed to call");
+    assert_eq!(span.downcast_ref::<Span>().unwrap().start, 6);
+
+    let err = itype.call(&obj, "shift", &[]).unwrap_err();
+    match err {
+        ReflectError::NoMatchingMethod { mut candidates, .. } => {
+            candidates.sort();
+            assert_eq!(candidates, vec!["(i32)", "(i32, i32)"]);
+        }
+        _ => panic!("expected no matching method, got: {}", err)
+    }
+}
@@ -0,0 +1,98 @@
+use reflect::TypeInfo;
+use reflect_macros::reflect_impl;
+use std::any::Any;
+use std::time::Instant;
+
+
+// about a megabyte of f64
+const LEN: usize = 131072;
+
+
+struct Series {
+    values: Vec<f64>,
+    scale: f64
+}
+
+#[reflect_impl]
+impl Series {
+    fn new (values: Vec<f64>, scale: f64) -> Self {
+        Series { values, scale }
+    }
+
+    fn exchange (&self, values: Vec<f64>) -> Vec<f64> {
+        values
+    }
+
+    fn total (&self) -> f64 {
+        self.values.iter().sum::<f64>() * self.scale
+    }
+}
+
+
+#[test]
+fn test_create_owned() {
+    let itype = TypeInfo::find_type("Series").expect("could not find type");
+    let values = vec![1.0; LEN];
+    let address = values.as_ptr();
+
+    // the vector is moved into the ctor, the scale converted from i32
+    let args = vec![Box::new(values) as Box<dyn Any>, Box::new(2i32) as Box<dyn Any>];
+    let obj = itype.create_owned(args).expect("failed to create");
+    let series = obj.downcast_ref::<Series>().unwrap();
+    assert_eq!(series.values.as_ptr(), address);
+    assert_eq!(series.scale, 2.0);
+
+    // whereas create clones it
+    let args = vec![Box::new(vec![1.0; LEN]) as Box<dyn Any>, Box::new(2.0) as Box<dyn Any>];
+    let obj = itype.create(&args).expect("failed to create");
+    assert_ne!(obj.downcast_ref::<Series>().unwrap().values.as_ptr(), args[0].downcast_ref::<Vec<f64>>().unwrap().as_ptr());
+
+    // a vector of another element type is converted as by create
+    let args = vec![Box::new(vec![1, 2]) as Box<dyn Any>, Box::new(1.0) as Box<dyn Any>];
+    let obj = itype.create_owned(args).expect("failed to create");
+    assert_eq!(obj.downcast_ref::<Series>().unwrap().values, vec![1.0, 2.0]);
+}
+
+
+#[test]
+fn test_call_owned() {
+    let itype = TypeInfo::find_type("Series").expect("could not find type");
+    let obj = itype.create_owned(vec![Box::new(vec![1.0, 2.0]) as Box<dyn Any>, Box::new(0.5) as Box<dyn Any>]).expect("failed to create");
+
+    let values = vec![3.0; LEN];
+    let address = values.as_ptr();
+    let result = itype.call_owned(&obj, "exchange", vec![Box::new(values) as Box<dyn Any>]).expect("failed to call");
+    assert_eq!(result.downcast_ref::<Vec<f64>>().unwrap().as_ptr(), address);
+
+    // methods without container parameters are called as by call
+    let result = itype.call_owned(&obj, "total", Vec::new()).expect("failed to call");
+    assert_eq!(*result.downcast_ref::<f64>().unwrap(), 1.5);
+    assert!(itype.call_owned(&obj, "missing", Vec::new()).is_err());
+}
+
+
+// cargo test --release --test test_owned1 -- --ignored --nocapture
+#[test]
+#[ignore]
+fn bench_create_owned() {
+    let itype = TypeInfo::find_type("Series").expect("could not find type");
+    let iterations = 1000;
+
+    let args = vec![Box::new(vec![1.0; LEN]) as Box<dyn Any>, Box::new(1.0) as Box<dyn Any>];
+    let start = Instant::now();
+    for _ in 0..iterations {
+        itype.create(&args).expect("failed to create");
+    }
+    let cloned = start.elapsed();
+
+    let mut elapsed = std::time::Duration::ZERO;
+    for _ in 0..iterations {
+        let args = vec![Box::new(vec![1.0; LEN]) as Box<dyn Any>, Box::new(1.0) as Box<dyn Any>];
+        let start = Instant::now();
+        itype.create_owned(args).expect("failed to create");
+        elapsed += start.elapsed();
+    }
+
+    println!("create: {:?} / call, create_owned: {:?} / call", cloned / iterations, elapsed / iterations);
+    assert!(elapsed < cloned);
+}
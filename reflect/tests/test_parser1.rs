@@ -0,0 +1,456 @@
+use reflect::{CTorParser, CtorExpr, ReflectError};
+use reflect_macros::{reflect_enum, reflect_impl};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+
+#[reflect_enum]
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[allow(clippy::upper_case_acronyms)]
+enum MAType {
+    SMA,
+    EMA
+}
+
+#[derive(Clone)]
+struct Momentum {
+    ma: MAType,
+    windows: Vec<i32>,
+    decay: f64
+}
+
+#[reflect_impl]
+impl Momentum {
+    fn new (ma: MAType, windows: &[i32], decay: f64) -> Self {
+        Momentum { ma, windows: windows.to_vec(), decay }
+    }
+
+    fn scaled (&self, k: f64) -> Momentum {
+        Momentum { ma: self.ma, windows: self.windows.clone(), decay: self.decay * k }
+    }
+
+    fn value (&self, x: i32) -> f64 {
+        self.decay * f64::from(x)
+    }
+}
+
+struct Resample {
+    signal: Momentum,
+    window: i32
+}
+
+#[reflect_impl]
+impl Resample {
+    fn new (signal: Momentum, window: i32) -> Self {
+        Resample { signal, window }
+    }
+}
+
+
+#[test]
+fn test_create_ctor() {
+    let rawobj = CTorParser::create("Momentum(EMA, [200,50,20], 0.9)").expect("failed to create");
+    let obj = rawobj.downcast_ref::<Momentum>().expect("failed to downcast to type");
+
+    assert_eq!(obj.ma, MAType::EMA);
+    assert_eq!(obj.windows, vec![200, 50, 20]);
+    assert_eq!(obj.decay, 0.9);
+}
+
+
+#[test]
+fn test_create_nested_ctor() {
+    let rawobj = CTorParser::create("Resample(Momentum(SMA,[200,560,10],0.9), 300)").expect("failed to create");
+    let obj = rawobj.downcast_ref::<Resample>().expect("failed to downcast to type");
+
+    assert_eq!(obj.window, 300);
+    assert_eq!(obj.signal.ma, MAType::SMA);
+    assert_eq!(obj.signal.windows, vec![200, 560, 10]);
+}
+
+
+#[test]
+fn test_create_errors() {
+    let err = CTorParser::create("Unknown(1, 2)").unwrap_err();
+    assert_eq!(err, ReflectError::TypeNotFound("Unknown".to_string()));
+    assert!(err.to_string().contains("Unknown"));
+
+    let err = CTorParser::create("Momentum(SMA,").unwrap_err();
+    assert!(matches!(err, ReflectError::ParseError(_)));
+    assert!(err.to_string().contains("failed to parse"));
+
+    assert!(CTorParser::create("Momentum(XYZ, [200], 0.9)").is_err());
+}
+
+
+#[test]
+fn test_nested_errors() {
+    // the error names the failing inner ctor
+    let err = CTorParser::create("Resample(Momentum(XYZ,[200],0.9), 300)").unwrap_err();
+    match &err {
+        ReflectError::InExpression { expression, error } => {
+            assert_eq!(expression, "Momentum(XYZ, [200], 0.9)");
+            assert_eq!(**error, ReflectError::ConversionFailed { from: "String".to_string(), to: "MAType".to_string() });
+        }
+        _ => panic!("unexpected error: {:?}", err)
+    }
+    assert!(err.to_string().ends_with(" in 'Momentum(XYZ, [200], 0.9)'"), "{}", err);
+
+    // the innermost failing sub-expression is named
+    let err = CTorParser::create("Resample(Momentum(SMA, [200], Unknown(1)), 300)").unwrap_err();
+    assert!(matches!(&err, ReflectError::InExpression { expression, .. } if expression == "Unknown(1)"), "{:?}", err);
+
+    // as is a failing receiver of a method call
+    let err = CTorParser::call("Momentum(XYZ, [200], 0.5).value(2)").unwrap_err();
+    assert!(matches!(&err, ReflectError::InExpression { expression, .. } if expression == "Momentum(XYZ, [200], 0.5)"), "{:?}", err);
+}
+
+
+#[test]
+fn test_call_method() {
+    let result = CTorParser::call("Momentum(SMA, [200], 0.5).value(42)").expect("failed to call");
+    assert_eq!(*result.downcast_ref::<f64>().unwrap(), 21.0);
+
+    // chained calls are applied to the result of the prior call
+    let result = CTorParser::call("Momentum(SMA, [200], 0.5).scaled(3).value(2)").expect("failed to call");
+    assert_eq!(*result.downcast_ref::<f64>().unwrap(), 3.0);
+
+    // no calls yields the object
+    let result = CTorParser::call("Momentum(SMA, [200], 0.5)").expect("failed to call");
+    assert!(result.downcast_ref::<Momentum>().is_some());
+
+    let err = CTorParser::call("Momentum(SMA, [200], 0.5).unknown(2)").unwrap_err();
+    assert_eq!(err, ReflectError::MethodNotFound { type_name: "Momentum".to_string(), method: "unknown".to_string() });
+    assert!(CTorParser::call("Momentum(SMA, [200], 0.5).value(2).value(2)").is_err());
+}
+
+
+struct Matrix {
+    rows: Vec<Vec<f64>>
+}
+
+#[reflect_impl]
+impl Matrix {
+    fn new (matrix: &[Vec<f64>]) -> Self {
+        Matrix { rows: matrix.to_vec() }
+    }
+}
+
+
+#[test]
+fn test_nested_list() {
+    let rawobj = CTorParser::create("Matrix([[1.0, 2.5], [3.0, 4.0]])").expect("failed to create");
+    let obj = rawobj.downcast_ref::<Matrix>().expect("failed to downcast to type");
+    assert_eq!(obj.rows, vec![vec![1.0, 2.5], vec![3.0, 4.0]]);
+
+    // integer rows are promoted to f64
+    let rawobj = CTorParser::create("Matrix([[1, 2], [3, 4]])").expect("failed to create");
+    let obj = rawobj.downcast_ref::<Matrix>().expect("failed to downcast to type");
+    assert_eq!(obj.rows, vec![vec![1.0, 2.0], vec![3.0, 4.0]]);
+
+    let rawobj = CTorParser::create("Matrix([[1, 2], [3.5, 4]])").expect("failed to create");
+    let obj = rawobj.downcast_ref::<Matrix>().expect("failed to downcast to type");
+    assert_eq!(obj.rows, vec![vec![1.0, 2.0], vec![3.5, 4.0]]);
+
+    assert!(CTorParser::create("Matrix([[1, 2], 3])").is_err());
+}
+
+
+struct Volumes {
+    counts: Vec<i64>,
+    weights: Vec<f64>,
+    blocks: Vec<Vec<i64>>
+}
+
+#[reflect_impl]
+impl Volumes {
+    fn new (counts: &[i64], weights: &[f64], blocks: &[Vec<i64>]) -> Self {
+        Volumes { counts: counts.to_vec(), weights: weights.to_vec(), blocks: blocks.to_vec() }
+    }
+}
+
+
+#[test]
+fn test_large_integer_list() {
+    let rawobj = CTorParser::create("Volumes([5000000000, 1], [5000000000, 2], [[1], [5000000000]])").expect("failed to create");
+    let obj = rawobj.downcast_ref::<Volumes>().expect("failed to downcast to type");
+    assert_eq!(obj.counts, vec![5_000_000_000, 1]);
+    assert_eq!(obj.weights, vec![5.0e9, 2.0]);
+    assert_eq!(obj.blocks, vec![vec![1], vec![5_000_000_000]]);
+
+    // integers within i32 are widened
+    let rawobj = CTorParser::create("Volumes([1, 2], [3], [[4, 5]])").expect("failed to create");
+    let obj = rawobj.downcast_ref::<Volumes>().expect("failed to downcast to type");
+    assert_eq!(obj.counts, vec![1, 2]);
+    assert_eq!(obj.blocks, vec![vec![4, 5]]);
+
+    // rows of large integers are promoted to f64 as are other integer rows
+    let rawobj = CTorParser::create("Matrix([[5000000000, 1], [2.5]])").expect("failed to create");
+    assert_eq!(rawobj.downcast_ref::<Matrix>().unwrap().rows, vec![vec![5.0e9, 1.0], vec![2.5]]);
+    let rawobj = CTorParser::create("Matrix([[5000000000], [1]])").expect("failed to create");
+    assert_eq!(rawobj.downcast_ref::<Matrix>().unwrap().rows, vec![vec![5.0e9], vec![1.0]]);
+
+    // but do not narrow to i32
+    assert!(CTorParser::create("Momentum(SMA, [5000000000], 0.5)").is_err());
+}
+
+
+struct Ensemble {
+    types: Vec<MAType>,
+    labels: Vec<String>
+}
+
+#[reflect_impl]
+impl Ensemble {
+    fn new (types: &[MAType], labels: Vec<String>) -> Self {
+        Ensemble { types: types.to_vec(), labels }
+    }
+}
+
+
+#[test]
+fn test_identifier_and_string_lists() {
+    let rawobj = CTorParser::create("Ensemble([SMA, EMA, SMA], [\"fast\", \"slow\"])").expect("failed to create");
+    let obj = rawobj.downcast_ref::<Ensemble>().expect("failed to downcast to type");
+    assert_eq!(obj.types, vec![MAType::SMA, MAType::EMA, MAType::SMA]);
+    assert_eq!(obj.labels, vec!["fast".to_string(), "slow".to_string()]);
+
+    // unknown variant
+    assert!(CTorParser::create("Ensemble([SMA, XYZ], [\"fast\"])").is_err());
+
+    // mixed lists are rejected
+    let err = CTorParser::create("Ensemble([SMA, 1], [\"fast\"])").unwrap_err();
+    assert!(err.to_string().contains("cannot mix"), "{}", err);
+}
+
+
+struct Clamp {
+    range: (f64, f64)
+}
+
+#[reflect_impl]
+impl Clamp {
+    fn new (range: (f64, f64)) -> Self {
+        Clamp { range }
+    }
+
+    fn apply (&self, x: f64) -> f64 {
+        x.clamp(self.range.0, self.range.1)
+    }
+
+    fn shifted (&self, offsets: (i32, String, bool)) -> String {
+        format!("{} {} {}", offsets.0, offsets.1, offsets.2)
+    }
+}
+
+
+#[test]
+fn test_tuples() {
+    let obj = CTorParser::create("Clamp((0.0, 1.0))").expect("failed to create");
+    assert_eq!(obj.downcast_ref::<Clamp>().unwrap().range, (0.0, 1.0));
+
+    // elements are converted
+    let result = CTorParser::call("Clamp((-1, 1)).apply(1.5)").expect("failed to call");
+    assert_eq!(*result.downcast_ref::<f64>().unwrap(), 1.0);
+    let result = CTorParser::call("Clamp((0, 1)).shifted((3, \"x\", true))").expect("failed to call");
+    assert_eq!(result.downcast_ref::<String>().unwrap(), "3 x true");
+
+    // tuple of the wrong size or element types
+    assert!(CTorParser::create("Clamp((0.0, 1.0, 2.0))").is_err());
+    assert!(CTorParser::create("Clamp((true, 1.0))").is_err());
+}
+
+
+struct Blend {
+    weights: HashMap<String, f64>
+}
+
+#[reflect_impl]
+impl Blend {
+    fn new (weights: HashMap<String, f64>) -> Self {
+        Blend { weights }
+    }
+
+    fn weight (&self, name: String) -> f64 {
+        self.weights.get(&name).copied().unwrap_or(0.0)
+    }
+}
+
+
+#[test]
+fn test_maps() {
+    let obj = CTorParser::create("Blend({ \"a\": 0.25, \"b\": 0.75 })").expect("failed to create");
+    let weights = &obj.downcast_ref::<Blend>().unwrap().weights;
+    assert_eq!(weights.len(), 2);
+    assert_eq!(weights["a"], 0.25);
+    assert_eq!(weights["b"], 0.75);
+
+    // integer values and identifier keys
+    let result = CTorParser::call("Blend({a: 1, b: 2}).weight(\"b\")").expect("failed to call");
+    assert_eq!(*result.downcast_ref::<f64>().unwrap(), 2.0);
+
+    assert!(CTorParser::create("Blend({\"a\": [1, 2]})").is_err());
+    assert!(CTorParser::create("Blend({\"a\": SMA})").is_err());
+}
+
+#[test]
+fn test_keyword_arguments() {
+    // arguments by name, in any order, following any positional arguments
+    let rawobj = CTorParser::create("Momentum(decay=0.5, ma=EMA, windows=[200, 50])").expect("failed to create");
+    let obj = rawobj.downcast_ref::<Momentum>().expect("failed to downcast to type");
+    assert_eq!(obj.ma, MAType::EMA);
+    assert_eq!(obj.windows, vec![200, 50]);
+    assert_eq!(obj.decay, 0.5);
+
+    let rawobj = CTorParser::create("Resample(window=60, signal=Momentum(SMA, decay=0.9, windows=[20]))").expect("failed to create");
+    let obj = rawobj.downcast_ref::<Resample>().expect("failed to downcast to type");
+    assert_eq!(obj.window, 60);
+    assert_eq!(obj.signal.windows, vec![20]);
+
+    // through the reflection interface
+    let itype = reflect::TypeInfo::find_type("Momentum").expect("could not find type");
+    let kwargs: Vec<(String, Box<dyn std::any::Any>)> = vec![
+        ("windows".to_string(), Box::new(vec![10i64, 5])),
+        ("decay".to_string(), Box::new(0.25f64)),
+        ("ma".to_string(), Box::new(MAType::SMA))
+    ];
+    let rawobj = itype.create_kwargs(&kwargs).expect("failed to create");
+    assert_eq!(rawobj.downcast_ref::<Momentum>().unwrap().windows, vec![10, 5]);
+    assert_eq!(itype.constructors[0].signature().arg_names, vec!["ma", "windows", "decay"]);
+
+    // unknown, repeated, or missing names match no ctor
+    for expr in ["Momentum(SMA, [200], rate=0.5)", "Momentum(SMA, ma=EMA, decay=0.5)", "Momentum(SMA, decay=0.5)"] {
+        let err = CTorParser::create(expr).unwrap_err();
+        assert!(matches!(err, ReflectError::NoMatchingConstructor { .. }), "{}", err);
+    }
+
+    let err = CTorParser::create("Momentum(ma=SMA, [200], 0.5)").unwrap_err();
+    assert!(err.to_string().contains("positional argument follows argument by name"), "{}", err);
+}
+
+
+static CONNECTIONS: AtomicUsize = AtomicUsize::new(0);
+
+struct Connection {
+    port: i32
+}
+
+#[reflect_impl]
+impl Connection {
+    fn new (host: &str, port: i32) -> Self {
+        assert!(!host.is_empty());
+        CONNECTIONS.fetch_add(1, Ordering::SeqCst);
+        Connection { port }
+    }
+
+    fn port (&self) -> i32 {
+        self.port
+    }
+}
+
+
+#[test]
+fn test_validate() {
+    assert_eq!(CTorParser::validate("Resample(Momentum(SMA, [200, 50], 0.9), 300)"), Ok(()));
+    assert_eq!(CTorParser::validate("Resample(window=60, signal=Momentum(SMA, decay=0.9, windows=[20]))"), Ok(()));
+    assert_eq!(CTorParser::validate("Momentum(SMA, [200], 0.5).scaled(3).value(2)"), Ok(()));
+    assert_eq!(CTorParser::validate("Connection(\"db\", 5432).port()"), Ok(()));
+
+    // nothing is constructed
+    assert_eq!(CONNECTIONS.load(Ordering::SeqCst), 0);
+
+    // each problem is reported, identifying the failing argument
+    let problems = CTorParser::validate("Resample(Unknown(1), Momentum(SMA, [200]), Connection(\"db\", [1]))").unwrap_err();
+    let expressions: Vec<String> = problems.iter().map(|problem| match problem {
+        ReflectError::InExpression { expression, .. } => expression.clone(),
+        _ => panic!("expected problem within a sub-expression: {}", problem)
+    }).collect();
+    assert_eq!(expressions, vec!["Unknown(1)", "Momentum(SMA, [200])", "Connection(\"db\", [1])"]);
+    assert!(matches!(&problems[0], ReflectError::InExpression { error, .. } if matches!(**error, ReflectError::TypeNotFound(_))));
+    assert!(matches!(&problems[1], ReflectError::InExpression { error, .. } if matches!(**error, ReflectError::NoMatchingConstructor { .. })));
+    assert!(matches!(&problems[2], ReflectError::InExpression { error, .. } if matches!(**error, ReflectError::NoMatchingConstructor { .. })));
+
+    // problems with the outermost ctor or method calls
+    let problems = CTorParser::validate("Momentum(SMA, [200], 0.5).unknown(2)").unwrap_err();
+    assert_eq!(problems, vec![ReflectError::MethodNotFound { type_name: "Momentum".to_string(), method: "unknown".to_string() }]);
+    let problems = CTorParser::validate("Resample(Momentum(SMA, [200], 0.5), [1, 2])").unwrap_err();
+    assert!(matches!(problems[..], [ReflectError::NoMatchingConstructor { .. }]), "{:?}", problems);
+    assert!(CTorParser::validate("Momentum(SMA,").is_err());
+
+    // ctors within nested or mixed lists are checked without being constructed
+    let problems = CTorParser::validate("Committee([[Connection(\"db\", 1)], [Connection(\"db\", 2)]], 1.0)").unwrap_err();
+    assert!(matches!(&problems[..], [ReflectError::InExpression { error, .. }] if error.to_string().contains("nested lists must contain numbers")), "{:?}", problems);
+    let problems = CTorParser::validate("Committee([Connection(\"db\", 1), 1], 1.0)").unwrap_err();
+    assert!(matches!(&problems[..], [ReflectError::InExpression { error, .. }] if error.to_string().contains("cannot mix")), "{:?}", problems);
+    let problems = CTorParser::validate("Committee([[Unknown(1)]], 1.0)").unwrap_err();
+    assert!(matches!(&problems[0], ReflectError::InExpression { error, .. } if matches!(**error, ReflectError::TypeNotFound(_))), "{:?}", problems);
+    assert_eq!(CONNECTIONS.load(Ordering::SeqCst), 0);
+}
+
+
+#[test]
+fn test_display_round_trip() {
+    let exprs = [
+        "Resample(Momentum(SMA, [200, 50, 20], 0.9), 300)",
+        "Momentum(SMA,[200],0.5).scaled( 3 ).value(2)",
+        "Resample(window=60, signal=Momentum(SMA, decay=0.9, windows=[20]))",
+        "Matrix([[1.0, 2.5], [3, 4]])",
+        "F(0.1, 1e-7, 12345678.901234567, -0.0, 1e300, 0xFF, 1_000)",
+        "F(\"a, \\\"b\\\"\\\\c\\n\\td\", '\\'', '\\t', 'x', true, null)",
+        "F((1, \"a\", 2.5), {a: 1, \"b c\": \"d\"}, a::B(1))",
+    ];
+    for expr in exprs {
+        let ast = CTorParser::parse_expr(expr).expect("failed to parse");
+        let text = ast.to_string();
+        assert_eq!(CTorParser::parse_expr(&text).expect("failed to parse printed"), ast, "{}", text);
+    }
+
+    // canonical spacing and quoting
+    let ast = CTorParser::parse_expr("Momentum( SMA,[200,50],decay = 0.9 ).value(1)").unwrap();
+    assert_eq!(ast.to_string(), "Momentum(SMA, [200, 50], decay=0.9).value(1)");
+    let ast = CTorParser::parse_expr("F({a: 'x'}, \"q\\\"\")").unwrap();
+    assert_eq!(ast.to_string(), "F({\"a\": 'x'}, \"q\\\"\")");
+
+    // floats retain their precision
+    let ast = CtorExpr::Float(0.1 + 0.2);
+    assert_eq!(CTorParser::eval::<f64>(&ast.to_string()).unwrap(), 0.1 + 0.2);
+}
+
+
+struct Committee {
+    members: Vec<Momentum>,
+    weight: f64
+}
+
+#[reflect_impl]
+impl Committee {
+    fn new (members: &[Box<dyn std::any::Any>], weight: f64) -> Self {
+        let members = members.iter().map(|m| m.downcast_ref::<Momentum>().expect("not a Momentum").clone()).collect();
+        Committee { members, weight }
+    }
+}
+
+
+#[test]
+fn test_list_of_ctors() {
+    let rawobj = CTorParser::create("Committee([Momentum(SMA, [200], 0.5), Momentum(EMA, [50, 20], 0.9)], 0.5)").expect("failed to create");
+    let obj = rawobj.downcast_ref::<Committee>().expect("failed to downcast to type");
+    assert_eq!(obj.members.len(), 2);
+    assert_eq!(obj.members[0].ma, MAType::SMA);
+    assert_eq!(obj.members[1].windows, vec![50, 20]);
+    assert_eq!(obj.weight, 0.5);
+
+    // the list is passed through while other arguments are converted
+    let rawobj = CTorParser::create("Committee([Momentum(SMA, [200], 0.5)], 2)").expect("failed to create");
+    assert_eq!(rawobj.downcast_ref::<Committee>().unwrap().weight, 2.0);
+    assert_eq!(CTorParser::validate("Committee([Momentum(SMA, [200], 0.5)], 2)"), Ok(()));
+
+    // failing elements are identified
+    let err = CTorParser::create("Committee([Momentum(SMA, [200], 0.5), Momentum(XYZ)], 1.0)").unwrap_err();
+    assert!(matches!(&err, ReflectError::InExpression { expression, .. } if expression == "Momentum(XYZ)"), "{:?}", err);
+    assert!(CTorParser::validate("Committee([Momentum(SMA, [200], 0.5), Momentum(XYZ)], 1.0)").is_err());
+
+    // ctors are not mixed with other elements
+    let err = CTorParser::create("Committee([Momentum(SMA, [200], 0.5), 1], 1.0)").unwrap_err();
+    assert!(err.to_string().contains("cannot mix"), "{}", err);
+}
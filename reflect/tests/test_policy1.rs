@@ -0,0 +1,88 @@
+#![cfg(feature = "policy")]
+
+use reflect::{ConversionPolicy, DefaultPolicy, ReflectError, TypeInfo, with_policy};
+use reflect_macros::reflect_impl;
+use std::any::{Any, TypeId};
+
+
+struct Gauge {
+    level: f64
+}
+
+#[reflect_impl]
+impl Gauge {
+    fn new (level: f64) -> Self {
+        Gauge { level }
+    }
+
+    fn scaled (&self, steps: i32) -> f64 {
+        self.level * f64::from(steps)
+    }
+
+    fn weighted (&self, weights: &[f64]) -> f64 {
+        self.level * weights.iter().sum::<f64>()
+    }
+}
+
+
+// rejects conversions of floating point to integers, which round
+struct Lossless;
+
+impl ConversionPolicy for Lossless {
+    fn score (&self, from: TypeId, to: TypeId, score: Option<i32>) -> Option<i32> {
+        let floats = [TypeId::of::<f32>(), TypeId::of::<f64>()];
+        let integers = [TypeId::of::<i32>(), TypeId::of::<i64>(), TypeId::of::<u32>(), TypeId::of::<u64>()];
+        match floats.contains(&from) && integers.contains(&to) {
+            true => None,
+            false => score
+        }
+    }
+}
+
+
+// rejects any conversion
+struct Verbatim;
+
+impl ConversionPolicy for Verbatim {
+    fn score (&self, _from: TypeId, _to: TypeId, _score: Option<i32>) -> Option<i32> {
+        None
+    }
+}
+
+
+#[test]
+fn test_lossless_policy() {
+    let itype = TypeInfo::find_type("Gauge").expect("could not find type");
+    let obj = itype.create(&[Box::new(2i32) as Box<dyn Any>]).expect("failed to create");
+    let args = vec![Box::new(3.0f64) as Box<dyn Any>];
+
+    // f64 -> i32 is accepted as registered, but rejected by the policy
+    let scaled = itype.call(&obj, "scaled", &args).expect("failed to call");
+    assert_eq!(*scaled.downcast_ref::<f64>().unwrap(), 6.0);
+    let err = with_policy(Lossless, || itype.call(&obj, "scaled", &args)).unwrap_err();
+    assert!(matches!(err, ReflectError::NoMatchingMethod { .. }), "{:?}", err);
+
+    // whereas i32 -> f64 is accepted
+    let obj = with_policy(Lossless, || itype.create(&[Box::new(4i32) as Box<dyn Any>])).expect("failed to create");
+    assert_eq!(obj.downcast_ref::<Gauge>().unwrap().level, 4.0);
+
+    // the policy only applies within the closure
+    assert!(itype.call(&obj, "scaled", &args).is_ok());
+    let scaled = with_policy(DefaultPolicy, || itype.call(&obj, "scaled", &args)).expect("failed to call");
+    assert_eq!(*scaled.downcast_ref::<f64>().unwrap(), 12.0);
+}
+
+
+#[test]
+fn test_equivalent_types_under_policy() {
+    let itype = TypeInfo::find_type("Gauge").expect("could not find type");
+    let obj = itype.create(&[Box::new(2.0f64) as Box<dyn Any>]).expect("failed to create");
+    let args = vec![Box::new(vec![1.0f64, 2.0]) as Box<dyn Any>];
+
+    // Vec<f64> -> &[f64] is equivalent, so is passed as is unless the policy rejects it
+    let weighted = itype.call(&obj, "weighted", &args).expect("failed to call");
+    assert_eq!(*weighted.downcast_ref::<f64>().unwrap(), 6.0);
+    let err = with_policy(Verbatim, || itype.call(&obj, "weighted", &args)).unwrap_err();
+    assert!(matches!(err, ReflectError::NoMatchingMethod { .. }), "{:?}", err);
+    assert!(with_policy(DefaultPolicy, || itype.call(&obj, "weighted", &args)).is_ok());
+}
@@ -0,0 +1,119 @@
+#![cfg(feature = "pyo3")]
+
+use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyModule};
+use reflect::python::py_reflect;
+use reflect_macros::{reflect_enum, reflect_impl};
+
+
+#[reflect_enum]
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[allow(clippy::upper_case_acronyms)]
+enum MAType {
+    SMA,
+    EMA
+}
+
+#[derive(Clone)]
+struct Momentum {
+    ma: MAType,
+    windows: Vec<i32>,
+    decay: f32
+}
+
+#[reflect_impl]
+impl Momentum {
+    fn new (ma: MAType, windows: &[i32], decay: f32) -> Self {
+        Momentum { ma, windows: windows.to_vec(), decay }
+    }
+
+    fn windows (&self) -> Vec<i32> {
+        self.windows.clone()
+    }
+
+    fn with_decay (self, decay: f32) -> Self {
+        Momentum { decay, ..self }
+    }
+
+    fn kind (&self) -> String {
+        self.ma.to_string()
+    }
+
+    fn value (&self, x: i32) -> f64 {
+        f64::from(self.decay) * f64::from(x)
+    }
+}
+
+struct Resample {
+    signal: Momentum,
+    window: u32
+}
+
+#[reflect_impl]
+impl Resample {
+    fn new (signal: Momentum, window: u32) -> Self {
+        Resample { signal, window }
+    }
+
+    fn signal (&self) -> Momentum {
+        self.signal.clone()
+    }
+
+    fn window (&self) -> u32 {
+        self.window
+    }
+}
+
+
+// smoke test, driving reflection from python through an embedded interpreter
+#[test]
+fn test_python() {
+    pyo3::prepare_freethreaded_python();
+    Python::with_gil(|py| {
+        let module = PyModule::new_bound(py, "PyReflect").unwrap();
+        py_reflect(&module).unwrap();
+        let globals = PyDict::new_bound(py);
+        globals.set_item("PyReflect", module).unwrap();
+
+        let script = r#"
+# python ints and floats convert to the narrower parameter types
+momentum = PyReflect.create("Momentum", "EMA", [200, 50, 20], 0.5)
+assert momentum.type_name() == "Momentum", momentum.type_name()
+assert PyReflect.call(momentum, "windows") == [200, 50, 20]
+assert PyReflect.call(momentum, "kind") == "EMA"
+assert PyReflect.call(momentum, "value", 4) == 2.0
+
+# handles are passed as arguments and returned as results
+resample = PyReflect.create("Resample", momentum, 900)
+assert PyReflect.call(resample, "window") == 900
+signal = PyReflect.call(resample, "signal")
+assert PyReflect.call(signal, "value", 2) == 1.0
+assert PyReflect.call(momentum, "kind") == "EMA"
+
+# methods taking self consume the object of the handle
+faster = PyReflect.call(signal, "with_decay", 2)
+assert PyReflect.call(faster, "value", 2) == 4.0
+try:
+    PyReflect.call(signal, "kind")
+    assert False
+except ValueError as e:
+    assert "consumed" in str(e), str(e)
+
+# errors are raised as ValueError
+try:
+    PyReflect.create("Momentum", "WMA", [200], 0.5)
+    assert False
+except ValueError as e:
+    assert "failed to convert" in str(e), str(e)
+
+try:
+    PyReflect.call(momentum, "unknown")
+    assert False
+except ValueError as e:
+    assert "could not find method 'unknown'" in str(e), str(e)
+"#;
+        if let Err(e) = py.run_bound(script, Some(&globals), None) {
+            panic!("python failed: {}", e);
+        }
+    });
+}
@@ -0,0 +1,96 @@
+use reflect::{TypeInfo, registered_types, unregister, unregister_method, clear_registry};
+use reflect::{register_type, register_type_name, name_collisions, qualified_name, find_type};
+use reflect_macros::reflect_impl;
+use std::any::Any;
+
+
+struct Alpha {
+    x: i32
+}
+
+#[reflect_impl]
+impl Alpha {
+    fn new (x: i32) -> Self {
+        Alpha { x }
+    }
+
+    fn x (&self) -> i32 {
+        self.x
+    }
+}
+
+
+struct Beta;
+
+#[reflect_impl]
+impl Beta {
+    fn new (_x: i32) -> Self {
+        Beta
+    }
+}
+
+
+// types of the same short name, registered by hand after the registry is cleared
+mod first {
+    pub struct Gamma;
+}
+
+mod second {
+    pub struct Gamma;
+}
+
+
+// a single test, as the registry is shared by tests running in parallel
+#[test]
+fn test_unregister() {
+    assert_eq!(registered_types(), vec!["Alpha", "Beta"]);
+
+    // remove method
+    let itype = TypeInfo::find_type("Alpha").expect("could not find type");
+    let obj = itype.create(&[Box::new(3i32) as Box<dyn Any>]).expect("failed to call ctor");
+    assert!(itype.call(&obj, "x", &[]).is_ok());
+
+    assert!(unregister_method("Alpha", "x"));
+    assert!(!unregister_method("Alpha", "x"));
+    assert!(!unregister_method("Gamma", "x"));
+    let itype = TypeInfo::find_type("Alpha").expect("could not find type");
+    assert!(itype.call(&obj, "x", &[]).is_err());
+
+    // remove type
+    assert!(unregister("Alpha"));
+    assert!(!unregister("Alpha"));
+    assert!(TypeInfo::find_type("Alpha").is_none());
+    assert_eq!(registered_types(), vec!["Beta"]);
+
+    // remove all
+    clear_registry();
+    assert!(registered_types().is_empty());
+
+    // the later of types sharing a name is registered under its qualified name
+    let first = qualified_name::<first::Gamma>();
+    let second = qualified_name::<second::Gamma>();
+    register_type::<first::Gamma>(None);
+    register_type::<second::Gamma>(None);
+    assert_eq!(registered_types(), vec!["Gamma".to_string(), second.clone()]);
+    assert_eq!(name_collisions(), vec![("Gamma".to_string(), second.clone())]);
+
+    // removing it removes its collision, such that it takes its short name once free
+    assert!(unregister(&second));
+    assert!(name_collisions().is_empty());
+    assert!(unregister("Gamma"));
+    assert!(find_type(&first).is_none());
+    register_type::<second::Gamma>(None);
+    assert_eq!(registered_types(), vec!["Gamma"]);
+
+    // once cleared, types are registered as if new, without their prior names
+    register_type::<first::Gamma>(Some("Renamed"));
+    clear_registry();
+    assert!(name_collisions().is_empty());
+    register_type::<second::Gamma>(None);
+    register_type::<first::Gamma>(None);
+    assert_eq!(registered_types(), vec!["Gamma".to_string(), first.clone()]);
+    assert!(find_type(&second).is_some_and(|info| info.name == "Gamma"));
+    assert!(find_type("Renamed").is_none());
+    register_type_name::<first::Gamma>("Renamed");
+    assert_eq!(registered_types(), vec!["Gamma", "Renamed"]);
+}
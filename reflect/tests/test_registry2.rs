@@ -0,0 +1,62 @@
+use reflect::{Function, Method, ReflectError, TypeInfo, register_method};
+use std::any::{Any, TypeId};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+
+// number of times a CountedMethod has been cloned
+static CLONES: AtomicUsize = AtomicUsize::new(0);
+
+struct Counted;
+
+struct CountedMethod {
+    name: String
+}
+
+impl Function for CountedMethod {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn arg_types(&self) -> &[TypeId] {
+        &[]
+    }
+
+    fn return_type(&self) -> TypeId {
+        TypeId::of::<()>()
+    }
+}
+
+impl Method for CountedMethod {
+    fn call(&self, _obj: &Box<dyn Any>, _args: &[&dyn Any]) -> Result<Box<dyn Any>, ReflectError> {
+        Ok(Box::new(()))
+    }
+
+    fn clone_boxed(&self) -> Box<dyn Method> {
+        CLONES.fetch_add(1, Ordering::SeqCst);
+        Box::new(CountedMethod { name: self.name.clone() })
+    }
+}
+
+
+fn register (i: usize) {
+    register_method::<Counted>(Box::new(CountedMethod { name: format!("m{}", i) }));
+}
+
+
+#[test]
+fn test_registration_does_not_clone() {
+    // members accumulate in place
+    for i in 0..100 {
+        register(i);
+    }
+    assert_eq!(CLONES.load(Ordering::SeqCst), 0);
+
+    // registration while the type information is held is copy-on-write
+    let itype = TypeInfo::find_type("Counted").expect("could not find type");
+    register(100);
+    assert_eq!(CLONES.load(Ordering::SeqCst), 100);
+    assert_eq!(itype.methods.len(), 100);
+
+    let itype = TypeInfo::find_type("Counted").expect("could not find type");
+    assert_eq!(itype.methods.len(), 101);
+}
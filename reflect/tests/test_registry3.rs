@@ -0,0 +1,118 @@
+use reflect::{TypeInfo, CTorParser, ReflectError, lookup_type, name_collisions, qualified_name, register_type, find_type_by_id};
+use std::any::{Any, TypeId};
+
+
+// types of the same name in different modules
+mod alpha {
+    use reflect_macros::reflect_impl;
+
+    pub struct Config {
+        pub level: i32
+    }
+
+    #[reflect_impl]
+    impl Config {
+        fn new (level: i32) -> Self {
+            Config { level }
+        }
+    }
+
+    pub struct Setting(pub i32);
+
+    #[reflect_impl(qualified)]
+    impl Setting {
+        fn new (x: i32) -> Self {
+            Setting(x)
+        }
+    }
+}
+
+mod beta {
+    use reflect_macros::reflect_impl;
+
+    pub struct Config {
+        pub name: String
+    }
+
+    #[reflect_impl]
+    impl Config {
+        fn new (name: String) -> Self {
+            Config { name }
+        }
+    }
+
+    pub struct Setting;
+
+    #[reflect_impl(qualified)]
+    impl Setting {
+        fn new (_x: String) -> Self {
+            Setting
+        }
+    }
+}
+
+
+#[test]
+fn test_collisions() {
+    // the type registered first keeps the short name, the other is registered by its full name
+    let collisions = name_collisions();
+    assert_eq!(collisions.len(), 1, "{:?}", collisions);
+    let (name, qualified) = &collisions[0];
+    assert_eq!(name, "Config");
+    assert!(*qualified == qualified_name::<alpha::Config>() || *qualified == qualified_name::<beta::Config>());
+
+    // both are found by their full name
+    let atype = lookup_type("test_registry3::alpha::Config").expect("could not find type");
+    let btype = lookup_type("test_registry3::beta::Config").expect("could not find type");
+    assert_ne!(atype.objtype, btype.objtype);
+
+    let obj = atype.create(&[Box::new(3i32) as Box<dyn Any>]).expect("failed to call ctor");
+    assert_eq!(obj.downcast_ref::<alpha::Config>().unwrap().level, 3);
+    let obj = CTorParser::create("test_registry3::beta::Config(\"b\")").expect("failed to create");
+    assert_eq!(obj.downcast_ref::<beta::Config>().unwrap().name, "b");
+
+    // the short name resolves to the type registered under it
+    assert!(TypeInfo::find_type("Config").is_some());
+}
+
+
+#[test]
+fn test_short_names() {
+    // registered by full name, found by full or short name
+    let qualified = qualified_name::<alpha::Setting>();
+    assert_eq!(qualified, "test_registry3::alpha::Setting");
+    assert_eq!(lookup_type(&qualified).expect("could not find type").name, qualified);
+
+    // ... unless the short name is ambiguous
+    let err = lookup_type("Setting").err().expect("expected ambiguous type");
+    assert_eq!(err, ReflectError::AmbiguousType {
+        name: "Setting".to_string(),
+        candidates: vec!["test_registry3::alpha::Setting".to_string(), "test_registry3::beta::Setting".to_string()]
+    });
+    assert!(TypeInfo::find_type("Setting").is_none());
+    assert_eq!(CTorParser::create("Setting(1)").unwrap_err(), err);
+
+    let obj = CTorParser::create("test_registry3::alpha::Setting(1)").expect("failed to create");
+    assert_eq!(obj.downcast_ref::<alpha::Setting>().unwrap().0, 1);
+}
+
+
+// types known to the registry without reflected members
+struct Pending;
+struct Declared;
+
+
+#[test]
+fn test_register_type() {
+    register_type::<Pending>(None);
+    register_type::<Declared>(Some("later::Declared"));
+
+    let itype = TypeInfo::find_type("Pending").expect("could not find type");
+    assert_eq!(itype.objtype, TypeId::of::<Pending>());
+    assert!(itype.constructors.is_empty() && itype.methods.is_empty() && itype.functions.is_empty());
+    assert!(itype.create(&[]).is_err());
+
+    let itype = find_type_by_id(TypeId::of::<Declared>()).expect("could not find type");
+    assert_eq!(itype.name, "later::Declared");
+    assert!(TypeInfo::find_type("Declared").is_some());
+}
@@ -0,0 +1,64 @@
+use reflect::{CTorParser, Conversions};
+use reflect_macros::reflect_impl;
+use std::any::{Any, TypeId};
+
+
+#[derive(Clone, Debug, PartialEq)]
+struct Level(i32);
+
+#[derive(Clone)]
+struct Step(i32);
+
+
+// the conversion to `Level`, registered apart from the types relying on it (as if by another
+// crate), in whatever order the ctor functions happen to run
+#[ctor::ctor]
+fn register_level_conversion() {
+    Conversions::add(TypeId::of::<i32>(), TypeId::of::<Level>(), 150, |v: &Box<dyn Any>| {
+        v.downcast_ref::<i32>().map(|x| Box::new(Level(*x)) as Box<dyn Any>)
+    });
+}
+
+
+// the type relying on the conversion, registered by a ctor function of its own (generated by
+// `reflect_impl`), along with the conversion of lists to `[Level; 2]`
+struct Mixer {
+    levels: [Level; 2]
+}
+
+#[reflect_impl]
+impl Mixer {
+    fn new (levels: [Level; 2]) -> Self {
+        Mixer { levels }
+    }
+}
+
+
+#[test]
+fn test_ctor_order_independence() {
+    let obj = CTorParser::create("Mixer([1, 2])").expect("failed to create");
+    assert_eq!(obj.downcast_ref::<Mixer>().unwrap().levels, [Level(1), Level(2)]);
+
+    let score = Conversions::score_of(TypeId::of::<Vec<i32>>(), TypeId::of::<[Level; 2]>());
+    assert_eq!(score, Some(150));
+}
+
+
+#[test]
+fn test_late_registration() {
+    // the list conversion is registered before the conversion of its elements
+    Conversions::add_array::<Step, 2>();
+    assert!(Conversions::find(TypeId::of::<Vec<i32>>(), TypeId::of::<[Step; 2]>()).is_none());
+    assert!(Conversions::find(TypeId::of::<Vec<Step>>(), TypeId::of::<[Step; 2]>()).is_some());
+
+    // and is found, scored as the element conversion, once the element conversion is registered
+    Conversions::add(TypeId::of::<i32>(), TypeId::of::<Step>(), 120, |v: &Box<dyn Any>| {
+        v.downcast_ref::<i32>().map(|x| Box::new(Step(*x)) as Box<dyn Any>)
+    });
+    let conversion = Conversions::find(TypeId::of::<Vec<i32>>(), TypeId::of::<[Step; 2]>()).expect("no conversion");
+    assert_eq!(Conversions::score_of(TypeId::of::<Vec<i32>>(), TypeId::of::<[Step; 2]>()), Some(120));
+
+    let steps = conversion.convert(&(Box::new(vec![4, 5]) as Box<dyn Any>)).expect("failed to convert");
+    let steps = steps.downcast_ref::<[Step; 2]>().unwrap();
+    assert_eq!((steps[0].0, steps[1].0), (4, 5));
+}
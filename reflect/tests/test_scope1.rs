@@ -0,0 +1,162 @@
+use reflect::{CTorParser, Constructor, Function, Method, ReflectError, TypeInfo, find_type_by_id, with_scope};
+use reflect_macros::reflect_impl;
+use std::any::{Any, TypeId};
+
+
+// a type registered by hand, only within scopes
+struct Tenant {
+    id: i64
+}
+
+#[derive(Clone)]
+struct TenantConstructor {
+    arg_types: Vec<TypeId>
+}
+
+impl Function for TenantConstructor {
+    fn name(&self) -> &str {
+        "*"
+    }
+
+    fn arg_types(&self) -> &[TypeId] {
+        &self.arg_types
+    }
+
+    fn return_type(&self) -> TypeId {
+        TypeId::of::<Tenant>()
+    }
+}
+
+impl Constructor for TenantConstructor {
+    fn create(&self, args: &[&dyn Any]) -> Result<Box<dyn Any>, ReflectError> {
+        match args.first().and_then(|arg| arg.downcast_ref::<i64>()) {
+            Some(id) => Ok(Box::new(Tenant { id: *id })),
+            None => Err(ReflectError::InvalidArgument { index: 0 })
+        }
+    }
+
+    fn clone_boxed(&self) -> Box<dyn Constructor> {
+        Box::new(self.clone())
+    }
+}
+
+
+// a type registered globally, extended within a scope
+struct Account {
+    balance: f64
+}
+
+#[reflect_impl]
+impl Account {
+    fn new (balance: f64) -> Self {
+        Account { balance }
+    }
+}
+
+#[derive(Clone)]
+struct DoubledMethod;
+
+impl Function for DoubledMethod {
+    fn name(&self) -> &str {
+        "doubled"
+    }
+
+    fn arg_types(&self) -> &[TypeId] {
+        &[]
+    }
+
+    fn return_type(&self) -> TypeId {
+        TypeId::of::<f64>()
+    }
+}
+
+impl Method for DoubledMethod {
+    fn call(&self, obj: &Box<dyn Any>, _args: &[&dyn Any]) -> Result<Box<dyn Any>, ReflectError> {
+        Ok(Box::new(obj.downcast_ref::<Account>().unwrap().balance * 2.0))
+    }
+
+    fn clone_boxed(&self) -> Box<dyn Method> {
+        Box::new(self.clone())
+    }
+}
+
+
+fn tenant_constructor() -> Box<dyn Constructor> {
+    Box::new(TenantConstructor { arg_types: vec![TypeId::of::<i64>()] })
+}
+
+
+#[test]
+fn test_scoped_type() {
+    let id = with_scope(|scope| {
+        scope.register_constructor::<Tenant>(tenant_constructor());
+        assert_eq!(scope.registered_types(), vec!["Tenant".to_string()]);
+        assert!(TypeInfo::find_type("Tenant").is_some());
+        assert!(find_type_by_id(TypeId::of::<Tenant>()).is_some());
+
+        let obj = CTorParser::create("Tenant(42)").expect("failed to create");
+        obj.downcast_ref::<Tenant>().unwrap().id
+    });
+    assert_eq!(id, 42);
+
+    // not visible once the scope ends
+    assert!(TypeInfo::find_type("Tenant").is_none());
+    assert!(find_type_by_id(TypeId::of::<Tenant>()).is_none());
+    assert_eq!(CTorParser::create("Tenant(42)").unwrap_err(), ReflectError::TypeNotFound("Tenant".to_string()));
+}
+
+
+#[test]
+fn test_scope_is_thread_local() {
+    with_scope(|scope| {
+        scope.register_constructor::<Tenant>(tenant_constructor());
+        let elsewhere = std::thread::spawn(|| TypeInfo::find_type("Tenant").is_none()).join().unwrap();
+        assert!(elsewhere);
+        assert!(TypeInfo::find_type("Tenant").is_some());
+    });
+}
+
+
+#[test]
+fn test_nested_scopes() {
+    with_scope(|outer| {
+        outer.register_type::<Tenant>(Some("Client"));
+        with_scope(|inner| {
+            // members extend the type of the enclosing scope
+            inner.register_constructor::<Tenant>(tenant_constructor());
+            assert_eq!(TypeInfo::find_type("Client").unwrap().constructors.len(), 1);
+            assert!(CTorParser::create("Client(1)").is_ok());
+        });
+        assert_eq!(TypeInfo::find_type("Client").unwrap().constructors.len(), 0);
+    });
+    assert!(TypeInfo::find_type("Client").is_none());
+}
+
+
+#[test]
+fn test_extend_global_type() {
+    with_scope(|scope| {
+        scope.register_method::<Account>(Box::new(DoubledMethod));
+        let doubled = CTorParser::eval::<f64>("Account(2.5).doubled()").expect("failed to evaluate");
+        assert_eq!(doubled, 5.0);
+    });
+
+    // the global type is as it was
+    let itype = TypeInfo::find_type("Account").expect("could not find type");
+    assert!(!itype.methods.contains_key("doubled"));
+    assert_eq!(itype.constructors.len(), 1);
+    assert!(CTorParser::call("Account(2.5).doubled()").is_err());
+}
+
+
+#[test]
+fn test_scope_ends_on_panic() {
+    let result = std::panic::catch_unwind(|| {
+        with_scope(|scope| {
+            scope.register_constructor::<Tenant>(tenant_constructor());
+            panic!("failed within scope");
+        })
+    });
+    assert!(result.is_err());
+    assert!(TypeInfo::find_type("Tenant").is_none());
+}
@@ -1,20 +1,28 @@
 //! Code generation for enum
 //! - generation of FromStr trait
+//! - generation of Display trait
 //! - generation of type conversion registration
 //!
 
-use proc_macro::TokenStream;
-use quote::{quote, format_ident, ToTokens};
-use syn::{parse_macro_input, DeriveInput, Data, Fields};
+use std::collections::HashSet;
+
+use quote::{quote, format_ident};
+use syn::{DeriveInput, Data, Fields, Lit, LitStr, Meta, NestedMeta, Variant};
+
+use crate::enums::options::EnumOptions;
 
 
 /// Generate implementation of FromStr trait for enum
-/// - generate `String` to `enum` field mappings
+/// - generate `String` to `enum` field mappings, including aliases given by
+///   `#[reflect(alias = "...")]` on variants and renames given by `#[reflect(rename = "...")]`
 /// - implement `FromStr` on enum
+/// - if `case_insensitive`, both the input and variant names are lowercased when matching
+/// - fails if a `#[reflect(...)]` attribute of a variant is invalid, or a name or alias is that
+///   of another variant (or repeated)
 ///
 /// We may want to check whether an implementation already exists OR allow user of macro to
 /// provide a boolean in macro call
-pub fn generate_enum_fromstr(input: &DeriveInput) -> proc_macro2::TokenStream {
+pub fn generate_enum_fromstr(input: &DeriveInput, options: &EnumOptions) -> Result<proc_macro2::TokenStream, syn::Error> {
     let name = &input.ident;
     let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
 
@@ -24,22 +32,45 @@ pub fn generate_enum_fromstr(input: &DeriveInput) -> proc_macro2::TokenStream {
         _ => panic!("This macro can only be applied to enums"),
     };
 
-    // conversion cases for match within from_str()
-    let from_str_cases = fields.iter().map(|v| {
+    // conversion cases for match within from_str(), each name matching a single variant
+    let fold = |name: String| if options.case_insensitive { name.to_lowercase() } else { name };
+    let mut seen = HashSet::new();
+    let mut from_str_cases = Vec::new();
+    for v in fields {
         let ident = &v.ident;
-        let stringified = ident.to_string();
+        let variant_options = parse_variant_options(v)?;
+        let mut names = Vec::new();
+        let spans = std::iter::once((variant_options.name, None))
+            .chain(variant_options.aliases.into_iter().map(|alias| (alias.value(), Some(alias))));
+        for (name, alias) in spans {
+            let name = fold(name);
+            if !seen.insert(name.clone()) {
+                let message = format!("`{}` of variant `{}` is already the name or alias of a variant", name, ident);
+                return Err(match alias {
+                    Some(alias) => syn::Error::new_spanned(alias, message),
+                    None => syn::Error::new_spanned(v, message),
+                });
+            }
+            names.push(name);
+        }
         match &v.fields {
-            Fields::Unit => quote! { #stringified => Ok(Self::#ident) },
-            _ => panic!("This macro only supports unit variants"),
+            Fields::Unit => from_str_cases.push(quote! { #(#names)|* => Ok(Self::#ident) }),
+            fields => return Err(syn::Error::new_spanned(fields, "reflect_enum only supports unit variants")),
         }
-    });
+    }
+
+    let matched = if options.case_insensitive {
+        quote! { s.to_lowercase().as_str() }
+    } else {
+        quote! { s }
+    };
 
     let expanded = quote! {
         impl #impl_generics std::str::FromStr for #name #ty_generics #where_clause {
             type Err = String;
 
             fn from_str(s: &str) -> Result<Self, Self::Err> {
-                match s {
+                match #matched {
                     #(#from_str_cases,)*
                     _ => Err(format!("Unknown variant: {}", s)),
                 }
@@ -47,32 +78,221 @@ pub fn generate_enum_fromstr(input: &DeriveInput) -> proc_macro2::TokenStream {
         }
     };
 
-    proc_macro2::TokenStream::from(expanded)
+    Ok(expanded)
+}
+
+
+/// Generate implementation of Display trait for enum
+/// - each variant is displayed by its name (or the name given by `#[reflect(rename = "...")]`),
+///   such that the displayed value parses back to the same variant
+pub fn generate_enum_display(input: &DeriveInput) -> Result<proc_macro2::TokenStream, syn::Error> {
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    // get enum fields
+    let fields = match &input.data {
+        Data::Enum(data_enum) => &data_enum.variants,
+        _ => panic!("This macro can only be applied to enums"),
+    };
+
+    // cases for match within fmt()
+    let mut display_cases = Vec::new();
+    for v in fields {
+        let ident = &v.ident;
+        let displayed = parse_variant_options(v)?.name;
+        display_cases.push(quote! { Self::#ident => #displayed });
+    }
+
+    Ok(quote! {
+        impl #impl_generics std::fmt::Display for #name #ty_generics #where_clause {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                let name = match self {
+                    #(#display_cases,)*
+                };
+                f.write_str(name)
+            }
+        }
+    })
 }
 
 
 /// Generate enum type conversion registration
-pub fn generate_enum_registration(input: &DeriveInput) -> proc_macro2::TokenStream {
+/// - `String` -> `enum`
+/// - `enum` -> `String`
+/// - `Vec<String>` -> `&[enum]` and `Vec<enum>`, converting element-wise
+/// - if `from_repr`, `i32` / `i64` <-> `enum` by discriminant (scoring below the conversion by
+///   name)
+pub fn generate_enum_registration(input: &DeriveInput, options: &EnumOptions) -> proc_macro2::TokenStream {
     let name = &input.ident;
     let register_ident = format_ident!("_REGISTER_{}", name);
 
+    let repr_conversions = if options.from_repr {
+        let variants: Vec<_> = match &input.data {
+            Data::Enum(data_enum) => data_enum.variants.iter().map(|v| &v.ident).collect(),
+            _ => panic!("This macro can only be applied to enums"),
+        };
+        let conversions = [quote! { i32 }, quote! { i64 }].into_iter().map(|int| quote! {
+            reflect::Conversions::add(
+                std::any::TypeId::of::<#int>(),
+                std::any::TypeId::of::<#name>(),
+                80,
+                |v: &Box<dyn std::any::Any>| {
+                    let x = *v.downcast_ref::<#int>().unwrap();
+                    #(
+                        if x == #name::#variants as #int {
+                            return Some(Box::new(#name::#variants) as Box<dyn std::any::Any>);
+                        }
+                    )*
+                    None
+                }
+            );
+            reflect::Conversions::add(
+                std::any::TypeId::of::<#name>(),
+                std::any::TypeId::of::<#int>(),
+                80,
+                |v: &Box<dyn std::any::Any>| {
+                    let x = match v.downcast_ref::<#name>().unwrap() {
+                        #( #name::#variants => #name::#variants as #int, )*
+                    };
+                    Some(Box::new(x) as Box<dyn std::any::Any>)
+                }
+            );
+        });
+        quote! { #(#conversions)* }
+    } else {
+        quote! {}
+    };
+
     let expanded = quote! {
         #[ctor::ctor]
+        #[allow(non_snake_case)]
         fn #register_ident () {
+            reflect::Conversions::add_type_name::<#name>();
             reflect::Conversions::add(
                 std::any::TypeId::of::<String>(),
                 std::any::TypeId::of::<#name>(),
                 100,
                 |v: &Box<dyn std::any::Any>| {
                     let s = v.downcast_ref::<String>().unwrap();
-                    match #name::from_str(s) {
+                    match <#name as std::str::FromStr>::from_str(s) {
                         Ok(e) => Some(Box::new(e) as Box<dyn std::any::Any>),
                         Err(_) => None
                     }
                 }
             );
+            reflect::Conversions::add(
+                std::any::TypeId::of::<#name>(),
+                std::any::TypeId::of::<String>(),
+                100,
+                |v: &Box<dyn std::any::Any>| {
+                    let e = v.downcast_ref::<#name>().unwrap();
+                    Some(Box::new(e.to_string()) as Box<dyn std::any::Any>)
+                }
+            );
+            reflect::Conversions::add(
+                std::any::TypeId::of::<Vec<String>>(),
+                std::any::TypeId::of::<&[#name]>(),
+                100,
+                reflect::Conversions::convert_elements::<String, #name>
+            );
+            reflect::Conversions::add(
+                std::any::TypeId::of::<Vec<String>>(),
+                std::any::TypeId::of::<Vec<#name>>(),
+                100,
+                reflect::Conversions::convert_elements::<String, #name>
+            );
+            #repr_conversions
         }
     };
 
-    proc_macro2::TokenStream::from(expanded)
-}
\ No newline at end of file
+    expanded
+}
+
+/// Remove `#[reflect(...)]` attributes from the variants of an enum
+/// - these are consumed by `reflect_enum`, so must not be emitted
+pub fn strip_variant_attributes(input: &mut DeriveInput) {
+    if let Data::Enum(data_enum) = &mut input.data {
+        for variant in data_enum.variants.iter_mut() {
+            variant.attrs.retain(|attr| !attr.path.is_ident("reflect"));
+        }
+    }
+}
+
+
+// Options of a variant given by `#[reflect(...)]`
+// - name of the variant (the identifier unless renamed)
+// - alternative names (aliases), which may be repeated
+struct VariantOptions {
+    name: String,
+    aliases: Vec<LitStr>,
+}
+
+
+// Parse options of a variant given by `#[reflect(rename = "...", alias = "...")]`, or an error
+// spanning the invalid option
+fn parse_variant_options(variant: &Variant) -> Result<VariantOptions, syn::Error> {
+    let mut options = VariantOptions { name: variant.ident.to_string(), aliases: Vec::new() };
+
+    for attr in variant.attrs.iter().filter(|attr| attr.path.is_ident("reflect")) {
+        let nested = match attr.parse_meta()? {
+            Meta::List(list) => list.nested,
+            meta => return Err(syn::Error::new_spanned(meta, "expected #[reflect(...)]")),
+        };
+
+        for option in nested {
+            match option {
+                NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("alias") => match nv.lit {
+                    Lit::Str(alias) => options.aliases.push(alias),
+                    lit => return Err(syn::Error::new_spanned(lit, "expected #[reflect(alias = \"name\")]")),
+                },
+                NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("rename") => match nv.lit {
+                    Lit::Str(name) => options.name = name.value(),
+                    lit => return Err(syn::Error::new_spanned(lit, "expected #[reflect(rename = \"name\")]")),
+                },
+                option => return Err(syn::Error::new_spanned(option, "unsupported option in #[reflect(...)] on enum variant")),
+            }
+        }
+    }
+
+    Ok(options)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use syn::parse_quote;
+
+    #[test]
+    fn test_invalid_variant_options() {
+        let input: DeriveInput = parse_quote! {
+            enum MAType { #[reflect(alias = 42)] SMA, EMA }
+        };
+        let error = generate_enum_fromstr(&input, &EnumOptions::default()).expect_err("expected error");
+        assert_eq!(error.to_string(), "expected #[reflect(alias = \"name\")]");
+
+        let input: DeriveInput = parse_quote! {
+            enum MAType { #[reflect(skip)] SMA, EMA }
+        };
+        let error = generate_enum_display(&input).expect_err("expected error");
+        assert_eq!(error.to_string(), "unsupported option in #[reflect(...)] on enum variant");
+    }
+
+    #[test]
+    fn test_duplicate_alias() {
+        let input: DeriveInput = parse_quote! {
+            enum MAType { #[reflect(alias = "simple")] SMA, #[reflect(alias = "SMA")] EMA }
+        };
+        let error = generate_enum_fromstr(&input, &EnumOptions::default()).expect_err("expected error");
+        assert_eq!(error.to_string(), "`SMA` of variant `EMA` is already the name or alias of a variant");
+
+        // names differing in case only are the same when matched irrespective of case
+        let input: DeriveInput = parse_quote! {
+            enum MAType { #[reflect(alias = "simple")] SMA, #[reflect(rename = "Sma")] EMA }
+        };
+        assert!(generate_enum_fromstr(&input, &EnumOptions::default()).is_ok());
+        let options = EnumOptions { case_insensitive: true, ..EnumOptions::default() };
+        let error = generate_enum_fromstr(&input, &options).expect_err("expected error");
+        assert_eq!(error.to_string(), "`sma` of variant `EMA` is already the name or alias of a variant");
+    }
+}
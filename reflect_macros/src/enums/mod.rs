@@ -1,2 +1,3 @@
 
 pub mod generator;
+pub mod options;
@@ -0,0 +1,60 @@
+//! Options of the `reflect_enum` attribute
+//! - `case_insensitive`: match variant names irrespective of case when parsing
+//! - `from_repr`: convert between integers and the enum by discriminant
+//!
+
+use syn::{AttributeArgs, Meta, NestedMeta};
+
+
+/// Options specified with `#[reflect_enum(...)]`
+#[derive(Default)]
+pub struct EnumOptions {
+    pub case_insensitive: bool,
+    pub from_repr: bool,
+}
+
+
+/// Parse options of `#[reflect_enum(...)]`
+///
+/// # Arguments
+/// * `args`: attribute arguments
+///
+/// # Returns
+/// * options specified (or defaults if there are none), or an error spanning the invalid option
+pub fn parse_enum_options(args: &AttributeArgs) -> Result<EnumOptions, syn::Error> {
+    let mut options = EnumOptions::default();
+
+    for arg in args {
+        match arg {
+            NestedMeta::Meta(Meta::Path(path)) if path.is_ident("case_insensitive") =>
+                options.case_insensitive = true,
+            NestedMeta::Meta(Meta::Path(path)) if path.is_ident("from_repr") =>
+                options.from_repr = true,
+            _ => return Err(syn::Error::new_spanned(arg, "unsupported option in #[reflect_enum(...)]")),
+        }
+    }
+
+    Ok(options)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use syn::parse_quote;
+
+    #[test]
+    fn test_parse_options() {
+        let args: AttributeArgs = vec![parse_quote!(case_insensitive), parse_quote!(from_repr)];
+        let options = parse_enum_options(&args).expect("failed to parse");
+        assert!(options.case_insensitive);
+        assert!(options.from_repr);
+    }
+
+    #[test]
+    fn test_unsupported_option() {
+        let args: AttributeArgs = vec![parse_quote!(from_repr), parse_quote!(from_str = "x")];
+        let error = parse_enum_options(&args).err().expect("expected error");
+        assert_eq!(error.to_string(), "unsupported option in #[reflect_enum(...)]");
+    }
+}
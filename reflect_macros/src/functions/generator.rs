@@ -0,0 +1,95 @@
+//! Code generation for a free function (not within an impl block)
+//! - generate code for the function
+//! - generate registration in the global function table
+//!
+
+use quote::{quote, format_ident};
+
+use crate::types::parser::ParsedFunction;
+use crate::types::generator::{generate_arg_details, generate_boxed_type, generate_param_defaults, generate_param_names, generate_return_value};
+use crate::utilities::ident_camel_case;
+
+
+/// Generates code for a free function and registration
+/// - implenentation of `Function` trait
+/// - implenentation of `StaticFunction` trait
+/// - registration as a free function
+pub fn generate_free_function(function: &ParsedFunction) -> proc_macro2::TokenStream {
+    let method_name = &function.name;
+    let reflected_name = &function.reflected_name;
+
+    let fun_impl_name = format_ident!("{}FreeFunction", ident_camel_case(method_name));
+    let register_ident = format_ident!("_REGISTER_{}", fun_impl_name);
+
+    let (arg_conversions, arg_names, arg_types) = generate_arg_details(&function.args);
+    let return_type = &function.return_type;
+    let boxed_type = generate_boxed_type(function);
+    let doc = &function.doc;
+    let param_names = generate_param_names(&function.args);
+    let param_defaults = generate_param_defaults(&function.defaults);
+    let return_value = generate_return_value(function.return_kind);
+
+    quote! {
+        #[derive(Clone)]
+        struct #fun_impl_name {
+            _name: String,
+            _arg_types: Vec<std::any::TypeId>,
+            _return_type_name: String
+        }
+
+        impl ::reflect::Function for #fun_impl_name {
+            fn name(&self) -> &str {
+                &self._name
+            }
+
+            fn arg_types(&self) -> &[std::any::TypeId] {
+                &self._arg_types
+            }
+
+            fn return_type(&self) -> std::any::TypeId {
+                std::any::TypeId::of::<#return_type>()
+            }
+
+            #boxed_type
+
+            fn return_type_name(&self) -> &str {
+                &self._return_type_name
+            }
+
+            fn doc(&self) -> &str {
+                #doc
+            }
+
+            fn arg_names(&self) -> &[&str] {
+                &[#(#param_names),*]
+            }
+
+            fn arg_defaults(&self) -> &[Option<&str>] {
+                &[#(#param_defaults),*]
+            }
+        }
+
+        impl ::reflect::StaticFunction for #fun_impl_name {
+            fn call(&self, args: &[&dyn std::any::Any]) -> Result<Box<dyn std::any::Any>, ::reflect::ReflectError> {
+                #(#arg_conversions)*
+                let result = #method_name(#(#arg_names),*);
+                #return_value
+            }
+
+            fn clone_boxed(&self) -> Box<dyn ::reflect::StaticFunction> {
+                Box::new(self.clone())
+            }
+        }
+
+        #[ctor::ctor]
+        #[allow(non_snake_case)]
+        fn #register_ident() {
+            ::reflect::Conversions::add_type_name::<#return_type>();
+            ::reflect::register_free_function(Box::new(#fun_impl_name {
+                _name: #reflected_name.to_string(),
+                _arg_types: vec![#(#arg_types),*],
+                _return_type_name: ::reflect::type_shortname::<#return_type>()
+            }));
+        }
+    }
+}
@@ -13,17 +13,19 @@
 
 mod types;
 mod enums;
+mod newtypes;
+mod functions;
 mod utilities;
 
 use proc_macro::TokenStream;
-use quote::{quote, format_ident, ToTokens};
-use syn::{parse_macro_input, DeriveInput, Data, Fields};
+use quote::quote;
+use syn::{parse_macro_input, AttributeArgs, DeriveInput};
 
 
 /// Attribute to reflect ctors and methods in a type implementation
 ///
 /// # Usage
-/// ```
+/// ```ignore
 /// #[reflect_impl]
 /// impl Trait for MyType {
 ///     fn f (&self, x: f64) -> f64;
@@ -45,6 +47,62 @@ use syn::{parse_macro_input, DeriveInput, Data, Fields};
 /// - registration for each ctor, method, static function
 /// - registration for the overall type
 ///
+/// Methods taking `&mut self` are called with `TypeInfo::call_mut`, and methods taking `self`
+/// (such as builder methods) with `TypeInfo::call_consuming`.
+/// With the `async` feature, async methods taking `&self` are called with
+/// `TypeInfo::call_async`, returning a future to be awaited on any runtime.
+///
+/// Functions returning `Self` (or `Result<Self, E>`) are ctors, as are functions returning
+/// `impl Trait`, which create an object of the concrete type behind the trait: methods of that
+/// type are called on the object as reflected for the concrete type (found by
+/// `find_type_by_id`).  A function returning a trait object (`Box<dyn Trait>`) is a static
+/// function, and as `Any` cannot recover the trait, no method of the trait can be called on it.
+///
+/// Functions marked with `#[reflect(skip)]` are not reflected.  Functions marked with
+/// `#[reflect(rename = "name")]` are reflected under the given name.  Methods (or static
+/// functions) reflected under the same name are overloads of the name, of which the best match
+/// for the arguments is called, as for ctors.
+///
+/// Parameters marked with `#[reflect(default = "expr")]`, such as
+/// `fn new (alpha: f64, #[reflect(default = "1.0")] beta: f64)`, may be omitted when called,
+/// taking the value of the expression (converted to the parameter type) instead.
+///
+/// The type is registered under its short type name, unless given another name or a namespace,
+/// for example `#[reflect_impl(rename = "Mom", namespace = "indicators")]` registers the type as
+/// `indicators::Mom`.  Other impl blocks of the type need not repeat the options.  With
+/// `#[reflect_impl(qualified)]` the type is registered under its fully qualified name, such as
+/// `mycrate::indicators::Momentum`, avoiding collisions with types of the same name in other
+/// modules.  Types are found by registered, fully qualified, or (if unambiguous) short name.
+///
+/// Reflected types are concrete and `'static`, so impls with generic or lifetime parameters
+/// (such as `impl<'a> Parser<'a>`) are rejected; reflect each instantiation instead, such as
+/// `impl Buffer<f64>` or `impl Parser<'static>` (registered as `Buffer<f64>` and `Parser`).
+///
+/// With `#[reflect_impl(clone)]`, the type's `Clone` implementation is registered as well, such
+/// that objects can be copied with `TypeInfo::clone_object` without knowing their type.
+/// Likewise `#[reflect_impl(debug)]` registers the type's `Debug` implementation, for
+/// `TypeInfo::debug_string`, and `#[reflect_impl(eq)]` its `PartialEq` implementation, for
+/// `TypeInfo::objects_equal`.
+///
+/// With `#[reflect_impl(auto_convert)]`, each ctor taking a single object, such as
+/// `fn new (point: Point) -> Self`, is registered as a conversion from `Point` to the type as
+/// well, such that a `Point` may be given wherever the type is expected, as in
+/// `Label(Point(1.0, 2.0), origin)`.  Ctors of primitives, strings, containers, or the type itself
+/// are not, and conversions registered explicitly between the types take precedence.
+///
+/// Trait methods relying on the trait's default implementation do not appear within the impl
+/// block, so must be listed by signature to be reflected, separated by `;`:
+/// ```ignore
+/// #[reflect_impl(include = "fn describe(&self) -> String; fn scaled(&self, k: f64) -> Self")]
+/// impl Shape for MyType {
+///     fn area (&self) -> f64;
+/// }
+/// ```
+///
+/// Functions returning `Result<T, E>` yield a boxed `T`, with the error surfaced as a
+/// `ReflectError`.  Functions returning `Option<T>` yield a boxed `Option<T>`, or if marked with
+/// `#[reflect(none_as_error)]`, a boxed `T` with `None` surfaced as a `ReflectError`.
+///
 /// Given the above registration can then:
 /// - create new `MyType` through reflection, yielding an object, say `obj`:
 ///   * `let obj = TypeInfo.create (args)`
@@ -56,7 +114,7 @@ use syn::{parse_macro_input, DeriveInput, Data, Fields};
 /// configuration, python, etc. could have a constructed expression such as:
 ///
 /// In json config
-/// ```
+/// ```text
 /// {
 ///    "ctor": "MyType(3.149256, [200, 50, 20])"
 /// }
@@ -65,10 +123,19 @@ use syn::{parse_macro_input, DeriveInput, Data, Fields};
 /// expressions in configuration or from a scripting environment.
 ///
 #[proc_macro_attribute]
-pub fn reflect_impl(_attr: TokenStream, item: TokenStream) -> TokenStream {
-    let input = parse_macro_input!(item as syn::ItemImpl);
-    let parsed_data = types::parser::parse_type_block (&input);
+pub fn reflect_impl(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(attr as AttributeArgs);
+    let mut input = parse_macro_input!(item as syn::ItemImpl);
+    let options = match types::options::parse_impl_options (&args) {
+        Ok(options) => options,
+        Err(error) => return error.to_compile_error().into(),
+    };
+    let parsed_data = match types::parser::parse_type_block (&input, &options) {
+        Ok(parsed_data) => parsed_data,
+        Err(error) => return error.to_compile_error().into(),
+    };
     let registrations = types::generator::generate_reflection_for_type (&parsed_data);
+    types::attributes::strip_reflect_attributes (&mut input);
 
     quote! {
         #input
@@ -77,13 +144,53 @@ pub fn reflect_impl(_attr: TokenStream, item: TokenStream) -> TokenStream {
 }
 
 
+/// Attribute to reflect a free function (a function not within an impl block)
+///
+/// # Usage
+/// ```ignore
+/// #[reflect_fn]
+/// fn blend (a: f64, b: f64, w: f64) -> f64 {
+///     a * (1.0 - w) + b * w
+/// }
+/// ```
+///
+/// This will generate an implementation of `StaticFunction` and register it in the global
+/// function table, such that it can be called by name:
+/// - `reflect::call_function ("blend", arguments)`
+/// - or within a ctor expression, such as `"Momentum(SMA, [200], blend(0.8, 0.9, 0.5))"`
+///
+/// The function may be marked with `#[reflect(rename = "name")]` to register it under a
+/// different name.
+///
+#[proc_macro_attribute]
+pub fn reflect_fn(_attr: TokenStream, item: TokenStream) -> TokenStream {
+    let mut input = parse_macro_input!(item as syn::ItemFn);
+    let options = match types::attributes::parse_reflect_attributes (&input.attrs) {
+        Ok(options) => options,
+        Err(error) => return error.to_compile_error().into(),
+    };
+    let parsed_data = match types::parser::parse_function (&input.sig, &options, types::function_type::FunctionType::Static, None) {
+        Ok(parsed_data) => parsed_data,
+        Err(error) => return error.to_compile_error().into(),
+    };
+    let registration = functions::generator::generate_free_function (&parsed_data);
+    types::attributes::strip_function_attributes (&mut input.attrs);
+    types::attributes::strip_parameter_attributes (&mut input.sig);
+
+    quote! {
+        #input
+        #registration
+    }.into()
+}
+
+
 /// Attribute to reflect enums
-/// - allow enum creation from `String`
-/// - registration of the `String` -> `enum` conversion
+/// - allow enum creation from `String` and display as `String`
+/// - registration of the `String` -> `enum` and `enum` -> `String` conversions
 ///
 /// # Usage
 /// Here is some example code:
-/// ```
+/// ```ignore
 ///   #[reflect_enum]
 ///   enum MAType {
 ///       SMA,
@@ -92,14 +199,14 @@ pub fn reflect_impl(_attr: TokenStream, item: TokenStream) -> TokenStream {
 ///   }
 /// ```
 ///
-/// The `reflect_enum` macro will generate an implementation of the `FromStr` trait
+/// The `reflect_enum` macro will generate implementations of the `FromStr` and `Display` traits
 /// for the `MAType` enum and register it for conversion between `String` and `MAType`.
 ///
 /// This comes in handy when instantiating a type from a ctor expression from config,
 /// such as:  `"Momentum(SMA, [200, 50, 20], [0.20, 0.30, 0.50])"`.  In this expression
 /// there would be a ctor for the `Momentum` type, expressed as:
 ///
-/// ```
+/// ```ignore
 ///    impl Momentum {
 ///        fn new (ma: MAType, windows: &[i32], weights: &[f64]) -> Self;
 ///    }
@@ -109,23 +216,77 @@ pub fn reflect_impl(_attr: TokenStream, item: TokenStream) -> TokenStream {
 /// when it hands off for object creation.  Due to the conversion mapping between `String` and
 /// `MAType`, the argyment will be converted to map to the appropriate enum.
 ///
+/// Variant names are matched exactly, unless the enum is marked with
+/// `#[reflect_enum(case_insensitive)]`, in which case `"sma"`, `"Sma"`, and `"SMA"` are all
+/// equivalent.  A variant may be given alternative names with `#[reflect(alias = "...")]`,
+/// or renamed with `#[reflect(rename = "...")]` (the name used for display); each name or alias
+/// may refer to a single variant.
+///
+/// An enum marked with `#[reflect_enum(from_repr)]` is also convertible from and to `i32` and
+/// `i64` by discriminant, for example `0` for the first variant (unless otherwise specified).
+/// Conversion by name is preferred where both apply.
+///
 /// Note that when trying to determine which ctor to call, the reflect library will score all
 /// ctos relative to the arguments provided, and tries to find the best fit.   Conversions may
 /// happen, as needed, if the match is not perfect.
 ///
 #[proc_macro_attribute]
-pub fn reflect_enum(_attr: TokenStream, item: TokenStream) -> TokenStream {
-    let input = parse_macro_input!(item as DeriveInput);
+pub fn reflect_enum(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(attr as AttributeArgs);
+    let mut input = parse_macro_input!(item as DeriveInput);
+    let options = match enums::options::parse_enum_options(&args) {
+        Ok(options) => options,
+        Err(error) => return error.to_compile_error().into(),
+    };
 
-    let name = &input.ident;
-    let fromstr = enums::generator::generate_enum_fromstr(&input);
-    let register = enums::generator::generate_enum_registration(&input);
+    let fromstr = match enums::generator::generate_enum_fromstr(&input, &options) {
+        Ok(fromstr) => fromstr,
+        Err(error) => return error.to_compile_error().into(),
+    };
+    let display = match enums::generator::generate_enum_display(&input) {
+        Ok(display) => display,
+        Err(error) => return error.to_compile_error().into(),
+    };
+    let register = enums::generator::generate_enum_registration(&input, &options);
+    enums::generator::strip_variant_attributes(&mut input);
 
     let expanded = quote! {
         #input
-        #(#fromstr)
-        #(#register)
+        #fromstr
+        #display
+        #register
     };
 
     TokenStream::from(expanded)
-}
\ No newline at end of file
+}
+
+/// Attribute to reflect newtypes (single-field tuple structs wrapping a value)
+/// - registration of the `inner` -> `newtype` and `newtype` -> `inner` conversions
+///
+/// # Usage
+/// ```ignore
+///   #[reflect_newtype]
+///   #[derive(Clone)]
+///   struct Bps(f64);
+/// ```
+///
+/// Newtypes lend type safety to parameters, such as `fn new (spread: Bps) -> Self`, while
+/// configuration gives bare values, such as `"Quoter(0.0005)"`: the `f64` argument is converted
+/// to `Bps` by wrapping it.  For a numeric field, integers and floats given in an expression are
+/// converted to the field type first, such that `Quoter(5)` is accepted as well.
+///
+/// The field must implement `Clone`, being cloned out of the argument when wrapped or unwrapped.
+///
+#[proc_macro_attribute]
+pub fn reflect_newtype(_attr: TokenStream, item: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(item as DeriveInput);
+    let register = match newtypes::generator::generate_newtype_registration(&input) {
+        Ok(register) => register,
+        Err(error) => return error.to_compile_error().into(),
+    };
+
+    quote! {
+        #input
+        #register
+    }.into()
+}
@@ -0,0 +1,98 @@
+//! Code generation for newtypes (single-field tuple structs)
+//! - generation of the conversions wrapping and unwrapping the field
+//!
+
+use quote::{quote, format_ident};
+use syn::{DeriveInput, Data, Fields, Type};
+
+
+// Numeric literal types, as produced by the parser (integers as `i32`, or `i64` if out of
+// range, and floats as `f64`)
+const LITERAL_TYPES: [&str; 3] = ["i32", "i64", "f64"];
+
+// Numeric primitive types, convertible from the literal types
+const NUMERIC_TYPES: [&str; 12] = ["i8", "i16", "i32", "i64", "isize", "u8", "u16", "u32", "u64", "usize", "f32", "f64"];
+
+
+/// Generate registration of the conversions of a newtype
+/// - `inner` -> `newtype`, wrapping the field
+/// - `newtype` -> `inner`, unwrapping the field (requires the field to be `Clone`)
+/// - for a numeric field, each numeric literal type (`i32`, `i64`, `f64`) -> `newtype`, converting
+///   the literal to the field type, then wrapping it (scored as the literal to the field type when
+///   looked up, see `Conversions::add_derived`)
+/// - fails if not a single-field tuple struct, or if generic
+pub fn generate_newtype_registration(input: &DeriveInput) -> Result<proc_macro2::TokenStream, syn::Error> {
+    let name = &input.ident;
+    let register_ident = format_ident!("_REGISTER_NEWTYPE_{}", name);
+
+    let inner = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Unnamed(fields) if fields.unnamed.len() == 1 => &fields.unnamed[0].ty,
+            _ => return Err(syn::Error::new_spanned(input,
+                "reflect_newtype requires a single-field tuple struct, such as `struct Bps(f64)`")),
+        },
+        _ => return Err(syn::Error::new_spanned(input,
+            "reflect_newtype requires a single-field tuple struct, such as `struct Bps(f64)`")),
+    };
+    if !input.generics.params.is_empty() {
+        return Err(syn::Error::new_spanned(&input.generics, "reflect_newtype does not support generic types"));
+    }
+
+    let literal_conversions = match primitive_name(inner) {
+        Some(primitive) if NUMERIC_TYPES.contains(&primitive.as_str()) => {
+            let literals = LITERAL_TYPES.iter()
+                .filter(|literal| **literal != primitive)
+                .map(|literal| format_ident!("{}", literal));
+            quote! {
+                #(
+                    ::reflect::Conversions::add_derived(
+                        std::any::TypeId::of::<#literals>(),
+                        std::any::TypeId::of::<#name>(),
+                        200,
+                        (std::any::TypeId::of::<#literals>(), std::any::TypeId::of::<#inner>()),
+                        |v: &Box<dyn std::any::Any>| {
+                            let conversion = ::reflect::Conversions::find((**v).type_id(), std::any::TypeId::of::<#inner>())?;
+                            let x = conversion.convert(v)?.downcast::<#inner>().ok()?;
+                            Some(Box::new(#name(*x)) as Box<dyn std::any::Any>)
+                        }
+                    );
+                )*
+            }
+        }
+        _ => quote! {}
+    };
+
+    Ok(quote! {
+        #[ctor::ctor]
+        #[allow(non_snake_case)]
+        fn #register_ident () {
+            ::reflect::Conversions::add_type_name::<#name>();
+            ::reflect::Conversions::add(
+                std::any::TypeId::of::<#inner>(),
+                std::any::TypeId::of::<#name>(),
+                150,
+                |v: &Box<dyn std::any::Any>| {
+                    v.downcast_ref::<#inner>().map(|x| Box::new(#name(x.clone())) as Box<dyn std::any::Any>)
+                }
+            );
+            ::reflect::Conversions::add(
+                std::any::TypeId::of::<#name>(),
+                std::any::TypeId::of::<#inner>(),
+                150,
+                |v: &Box<dyn std::any::Any>| {
+                    v.downcast_ref::<#name>().map(|x| Box::new(x.0.clone()) as Box<dyn std::any::Any>)
+                }
+            );
+            #literal_conversions
+        }
+    })
+}
+
+
+// Name of a primitive type (a single identifier, such as `f64`), or `None` if not a plain identifier
+fn primitive_name(ty: &Type) -> Option<String> {
+    match ty {
+        Type::Path(type_path) => type_path.path.get_ident().map(|ident| ident.to_string()),
+        _ => None
+    }
+}
@@ -0,0 +1,174 @@
+//! Reflection attributes on functions within a reflected impl block
+//! - `#[reflect(skip)]`: do not reflect the function
+//! - `#[reflect(rename = "name")]`: reflect the function under a different name
+//! - `#[reflect(none_as_error)]`: for a function returning `Option<T>`, report `None` as an error
+//! - `#[reflect(default = "expr")]`: on a parameter, the value of the parameter when no argument
+//!   is given for it (as an expression, such as `"1.0"` or `"[200, 50]"`)
+//!
+//! The doc comment of the function (its `#[doc = "..."]` attributes) is collected as well.
+//!
+//! The attributes are consumed by `reflect_impl`, so must be stripped from the impl block
+//! before it is emitted (there is no `reflect` attribute for the compiler to resolve).
+//!
+
+use syn::{Attribute, FnArg, ImplItem, ItemImpl, Lit, Meta, NestedMeta, Signature};
+
+
+/// Options specified with `#[reflect(...)]` on a function
+#[derive(Default)]
+pub struct ReflectAttributes {
+    pub skip: bool,
+    pub rename: Option<String>,
+    pub none_as_error: bool,
+    pub doc: String,
+}
+
+
+/// Parse `#[reflect(...)]` attributes on a function
+///
+/// # Arguments
+/// * `attrs`: attributes of the function
+///
+/// # Returns
+/// * options specified in the attributes (or defaults if there are none), and the doc comment,
+///   or an error spanning the invalid option
+pub fn parse_reflect_attributes(attrs: &[Attribute]) -> Result<ReflectAttributes, syn::Error> {
+    let mut options = ReflectAttributes {
+        doc: parse_doc(attrs),
+        ..ReflectAttributes::default()
+    };
+
+    for attr in attrs.iter().filter(|attr| is_reflect_attribute(attr)) {
+        let nested = match attr.parse_meta()? {
+            Meta::List(list) => list.nested,
+            meta => return Err(syn::Error::new_spanned(meta, "expected #[reflect(...)]")),
+        };
+
+        for option in nested {
+            match option {
+                NestedMeta::Meta(Meta::Path(path)) if path.is_ident("skip") =>
+                    options.skip = true,
+                NestedMeta::Meta(Meta::Path(path)) if path.is_ident("none_as_error") =>
+                    options.none_as_error = true,
+                NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("rename") => match nv.lit {
+                    Lit::Str(name) => options.rename = Some(name.value()),
+                    lit => return Err(syn::Error::new_spanned(lit, "expected #[reflect(rename = \"name\")]")),
+                },
+                option => return Err(syn::Error::new_spanned(option, "unsupported option in #[reflect(...)]")),
+            }
+        }
+    }
+
+    Ok(options)
+}
+
+
+/// Doc comment given by `#[doc = "..."]` attributes (`///` comments), as lines joined by `\n`
+/// - the space following `///` is removed from each line
+pub fn parse_doc(attrs: &[Attribute]) -> String {
+    let lines: Vec<String> = attrs.iter()
+        .filter(|attr| attr.path.is_ident("doc"))
+        .filter_map(|attr| match attr.parse_meta() {
+            Ok(Meta::NameValue(nv)) => match nv.lit {
+                Lit::Str(line) => Some(line.value()),
+                _ => None,
+            },
+            _ => None,
+        })
+        .map(|line| line.strip_prefix(' ').map(str::to_string).unwrap_or(line))
+        .collect();
+    lines.join("\n").trim().to_string()
+}
+
+
+/// Parse the `#[reflect(default = "...")]` attribute of a parameter
+///
+/// # Arguments
+/// * `attrs`: attributes of the parameter
+///
+/// # Returns
+/// * default value expression, if any, or an error for any other option
+pub fn parse_parameter_default(attrs: &[Attribute]) -> Result<Option<String>, syn::Error> {
+    let mut default = None;
+    for attr in attrs.iter().filter(|attr| is_reflect_attribute(attr)) {
+        let nested = match attr.parse_meta()? {
+            Meta::List(list) => list.nested,
+            meta => return Err(syn::Error::new_spanned(meta, "expected #[reflect(default = \"...\")]")),
+        };
+
+        for option in nested {
+            match option {
+                NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("default") => match nv.lit {
+                    Lit::Str(expr) => default = Some(expr.value()),
+                    lit => return Err(syn::Error::new_spanned(lit, "expected #[reflect(default = \"...\")]")),
+                },
+                option => return Err(syn::Error::new_spanned(option, "unsupported option in #[reflect(...)] on a parameter")),
+            }
+        }
+    }
+    Ok(default)
+}
+
+
+/// Remove `#[reflect(...)]` attributes from the functions of an impl block
+pub fn strip_reflect_attributes(input: &mut ItemImpl) {
+    for item in input.items.iter_mut() {
+        if let ImplItem::Method(method) = item {
+            strip_function_attributes(&mut method.attrs);
+            strip_parameter_attributes(&mut method.sig);
+        }
+    }
+}
+
+
+/// Remove `#[reflect(...)]` attributes from the parameters of a function
+pub fn strip_parameter_attributes(sig: &mut Signature) {
+    for arg in sig.inputs.iter_mut() {
+        if let FnArg::Typed(pat_type) = arg {
+            strip_function_attributes(&mut pat_type.attrs);
+        }
+    }
+}
+
+
+/// Remove `#[reflect(...)]` attributes from the attributes of a function
+pub fn strip_function_attributes(attrs: &mut Vec<Attribute>) {
+    attrs.retain(|attr| !is_reflect_attribute(attr));
+}
+
+
+// determine whether attribute is `#[reflect(...)]`
+fn is_reflect_attribute(attr: &Attribute) -> bool {
+    attr.path.is_ident("reflect")
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use syn::parse_quote;
+
+    #[test]
+    fn test_parse_attributes() {
+        let attrs: Vec<Attribute> = vec![parse_quote!(#[reflect(rename = "alias", none_as_error)])];
+        let options = parse_reflect_attributes(&attrs).expect("failed to parse");
+        assert_eq!(options.rename.as_deref(), Some("alias"));
+        assert!(options.none_as_error);
+        assert!(!options.skip);
+    }
+
+    #[test]
+    fn test_invalid_attributes() {
+        let attrs: Vec<Attribute> = vec![parse_quote!(#[reflect(skip, frobnicate)])];
+        let error = parse_reflect_attributes(&attrs).err().expect("expected error");
+        assert_eq!(error.to_string(), "unsupported option in #[reflect(...)]");
+
+        let attrs: Vec<Attribute> = vec![parse_quote!(#[reflect(rename = 42)])];
+        let error = parse_reflect_attributes(&attrs).err().expect("expected error");
+        assert_eq!(error.to_string(), "expected #[reflect(rename = \"name\")]");
+
+        let attrs: Vec<Attribute> = vec![parse_quote!(#[reflect = "skip"])];
+        let error = parse_reflect_attributes(&attrs).err().expect("expected error");
+        assert_eq!(error.to_string(), "expected #[reflect(...)]");
+    }
+}
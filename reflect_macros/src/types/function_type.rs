@@ -1,10 +1,13 @@
 //! Determine type of function
 //! - Constructor
 //! - Method
+//! - MutMethod (method taking `&mut self`)
+//! - ConsumingMethod (method taking `self`, such as a builder method)
+//! - AsyncMethod (async method taking `&self`)
 //! - Static (static type-level function)
 //!
 
-use syn::{ImplItemMethod, Type, ReturnType};
+use syn::{FnArg, GenericArgument, PathArguments, Signature, Type, ReturnType};
 
 /// Type of function
 /// - our treatment of functions is somewhat different depending on whether is one of the three
@@ -12,27 +15,61 @@ use syn::{ImplItemMethod, Type, ReturnType};
 ///
 /// - for a `Constructor`, the function is like a static function except returns Self / Trait type
 /// - for a `Method`, the function take a reference to &self, requiring an object reference
+/// - for a `MutMethod`, the function takes `&mut self`, requiring a mutable object reference
+/// - for a `ConsumingMethod`, the function takes `self`, requiring ownership of the object
+/// - for an `AsyncMethod`, the function takes `&self` and is async, returning a future
 /// - for a `Static`, the function, like a ctor, does not take a reference to self and does not
 ///   need an object reference
 #[derive(Clone, Copy)]
 pub enum FunctionType {
     Constructor,
     Method,
+    MutMethod,
+    ConsumingMethod,
+    AsyncMethod,
     Static,
 }
 
+/// How a function returns its value
+/// - `Value`: the value is returned directly
+/// - `Result`: the value is returned as `Result<T, E>`; the error is surfaced as a `ReflectError`
+/// - `Option`: the value is returned as `Option<T>`, which is boxed as is, unless `none_as_error`
+///   in which case `T` is boxed and `None` is surfaced as a `ReflectError`
+#[derive(Clone, Copy)]
+pub enum ReturnKind {
+    Value,
+    Result,
+    Option { none_as_error: bool },
+}
+
 /// Determine the type of function given function AST
 ///
 /// # Parameters
-/// * `function`: the AST corresponding to the function
+/// * `sig`: the signature of the function
 ///
 /// # Returns
 /// * the type of function
-pub fn determine_function_type(function: &ImplItemMethod) -> FunctionType {
-    if function.sig.receiver().is_none() {
-        match &function.sig.output {
+pub fn determine_function_type(sig: &Signature) -> FunctionType {
+    if let Some(FnArg::Receiver(receiver)) = sig.receiver() {
+        if receiver.reference.is_none() {
+            return FunctionType::ConsumingMethod;
+        }
+        if receiver.mutability.is_some() {
+            return FunctionType::MutMethod;
+        }
+        if sig.asyncness.is_some() {
+            return FunctionType::AsyncMethod;
+        }
+    }
+
+    if sig.receiver().is_none() {
+        match &sig.output {
             ReturnType::Type(_, ty) =>
-                if is_self_or_impl_trait(ty) { FunctionType::Constructor } else { FunctionType::Static },
+                if is_self_or_impl_trait(ty) || result_ok_type(ty).is_some_and(is_self_or_impl_trait) {
+                    FunctionType::Constructor
+                } else {
+                    FunctionType::Static
+                },
             ReturnType::Default =>
                 FunctionType::Static,
         }
@@ -52,4 +89,63 @@ pub fn is_self_or_impl_trait(ty: &Type) -> bool {
         Type::ImplTrait(_) => true,
         _ => false,
     }
-}
\ No newline at end of file
+}
+
+/// Determine whether the return type is a `Result<T, E>`
+/// - the error is surfaced as the error of the reflected call, rather than boxed with the value
+pub fn is_result_type(ty: &Type) -> bool {
+    result_ok_type(ty).is_some()
+}
+
+/// Get the `T` of a `Result<T, E>` return type (or `None` if not a `Result`)
+pub fn result_ok_type(ty: &Type) -> Option<&Type> {
+    first_generic_type(ty, "Result")
+}
+
+/// Get the `T` of an `Option<T>` return type (or `None` if not an `Option`)
+pub fn option_some_type(ty: &Type) -> Option<&Type> {
+    first_generic_type(ty, "Option")
+}
+
+/// Get the `T` of a `VecDeque<T>`, `HashSet<T>`, or `BTreeSet<T>` parameter type (or `None` if
+/// not one of these collections)
+pub fn collection_element_type(ty: &Type) -> Option<&Type> {
+    ["VecDeque", "HashSet", "BTreeSet"].iter().find_map(|name| first_generic_type(ty, name))
+}
+
+/// Determine whether a parameter type is an object type (such as `Point` or `Buffer<f64>`),
+/// rather than a primitive, string, or standard container passed by value
+pub fn is_object_type(ty: &Type) -> bool {
+    const STANDARD_TYPES: [&str; 23] = [
+        "i8", "i16", "i32", "i64", "i128", "isize", "u8", "u16", "u32", "u64", "u128", "usize",
+        "f32", "f64", "bool", "char", "String", "Vec", "HashMap", "Option", "Box", "Rc", "Arc"];
+
+    match ty {
+        Type::Path(type_path) => match type_path.path.segments.last() {
+            Some(segment) => !STANDARD_TYPES.contains(&segment.ident.to_string().as_str()) &&
+                collection_element_type(ty).is_none(),
+            None => false
+        },
+        _ => false
+    }
+}
+
+// Get the first type argument of a generic type with the given name, for example `T` in
+// `Result<T, E>`
+fn first_generic_type<'a>(ty: &'a Type, name: &str) -> Option<&'a Type> {
+    let segment = match ty {
+        Type::Path(type_path) => type_path.path.segments.last()?,
+        _ => return None,
+    };
+    if segment.ident != name {
+        return None;
+    }
+
+    match &segment.arguments {
+        PathArguments::AngleBracketed(generics) => match generics.args.first() {
+            Some(GenericArgument::Type(inner)) => Some(inner),
+            _ => None,
+        },
+        _ => None,
+    }
+}
@@ -7,10 +7,11 @@
 
 use proc_macro2::Ident;
 use quote::{quote, format_ident};
-use syn::{Type, TypePath, TypeReference};
+use syn::{Type, TypeArray, TypePath, TypeReference};
+use syn::ext::IdentExt;
 
 use crate::types::parser::{ParsedType, ParsedFunction};
-use crate::types::function_type::FunctionType;
+use crate::types::function_type::{FunctionType, ReturnKind, collection_element_type, is_object_type, option_some_type};
 use crate::utilities::{ident_camel_case};
 
 
@@ -25,13 +26,47 @@ use crate::utilities::{ident_camel_case};
 /// # Returns
 ///  * vector of token streams representing the generated code
 pub fn generate_reflection_for_type(data: &ParsedType) -> Vec<proc_macro2::TokenStream> {
-    data.functions.iter().map(|method| {
+    let mut generated: Vec<proc_macro2::TokenStream> = data.functions.iter().map(|method| {
         match method.function_type {
             FunctionType::Constructor => generate_constructor(data, method),
-            FunctionType::Method => generate_method(data, method),
+            FunctionType::Method | FunctionType::MutMethod | FunctionType::ConsumingMethod | FunctionType::AsyncMethod =>
+                generate_method(data, method),
             FunctionType::Static => generate_static(data, method),
         }
-    }).collect()
+    }).collect();
+
+    if data.clone || data.debug || data.eq {
+        generated.push(generate_operations(data));
+    }
+    generated
+}
+
+/// Generates registration of the operations on objects of the type requested of `reflect_impl`
+/// - `clone`: the type's `Clone` implementation
+/// - `debug`: the type's `Debug` implementation
+/// - `eq`: the type's `PartialEq` implementation
+fn generate_operations(data: &ParsedType) -> proc_macro2::TokenStream {
+    let type_name = &data.type_name;
+    let naming = generate_type_naming(data);
+    let register_ident = match &data.trait_name {
+        Some(tname) => format_ident!("_REGISTER_{}{}Operations", data.short_type_name, tname),
+        None => format_ident!("_REGISTER_{}Operations", data.short_type_name)
+    };
+
+    let clone = data.clone.then(|| quote! { ::reflect::register_clone::<#type_name>(); });
+    let debug = data.debug.then(|| quote! { ::reflect::register_debug::<#type_name>(); });
+    let eq = data.eq.then(|| quote! { ::reflect::register_eq::<#type_name>(); });
+
+    quote! {
+        #[ctor::ctor]
+        #[allow(non_snake_case)]
+        fn #register_ident() {
+            #naming
+            #clone
+            #debug
+            #eq
+        }
+    }
 }
 
 /// Generates code for a constructor and registration
@@ -40,17 +75,50 @@ pub fn generate_reflection_for_type(data: &ParsedType) -> Vec<proc_macro2::Token
 /// - registration
 fn generate_constructor(data: &ParsedType, function: &ParsedFunction) -> proc_macro2::TokenStream {
     let short_type_name = &data.short_type_name;
+    let type_name = &data.type_name;
     let method_name = &function.name;
-    let ctor_name = format_ident!("{}Constructor", ident_camel_case(method_name));
+    let implementor = generate_implementor(data);
+    let naming = generate_type_naming(data);
+    let ctor_name = match &data.trait_name {
+        Some(tname) => format_ident!("{}{}{}Constructor", short_type_name, ident_camel_case(method_name), tname),
+        None => format_ident!("{}{}Constructor", short_type_name, ident_camel_case(method_name))
+    };
     let register_ident = format_ident!("_REGISTER_{}", ctor_name);
 
     let (arg_conversions, arg_names, arg_types) = generate_arg_details(&function.args);
-    let return_type = &function.return_type;
+    let doc = &function.doc;
+    let param_names = generate_param_names(&function.args);
+    let param_defaults = generate_param_defaults(&function.defaults);
+    let return_value = generate_return_value(function.return_kind);
+    let returned = generate_returned_type(&implementor, function);
+    let boxed_type = generate_boxed_type(function);
+    let create_owned = generate_owned_arg_conversions(&function.args).map(|owned_conversions| quote! {
+        fn create_owned(&self, args: Vec<Box<dyn std::any::Any>>) -> Result<Box<dyn std::any::Any>, ::reflect::ReflectError> {
+            #owned_conversions
+            let result = #implementor::#method_name(#(#arg_names),*);
+            #return_value
+        }
+    });
+
+    // with auto_convert, a ctor taking a single object converts the object to the type, unless
+    // the object is of the type itself
+    let auto_conversion = match (data.auto_convert, function.args.as_slice()) {
+        (true, [(_, arg_type)]) if is_object_type(arg_type) => quote! {
+            if std::any::TypeId::of::<#arg_type>() != return_type {
+                let convert = ctor.clone();
+                ::reflect::Conversions::add_auto(std::any::TypeId::of::<#arg_type>(), return_type,
+                    move |v: &Box<dyn std::any::Any>| ::reflect::Constructor::create(&convert, &[v.as_ref()]).ok());
+            }
+        },
+        _ => quote! {}
+    };
 
     quote! {
         #[derive(Clone)]
         struct #ctor_name {
-            _arg_types: Vec<std::any::TypeId>
+            _arg_types: Vec<std::any::TypeId>,
+            _return_type: std::any::TypeId,
+            _return_type_name: String
         }
 
         impl ::reflect::Function for #ctor_name {
@@ -63,58 +131,218 @@ fn generate_constructor(data: &ParsedType, function: &ParsedFunction) -> proc_ma
             }
 
             fn return_type(&self) -> std::any::TypeId {
-                std::any::TypeId::of::<#return_type>()
+                self._return_type
+            }
+
+            #boxed_type
+
+            fn return_type_name(&self) -> &str {
+                &self._return_type_name
+            }
+
+            fn doc(&self) -> &str {
+                #doc
+            }
+
+            fn arg_names(&self) -> &[&str] {
+                &[#(#param_names),*]
+            }
+
+            fn arg_defaults(&self) -> &[Option<&str>] {
+                &[#(#param_defaults),*]
             }
         }
 
         impl ::reflect::Constructor for #ctor_name {
-            fn create(&self, args: &[Box<dyn std::any::Any>]) -> Result<Box<dyn std::any::Any>, String> {
+            fn create(&self, args: &[&dyn std::any::Any]) -> Result<Box<dyn std::any::Any>, ::reflect::ReflectError> {
                 #(#arg_conversions)*
-                let result = #short_type_name::#method_name(#(#arg_names),*);
-                Ok(Box::new(result))
+                let result = #implementor::#method_name(#(#arg_names),*);
+                #return_value
             }
 
-            fn clone_boxed(&self) -> Box<dyn Constructor> {
+            #create_owned
+
+            fn clone_boxed(&self) -> Box<dyn ::reflect::Constructor> {
                 Box::new(self.clone())
             }
         }
 
         #[ctor::ctor]
+        #[allow(non_snake_case)]
         fn #register_ident() {
-            ::reflect::register_constructor::<#short_type_name>(Box::new(#ctor_name {
-                _arg_types: vec![#(#arg_types),*]
-            }));
+            let (return_type, return_type_name) = #returned;
+            #naming
+            let ctor = #ctor_name {
+                _arg_types: vec![#(#arg_types),*],
+                _return_type: return_type,
+                _return_type_name: return_type_name
+            };
+            #auto_conversion
+            ::reflect::register_constructor::<#type_name>(Box::new(ctor));
         }
     }
 }
 
+/// Generate code yielding the `TypeId` and readable name of the type a ctor returns, registering
+/// the name
+/// - the concrete type behind an `impl Trait` return cannot be named, so is inferred from a call
+///   of the ctor within a closure that is never called
+/// - the object created is boxed as its concrete type, so methods reflected for that type can be
+///   called on it (whereas a `Box<dyn Trait>` return is not a ctor, and the trait object cannot be
+///   recovered from `Any`)
+fn generate_returned_type(implementor: &proc_macro2::TokenStream, function: &ParsedFunction) -> proc_macro2::TokenStream {
+    let return_type = &function.return_type;
+    if !matches!(return_type, Type::ImplTrait(_)) {
+        return quote! {{
+            ::reflect::Conversions::add_type_name::<#return_type>();
+            (std::any::TypeId::of::<#return_type>(), ::reflect::type_shortname::<#return_type>())
+        }};
+    }
+
+    let method_name = &function.name;
+    let args = function.args.iter().map(|_| quote! { unreachable!() });
+    let unwrap = match function.return_kind {
+        ReturnKind::Result => quote! { .ok().unwrap() },
+        _ => quote! {}
+    };
+    quote! {{
+        fn returned<R: 'static> (_: impl FnOnce() -> R) -> (std::any::TypeId, String) {
+            ::reflect::Conversions::add_type_name::<R>();
+            (std::any::TypeId::of::<R>(), ::reflect::type_shortname::<R>())
+        }
+        #[allow(unreachable_code)]
+        returned(|| #implementor::#method_name(#(#args),*) #unwrap)
+    }}
+}
+
 /// Generates code for a method and registration
 /// - implenentation of `Function` trait
-/// - implenentation of `Method` trait
+/// - implenentation of `Method` trait (`call_mut` for methods taking `&mut self`,
+///   `call_consuming` for methods taking `self`)
 /// - registration
 fn generate_method(data: &ParsedType, function: &ParsedFunction) -> proc_macro2::TokenStream {
     let short_type_name = &data.short_type_name;
+    let type_name = &data.type_name;
     let type_path = &data.type_path;
     let method_name = &function.name;
+    let reflected_name = &function.reflected_name;
     let trait_name = &data.trait_name;
+    let implementor = generate_implementor(data);
+    let naming = generate_type_naming(data);
 
     let method_impl_name = match trait_name {
-        Some(tname) => format_ident!("{}{}Method", ident_camel_case(method_name), tname),
-        None => format_ident!("{}Method", ident_camel_case(method_name))
-    };
-    let register_ident = match trait_name {
-        Some(tname) => format_ident!("_REGISTER_{}{}", method_impl_name, tname),
-        None => format_ident!("_REGISTER_{}", method_impl_name)
+        Some(tname) => format_ident!("{}{}{}Method", short_type_name, ident_camel_case(method_name), tname),
+        None => format_ident!("{}{}Method", short_type_name, ident_camel_case(method_name))
     };
+    let register_ident = format_ident!("_REGISTER_{}", method_impl_name);
 
     let (arg_conversions, arg_names, arg_types) = generate_arg_details(&function.args);
     let return_type = &function.return_type;
+    let doc = &function.doc;
+    let param_names = generate_param_names(&function.args);
+    let boxed_type = generate_boxed_type(function);
+    let param_defaults = generate_param_defaults(&function.defaults);
+    let return_value = generate_return_value(function.return_kind);
+
+    let call_impl = match function.function_type {
+        FunctionType::MutMethod => quote! {
+            fn call(&self, _obj: &Box<dyn std::any::Any>, _args: &[&dyn std::any::Any]) -> Result<Box<dyn std::any::Any>, ::reflect::ReflectError> {
+                Err(::reflect::ReflectError::MutableReceiver { method: self._name.clone(), required: true })
+            }
+
+            fn call_mut(&self, obj: &mut Box<dyn std::any::Any>, args: &[&dyn std::any::Any]) -> Result<Box<dyn std::any::Any>, ::reflect::ReflectError> {
+                #(#arg_conversions)*
+                let realobj = match obj.downcast_mut::<#type_path>() {
+                    Some(realobj) => realobj,
+                    None => return Err(::reflect::ReflectError::WrongReceiverType { expected: ::reflect::registered_name::<#type_path>() }),
+                };
+                let result = #implementor::#method_name(realobj, #(#arg_names),*);
+                #return_value
+            }
+
+            fn is_mut(&self) -> bool {
+                true
+            }
+        },
+        FunctionType::ConsumingMethod => quote! {
+            fn call(&self, _obj: &Box<dyn std::any::Any>, _args: &[&dyn std::any::Any]) -> Result<Box<dyn std::any::Any>, ::reflect::ReflectError> {
+                Err(::reflect::ReflectError::ConsumingReceiver { method: self._name.clone() })
+            }
+
+            fn call_mut(&self, _obj: &mut Box<dyn std::any::Any>, _args: &[&dyn std::any::Any]) -> Result<Box<dyn std::any::Any>, ::reflect::ReflectError> {
+                Err(::reflect::ReflectError::ConsumingReceiver { method: self._name.clone() })
+            }
+
+            fn call_consuming(&self, obj: Box<dyn std::any::Any>, args: &[&dyn std::any::Any]) -> Result<Box<dyn std::any::Any>, ::reflect::ReflectError> {
+                #(#arg_conversions)*
+                let realobj = match obj.downcast::<#type_path>() {
+                    Ok(realobj) => *realobj,
+                    Err(_) => return Err(::reflect::ReflectError::WrongReceiverType { expected: ::reflect::registered_name::<#type_path>() }),
+                };
+                let result = #implementor::#method_name(realobj, #(#arg_names),*);
+                #return_value
+            }
+
+            fn is_consuming(&self) -> bool {
+                true
+            }
+        },
+        FunctionType::AsyncMethod => quote! {
+            fn call(&self, _obj: &Box<dyn std::any::Any>, _args: &[&dyn std::any::Any]) -> Result<Box<dyn std::any::Any>, ::reflect::ReflectError> {
+                Err(::reflect::ReflectError::AsyncReceiver { method: self._name.clone() })
+            }
+
+            fn call_async<'a>(&'a self, obj: &'a Box<dyn std::any::Any>, args: &'a [&'a dyn std::any::Any]) -> ::reflect::AsyncResult<'a> {
+                Box::pin(async move {
+                    #(#arg_conversions)*
+                    let realobj = match obj.downcast_ref::<#type_path>() {
+                        Some(realobj) => realobj,
+                        None => return Err(::reflect::ReflectError::WrongReceiverType { expected: ::reflect::registered_name::<#type_path>() }),
+                    };
+                    let result = #implementor::#method_name(realobj, #(#arg_names),*).await;
+                    let value: Result<Box<dyn std::any::Any>, ::reflect::ReflectError> = #return_value;
+                    value
+                })
+            }
+
+            fn is_async(&self) -> bool {
+                true
+            }
+        },
+        _ => {
+            let call_owned = generate_owned_arg_conversions(&function.args).map(|owned_conversions| quote! {
+                fn call_owned(&self, obj: &Box<dyn std::any::Any>, args: Vec<Box<dyn std::any::Any>>) -> Result<Box<dyn std::any::Any>, ::reflect::ReflectError> {
+                    #owned_conversions
+                    let realobj = match obj.downcast_ref::<#type_path>() {
+                        Some(realobj) => realobj,
+                        None => return Err(::reflect::ReflectError::WrongReceiverType { expected: ::reflect::registered_name::<#type_path>() }),
+                    };
+                    let result = #implementor::#method_name(realobj, #(#arg_names),*);
+                    #return_value
+                }
+            });
+            quote! {
+                fn call(&self, obj: &Box<dyn std::any::Any>, args: &[&dyn std::any::Any]) -> Result<Box<dyn std::any::Any>, ::reflect::ReflectError> {
+                    #(#arg_conversions)*
+                    let realobj = match obj.downcast_ref::<#type_path>() {
+                        Some(realobj) => realobj,
+                        None => return Err(::reflect::ReflectError::WrongReceiverType { expected: ::reflect::registered_name::<#type_path>() }),
+                    };
+                    let result = #implementor::#method_name(realobj, #(#arg_names),*);
+                    #return_value
+                }
+
+                #call_owned
+            }
+        }
+    };
 
     quote! {
         #[derive(Clone)]
         struct #method_impl_name {
             _name: String,
-            _arg_types: Vec<std::any::TypeId>
+            _arg_types: Vec<std::any::TypeId>,
+            _return_type_name: String
         }
 
         impl ::reflect::Function for #method_impl_name {
@@ -129,26 +357,43 @@ fn generate_method(data: &ParsedType, function: &ParsedFunction) -> proc_macro2:
             fn return_type(&self) -> std::any::TypeId {
                 std::any::TypeId::of::<#return_type>()
             }
+
+            #boxed_type
+
+            fn return_type_name(&self) -> &str {
+                &self._return_type_name
+            }
+
+            fn doc(&self) -> &str {
+                #doc
+            }
+
+            fn arg_names(&self) -> &[&str] {
+                &[#(#param_names),*]
+            }
+
+            fn arg_defaults(&self) -> &[Option<&str>] {
+                &[#(#param_defaults),*]
+            }
         }
 
         impl ::reflect::Method for #method_impl_name {
-            fn call(&self, obj: &Box<dyn std::any::Any>, args: &[Box<dyn std::any::Any>]) -> Result<Box<dyn std::any::Any>, String> {
-                #(#arg_conversions)*
-                let realobj = obj.downcast_ref::<#type_path>().expect("Failed to downcast to correct type");
-                let result = realobj.#method_name(#(#arg_names),*);
-                Ok(Box::new(result))
-            }
+            #call_impl
 
-            fn clone_boxed(&self) -> Box<dyn Method> {
+            fn clone_boxed(&self) -> Box<dyn ::reflect::Method> {
                 Box::new(self.clone())
             }
         }
 
         #[ctor::ctor]
+        #[allow(non_snake_case)]
         fn #register_ident() {
-            ::reflect::register_method::<#short_type_name>(Box::new(#method_impl_name {
-                _name: stringify!(#method_name).to_string(),
-                _arg_types: vec![#(#arg_types),*]
+            ::reflect::Conversions::add_type_name::<#return_type>();
+            #naming
+            ::reflect::register_method::<#type_name>(Box::new(#method_impl_name {
+                _name: #reflected_name.to_string(),
+                _arg_types: vec![#(#arg_types),*],
+                _return_type_name: ::reflect::type_shortname::<#return_type>()
             }));
         }
     }
@@ -160,27 +405,34 @@ fn generate_method(data: &ParsedType, function: &ParsedFunction) -> proc_macro2:
 /// - registration
 fn generate_static(data: &ParsedType, method: &ParsedFunction) -> proc_macro2::TokenStream {
     let short_type_name = &data.short_type_name;
+    let type_name = &data.type_name;
     let method_name = &method.name;
+    let reflected_name = &method.reflected_name;
     let trait_name = &data.trait_name;
+    let implementor = generate_implementor(data);
+    let naming = generate_type_naming(data);
 
     let fun_impl_name = match trait_name {
-        Some(tname) => format_ident!("{}{}Method", ident_camel_case(method_name), tname),
-        None => format_ident!("{}Method", ident_camel_case(method_name))
-    };
-    let register_ident = match trait_name {
-        Some(tname) => format_ident!("_REGISTER_{}{}", method_name, tname),
-        None => format_ident!("_REGISTER_{}", method_name)
+        Some(tname) => format_ident!("{}{}{}Function", short_type_name, ident_camel_case(method_name), tname),
+        None => format_ident!("{}{}Function", short_type_name, ident_camel_case(method_name))
     };
+    let register_ident = format_ident!("_REGISTER_{}", fun_impl_name);
 
 
     let (arg_conversions, arg_names, arg_types) = generate_arg_details(&method.args);
     let return_type = &method.return_type;
+    let doc = &method.doc;
+    let param_names = generate_param_names(&method.args);
+    let boxed_type = generate_boxed_type(method);
+    let param_defaults = generate_param_defaults(&method.defaults);
+    let return_value = generate_return_value(method.return_kind);
 
     quote! {
         #[derive(Clone)]
         struct #fun_impl_name {
             _name: String,
-            _arg_types: Vec<std::any::TypeId>
+            _arg_types: Vec<std::any::TypeId>,
+            _return_type_name: String
         }
 
         impl ::reflect::Function for #fun_impl_name {
@@ -195,35 +447,137 @@ fn generate_static(data: &ParsedType, method: &ParsedFunction) -> proc_macro2::T
             fn return_type(&self) -> std::any::TypeId {
                 std::any::TypeId::of::<#return_type>()
             }
+
+            #boxed_type
+
+            fn return_type_name(&self) -> &str {
+                &self._return_type_name
+            }
+
+            fn doc(&self) -> &str {
+                #doc
+            }
+
+            fn arg_names(&self) -> &[&str] {
+                &[#(#param_names),*]
+            }
+
+            fn arg_defaults(&self) -> &[Option<&str>] {
+                &[#(#param_defaults),*]
+            }
         }
 
         impl ::reflect::StaticFunction for #fun_impl_name {
-            fn call(&self, args: &[Box<dyn std::any::Any>]) -> Result<Box<dyn std::any::Any>, String> {
+            fn call(&self, args: &[&dyn std::any::Any]) -> Result<Box<dyn std::any::Any>, ::reflect::ReflectError> {
                 #(#arg_conversions)*
-                let result = #short_type_name::#method_name(#(#arg_names),*);
-                Ok(Box::new(result))
+                let result = #implementor::#method_name(#(#arg_names),*);
+                #return_value
             }
 
-            fn clone_boxed(&self) -> Box<dyn StaticFunction> {
+            fn clone_boxed(&self) -> Box<dyn ::reflect::StaticFunction> {
                 Box::new(self.clone())
             }
         }
 
         #[ctor::ctor]
+        #[allow(non_snake_case)]
         fn #register_ident() {
-            ::reflect::register_static::<#short_type_name>(Box::new(#fun_impl_name {
-                _name: stringify!(#method_name).to_string(),
-                _arg_types: vec![#(#arg_types),*]
+            ::reflect::Conversions::add_type_name::<#return_type>();
+            #naming
+            ::reflect::register_function::<#type_name>(Box::new(#fun_impl_name {
+                _name: #reflected_name.to_string(),
+                _arg_types: vec![#(#arg_types),*],
+                _return_type_name: ::reflect::type_shortname::<#return_type>()
             }));
         }
     }
 }
 
+/// Generate the path through which functions of the impl block are called
+/// - `<Type>` for an inherent impl
+/// - `<Type as Trait>` for a trait impl, such that the trait's function is called even where an
+///   inherent function has the same name, or where the trait is not in scope
+fn generate_implementor(data: &ParsedType) -> proc_macro2::TokenStream {
+    let type_name = &data.type_name;
+    match &data.trait_path {
+        Some(path) => quote! { <#type_name as #path> },
+        None => quote! { <#type_name> },
+    }
+}
+
+/// Generate code registering the type under the name given by the `rename`, `namespace`, and
+/// `qualified` options (if any)
+/// - each registration function names the type, as their order at startup is not defined
+fn generate_type_naming(data: &ParsedType) -> proc_macro2::TokenStream {
+    let type_name = &data.type_name;
+    if data.rename.is_none() && data.namespace.is_none() && !data.qualified {
+        return quote! {};
+    }
+
+    let name = match (&data.rename, data.qualified) {
+        (Some(name), _) => quote! { #name.to_string() },
+        (None, true) => quote! { ::reflect::qualified_name::<#type_name>() },
+        (None, false) => quote! { ::reflect::type_shortname::<#type_name>() },
+    };
+    match &data.namespace {
+        Some(namespace) => quote! {
+            ::reflect::register_type_name::<#type_name>(&format!("{}::{}", #namespace, #name));
+        },
+        None => quote! {
+            ::reflect::register_type_name::<#type_name>(&#name);
+        },
+    }
+}
+
+/// Generate code boxing the `result` of a call
+/// - a `Result` is unwrapped, with its error surfaced as `ReflectError::CallFailed`
+/// - an `Option` is boxed as is, or if `none_as_error`, unwrapped with `None` surfaced as
+///   `ReflectError::CallFailed`
+pub fn generate_return_value(kind: ReturnKind) -> proc_macro2::TokenStream {
+    match kind {
+        ReturnKind::Value => quote! {
+            Ok(Box::new(result))
+        },
+        ReturnKind::Result => quote! {
+            match result {
+                Ok(value) => Ok(Box::new(value)),
+                Err(e) => Err(::reflect::ReflectError::CallFailed(e.to_string())),
+            }
+        },
+        ReturnKind::Option { none_as_error: false } => quote! {
+            Ok(Box::new(result))
+        },
+        ReturnKind::Option { none_as_error: true } => quote! {
+            match result {
+                Some(value) => Ok(Box::new(value)),
+                None => Err(::reflect::ReflectError::CallFailed(format!("{} returned None", ::reflect::Function::name(self)))),
+            }
+        },
+    }
+}
+
+/// Generate `Function::boxed_type` where the value boxed by a call is not of the return type
+/// - an `Option<T>` (unless `none_as_error`) is boxed as is, whereas the return type is `T`
+/// - none if the `Option` wraps an `impl Trait`, which cannot be named
+pub fn generate_boxed_type(function: &ParsedFunction) -> proc_macro2::TokenStream {
+    let return_type = &function.return_type;
+    match function.return_kind {
+        ReturnKind::Option { none_as_error: false } if !matches!(return_type, Type::ImplTrait(_)) => quote! {
+            fn boxed_type(&self) -> std::any::TypeId {
+                std::any::TypeId::of::<Option<#return_type>>()
+            }
+        },
+        _ => quote! {}
+    }
+}
+
 /// Generate code for:
 /// - argument conversions (from `Box<dyn Any>` to specific type for argument dispatch)
 /// - argument namees
-/// - argument type names
-fn generate_arg_details(args: &[(syn::Ident, syn::Type)]) -> (Vec<proc_macro2::TokenStream>, Vec<proc_macro2::TokenStream>, Vec<proc_macro2::TokenStream>) {
+/// - argument types (registering the readable name of each type, and for `Option<T>`, 2- or
+///   3-tuples, arrays, and collections (`VecDeque<T>`, `HashSet<T>`, `BTreeSet<T>`) the
+///   conversions to it)
+pub fn generate_arg_details(args: &[(syn::Ident, syn::Type)]) -> (Vec<proc_macro2::TokenStream>, Vec<proc_macro2::TokenStream>, Vec<proc_macro2::TokenStream>) {
     let arg_conversions = args.iter().enumerate().map(|(i, (name, ty))| {
         generate_arg_conversion(i, name, ty)
     }).collect();
@@ -231,13 +585,100 @@ fn generate_arg_details(args: &[(syn::Ident, syn::Type)]) -> (Vec<proc_macro2::T
     let arg_names = args.iter().map(|(name, _)| quote! { #name }).collect();
 
     let arg_types = args.iter()
-        .map(|(_, ty)| quote! { std::any::TypeId::of::<#ty>() })
+        .map(|(_, ty)| {
+            // optional parameters accept the value or null
+            let optional = option_some_type(ty).map(|inner| quote! {
+                ::reflect::Conversions::add_optional::<#inner>();
+            });
+            // collection parameters accept lists
+            let collection = collection_element_type(ty).map(|element| quote! {
+                ::reflect::Conversions::add_collection::<#element, #ty>();
+            });
+            // tuple parameters accept tuple literals
+            let tuple = match ty {
+                Type::Tuple(tuple) if tuple.elems.len() == 2 => {
+                    let elems = tuple.elems.iter();
+                    Some(quote! { ::reflect::Conversions::add_tuple::<#(#elems),*>(); })
+                }
+                Type::Tuple(tuple) if tuple.elems.len() == 3 => {
+                    let elems = tuple.elems.iter();
+                    Some(quote! { ::reflect::Conversions::add_tuple3::<#(#elems),*>(); })
+                }
+                // array parameters accept list literals
+                Type::Array(array) => {
+                    let (elem, len) = (&array.elem, &array.len);
+                    Some(quote! { ::reflect::Conversions::add_array::<#elem, { #len }>(); })
+                }
+                _ => None
+            };
+            quote! {
+                {
+                    ::reflect::Conversions::add_type_name::<#ty>();
+                    #optional
+                    #collection
+                    #tuple
+                    std::any::TypeId::of::<#ty>()
+                }
+            }
+        })
         .collect();
 
     (arg_conversions, arg_names, arg_types)
 }
 
 
+/// Generate code for argument conversions from owned arguments (`Vec<Box<dyn Any>>`)
+/// - arguments for container parameters (`Vec<T>`, `HashMap<K, V>`, and collections) are moved
+///   out of the arguments rather than cloned; others are converted as by `generate_arg_details`
+///   from a view of the remaining arguments
+/// - None if there is no container parameter, as owned arguments are then no different
+pub fn generate_owned_arg_conversions(args: &[(syn::Ident, syn::Type)]) -> Option<proc_macro2::TokenStream> {
+    if !args.iter().any(|(_, ty)| is_container(ty)) {
+        return None;
+    }
+
+    let moved = args.iter().enumerate()
+        .filter(|(_, (_, ty))| is_container(ty))
+        .map(|(i, (name, ty))| quote! {
+            let #name = match owned.get_mut(#i).and_then(Option::take).map(|arg| arg.downcast::<#ty>()) {
+                Some(Ok(value)) => *value,
+                _ => return Err(::reflect::ReflectError::InvalidArgument { index: #i }),
+            };
+        });
+    let borrowed = args.iter().enumerate()
+        .filter(|(_, (_, ty))| !is_container(ty))
+        .map(|(i, (name, ty))| generate_arg_conversion(i, name, ty));
+
+    Some(quote! {
+        let mut owned: Vec<Option<Box<dyn std::any::Any>>> = args.into_iter().map(Some).collect();
+        #(#moved)*
+        let remaining: Vec<&dyn std::any::Any> = owned.iter()
+            .map(|arg| match arg {
+                Some(arg) => arg.as_ref(),
+                None => &() as &dyn std::any::Any
+            })
+            .collect();
+        let args = remaining.as_slice();
+        #(#borrowed)*
+    })
+}
+
+
+/// Generate the names of the parameters (as registered, for calls with arguments by name)
+pub fn generate_param_names(args: &[(syn::Ident, syn::Type)]) -> Vec<String> {
+    args.iter().map(|(name, _)| name.unraw().to_string()).collect()
+}
+
+
+/// Generate the default values of the parameters (as expressions, evaluated when called)
+pub fn generate_param_defaults(defaults: &[Option<String>]) -> Vec<proc_macro2::TokenStream> {
+    defaults.iter().map(|default| match default {
+        Some(expr) => quote! { Some(#expr) },
+        None => quote! { None }
+    }).collect()
+}
+
+
 /// Handle argument dereferencing dependent on type
 ///
 /// # How this works
@@ -251,9 +692,18 @@ fn generate_arg_details(args: &[(syn::Ident, syn::Type)]) -> (Vec<proc_macro2::T
 ///   target function parameter is a slice `&[T]` and if the incoming value is a `Vec[t]`
 ///   will get a slice on the `Vec[T]` argument
 ///
+/// - likewise, `&str` parameters borrow a `String` (or `&String`) argument
+///
+/// - `Option<T>` parameters accept the option, the value itself (as `Some`), or `Null` (as `None`)
+///
+/// - array `[T; N]` parameters accept the array, or a `Vec<T>` of `N` elements
+///
 /// - aside from slices, there are references, primitive types, and struct based types.  There
 ///   may be some special handling for each in properly dereferencing
 ///
+/// - struct based types passed by value (for example an object constructed by a nested ctor
+///   expression) are cloned out of the argument, so must implement `Clone`
+///
 fn generate_arg_conversion(i: usize, name: &Ident, parameter_type: &Type) -> proc_macro2::TokenStream {
     match parameter_type {
         Type::Reference(TypeReference { elem, .. }) => {
@@ -267,10 +717,28 @@ fn generate_arg_conversion(i: usize, name: &Ident, parameter_type: &Type) -> pro
                             } else if let Some(slice) = arg.downcast_ref::<#parameter_type>() {
                                 *slice
                             } else {
-                                return Err(format!("Invalid argument type for parameter {}", #i));
+                                return Err(::reflect::ReflectError::InvalidArgument { index: #i });
+                            }
+                        },
+                        None => return Err(::reflect::ReflectError::InvalidArgument { index: #i }),
+                    };
+                }
+            } else if is_str(elem) {
+                // Handle &str, borrowing a String argument
+                quote! {
+                    let #name = match args.get(#i) {
+                        Some(arg) => {
+                            if let Some(s) = arg.downcast_ref::<String>() {
+                                s.as_str()
+                            } else if let Some(s) = arg.downcast_ref::<&String>() {
+                                s.as_str()
+                            } else if let Some(s) = arg.downcast_ref::<&str>() {
+                                *s
+                            } else {
+                                return Err(::reflect::ReflectError::InvalidArgument { index: #i });
                             }
                         },
-                        None => return Err(format!("Missing argument for parameter {}", #i)),
+                        None => return Err(::reflect::ReflectError::InvalidArgument { index: #i }),
                     };
                 }
             } else {
@@ -278,44 +746,104 @@ fn generate_arg_conversion(i: usize, name: &Ident, parameter_type: &Type) -> pro
                 quote! {
                     let #name = match args.get(#i).and_then(|arg| arg.downcast_ref::<#parameter_type>()) {
                         Some(value) => *value,
-                        None => return Err(format!("Invalid argument type for parameter {}", #i)),
+                        None => return Err(::reflect::ReflectError::InvalidArgument { index: #i }),
                     };
                 }
             }
         },
-        Type::Path(TypePath { path, .. }) => {
-            if path.segments.last().map_or(false, |seg| seg.ident == "Vec") {
-                // Handle Vec<T>
+        Type::Array(TypeArray { elem, .. }) => {
+            // Handle [T; N], given as the array or a Vec<T> of N elements (requires Clone)
+            quote! {
+                let #name: #parameter_type = match args.get(#i) {
+                    Some(arg) => {
+                        if let Some(array) = arg.downcast_ref::<#parameter_type>() {
+                            array.clone()
+                        } else if let Some(vec) = arg.downcast_ref::<Vec<#elem>>() {
+                            match vec.clone().try_into() {
+                                Ok(array) => array,
+                                Err(_) => return Err(::reflect::ReflectError::ConversionFailed {
+                                    from: ::reflect::type_shortname::<Vec<#elem>>(),
+                                    to: ::reflect::type_shortname::<#parameter_type>()
+                                }),
+                            }
+                        } else {
+                            return Err(::reflect::ReflectError::InvalidArgument { index: #i });
+                        }
+                    },
+                    None => return Err(::reflect::ReflectError::InvalidArgument { index: #i }),
+                };
+            }
+        },
+        Type::Path(_) if option_some_type(parameter_type).is_some() => {
+            // Handle Option<T>, given as the option, the value itself or null
+            let inner = option_some_type(parameter_type);
+            quote! {
+                let #name = match args.get(#i) {
+                    Some(arg) => {
+                        if let Some(option) = arg.downcast_ref::<#parameter_type>() {
+                            option.clone()
+                        } else if let Some(value) = arg.downcast_ref::<#inner>() {
+                            Some(value.clone())
+                        } else if arg.is::<::reflect::Null>() {
+                            None
+                        } else {
+                            return Err(::reflect::ReflectError::InvalidArgument { index: #i });
+                        }
+                    },
+                    None => return Err(::reflect::ReflectError::InvalidArgument { index: #i }),
+                };
+            }
+        },
+        Type::Path(_) => {
+            if is_container(parameter_type) {
+                // Handle Vec<T>, HashMap<K, V>, and collections (VecDeque<T>, HashSet<T>, BTreeSet<T>)
                 quote! {
                     let #name = match args.get(#i) {
                         Some(arg) => {
                             if let Some(vec) = arg.downcast_ref::<#parameter_type>() {
                                 vec.clone()
                             } else {
-                                return Err(format!("Invalid argument type for parameter {}", #i));
+                                return Err(::reflect::ReflectError::InvalidArgument { index: #i });
                             }
                         },
-                        None => return Err(format!("Missing argument for parameter {}", #i)),
+                        None => return Err(::reflect::ReflectError::InvalidArgument { index: #i }),
                     };
                 }
             } else {
-                // Handle primitive types
+                // Handle primitive types and reflected objects passed by value (requires Clone)
                 quote! {
                     let #name = match args.get(#i).and_then(|arg| arg.downcast_ref::<#parameter_type>()) {
-                        Some(value) => *value,
-                        None => return Err(format!("Invalid argument type for parameter {}", #i)),
+                        Some(value) => value.clone(),
+                        None => return Err(::reflect::ReflectError::InvalidArgument { index: #i }),
                     };
                 }
             }
         },
         _ => {
-            // Handle other types
+            // Handle other types, such as tuples (by value, requires Clone)
             quote! {
                 let #name = match args.get(#i).and_then(|arg| arg.downcast_ref::<#parameter_type>()) {
                     Some(value) => value.clone(),
-                    None => return Err(format!("Invalid argument type for parameter {}", #i)),
+                    None => return Err(::reflect::ReflectError::InvalidArgument { index: #i }),
                 };
             }
         }
     }
 }
+
+
+// Determine whether a parameter type is a container passed by value (`Vec<T>`, `HashMap<K, V>`,
+// or a collection such as `VecDeque<T>`)
+fn is_container(ty: &Type) -> bool {
+    match ty {
+        Type::Path(TypePath { path, .. }) =>
+            path.segments.last().is_some_and(|seg| seg.ident == "Vec" || seg.ident == "HashMap") ||
+            collection_element_type(ty).is_some(),
+        _ => false
+    }
+}
+
+// Determine whether a type is `str`
+fn is_str(ty: &Type) -> bool {
+    matches!(ty, Type::Path(TypePath { path, .. }) if path.is_ident("str"))
+}
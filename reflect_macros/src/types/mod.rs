@@ -1,4 +1,6 @@
 
 pub mod parser;
 pub mod function_type;
-pub mod generator;
\ No newline at end of file
+pub mod generator;
+pub mod attributes;
+pub mod options;
\ No newline at end of file
@@ -0,0 +1,119 @@
+//! Options of the `reflect_impl` attribute
+//! - `rename = "name"`: register the type under the given name rather than the Rust type name
+//! - `namespace = "ns"`: register the type under the given namespace, as `ns::Name`
+//! - `qualified`: register the type under its fully qualified name, such as `mycrate::module::Name`
+//! - `clone`: register the type's `Clone` implementation, such that objects can be cloned through
+//!   reflection (`TypeInfo::clone_object`)
+//! - `debug`: register the type's `Debug` implementation, such that objects can be formatted
+//!   through reflection (`TypeInfo::debug_string`)
+//! - `eq`: register the type's `PartialEq` implementation, such that objects can be compared
+//!   through reflection (`TypeInfo::objects_equal`)
+//! - `auto_convert`: register a conversion from the parameter type of each ctor taking a single
+//!   object (such as `fn new (inner: Point) -> Self`) to the type, calling the ctor, such that
+//!   the object may be given wherever the type is expected
+//! - `include = "..."`: signatures of trait methods to reflect which are not written in the impl
+//!   block (relying on the trait's default implementation), separated by `;`, for example
+//!   `include = "fn describe(&self) -> String; fn scaled(&self, k: f64) -> Self"`
+//!
+//! The signatures must be given in full, as the macro only sees the impl block, not the trait
+//! declaring the default methods.
+//!
+
+use syn::{AttributeArgs, Lit, LitStr, Meta, NestedMeta, Signature};
+
+
+/// Options specified with `#[reflect_impl(...)]`
+#[derive(Default)]
+pub struct ImplOptions {
+    pub rename: Option<String>,
+    pub namespace: Option<String>,
+    pub qualified: bool,
+    pub clone: bool,
+    pub debug: bool,
+    pub eq: bool,
+    pub auto_convert: bool,
+    pub include: Vec<Signature>,
+}
+
+
+/// Parse options of `#[reflect_impl(...)]`
+///
+/// # Arguments
+/// * `args`: attribute arguments
+///
+/// # Returns
+/// * options specified (or defaults if there are none), or an error spanning the invalid option
+pub fn parse_impl_options(args: &AttributeArgs) -> Result<ImplOptions, syn::Error> {
+    let mut options = ImplOptions::default();
+
+    for arg in args {
+        match arg {
+            NestedMeta::Meta(Meta::Path(path)) if path.is_ident("qualified") =>
+                options.qualified = true,
+            NestedMeta::Meta(Meta::Path(path)) if path.is_ident("clone") =>
+                options.clone = true,
+            NestedMeta::Meta(Meta::Path(path)) if path.is_ident("debug") =>
+                options.debug = true,
+            NestedMeta::Meta(Meta::Path(path)) if path.is_ident("eq") =>
+                options.eq = true,
+            NestedMeta::Meta(Meta::Path(path)) if path.is_ident("auto_convert") =>
+                options.auto_convert = true,
+            NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("rename") => match &nv.lit {
+                Lit::Str(name) => options.rename = Some(name.value()),
+                lit => return Err(syn::Error::new_spanned(lit, "expected #[reflect_impl(rename = \"name\")]")),
+            },
+            NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("namespace") => match &nv.lit {
+                Lit::Str(namespace) => options.namespace = Some(namespace.value()),
+                lit => return Err(syn::Error::new_spanned(lit, "expected #[reflect_impl(namespace = \"ns\")]")),
+            },
+            NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("include") => match &nv.lit {
+                Lit::Str(signatures) => options.include.extend(parse_signatures(signatures)?),
+                lit => return Err(syn::Error::new_spanned(lit, "expected #[reflect_impl(include = \"fn f(...) -> T; ...\")]")),
+            },
+            _ => return Err(syn::Error::new_spanned(arg, "unsupported option in #[reflect_impl(...)]")),
+        }
+    }
+
+    Ok(options)
+}
+
+
+// parse signatures separated by `;`
+fn parse_signatures(signatures: &LitStr) -> Result<Vec<Signature>, syn::Error> {
+    signatures.value().split(';')
+        .map(str::trim)
+        .filter(|sig| !sig.is_empty())
+        .map(|sig| syn::parse_str::<Signature>(sig).map_err(|_| syn::Error::new_spanned(signatures,
+            format!("invalid signature in #[reflect_impl(include = ...)]: {}", sig))))
+        .collect()
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use syn::parse_quote;
+
+    #[test]
+    fn test_parse_options() {
+        let args: AttributeArgs = vec![parse_quote!(clone), parse_quote!(rename = "Alias")];
+        let options = parse_impl_options(&args).expect("failed to parse");
+        assert!(options.clone);
+        assert_eq!(options.rename.as_deref(), Some("Alias"));
+    }
+
+    #[test]
+    fn test_unsupported_option() {
+        let args: AttributeArgs = vec![parse_quote!(clone), parse_quote!(frobnicate)];
+        let error = parse_impl_options(&args).err().expect("expected error");
+        assert_eq!(error.to_string(), "unsupported option in #[reflect_impl(...)]");
+
+        let args: AttributeArgs = vec![parse_quote!(rename = 42)];
+        let error = parse_impl_options(&args).err().expect("expected error");
+        assert_eq!(error.to_string(), "expected #[reflect_impl(rename = \"name\")]");
+
+        let args: AttributeArgs = vec![parse_quote!(include = "fn f(&self) -> ; fn g(&self)")];
+        let error = parse_impl_options(&args).err().expect("expected error");
+        assert_eq!(error.to_string(), "invalid signature in #[reflect_impl(include = ...)]: fn f(&self) ->");
+    }
+}
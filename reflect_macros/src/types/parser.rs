@@ -2,87 +2,190 @@
 //! - AST-level representation of functions
 //! - AST-level representation of type
 //! - parsing of impl block -> abstract type representation
+//! - parsing of function signature -> abstract function representation
 //!
 
-use syn::{ItemImpl, ImplItem, Type, TypePath, Ident, ReturnType, FnArg, Pat, Path};
+use syn::{ItemImpl, ImplItem, Type, TypePath, Ident, ReturnType, FnArg, GenericParam, Pat, PathSegment, Signature};
+use crate::utilities::to_camel_case;
 use quote::ToTokens;
-use crate::types::function_type::{FunctionType, determine_function_type};
+use proc_macro2::{TokenStream, TokenTree, Group};
+use crate::types::function_type::{FunctionType, ReturnKind, determine_function_type, is_result_type, result_ok_type, option_some_type};
+use crate::types::attributes::{ReflectAttributes, parse_reflect_attributes, parse_parameter_default};
+use crate::types::options::ImplOptions;
 
 
 /// Representation of a function
 /// - name of function (important for methods and static functions)
+/// - name under which the function is reflected (differs if renamed)
 /// - type of function (Constructor, Method, Static)
 /// - argument vector of (name, type)
-/// - function return type
+/// - function return type (the `T` of a `Result<T, E>` or `Option<T>`)
+/// - how the value is returned (directly, as a `Result`, or as an `Option`)
+/// - doc comment (empty if undocumented)
 pub struct ParsedFunction {
     pub name: syn::Ident,
+    pub reflected_name: String,
     pub function_type: FunctionType,
     pub args: Vec<(syn::Ident, syn::Type)>,
+    pub defaults: Vec<Option<String>>,
     pub return_type: syn::Type,
+    pub return_kind: ReturnKind,
+    pub doc: String,
 }
 
 /// AST-level representation of a type
 pub struct ParsedType {
     pub type_name: syn::Type,
     pub trait_name: Option<Ident>,
+    pub trait_path: Option<syn::Path>,
+    pub rename: Option<String>,
+    pub namespace: Option<String>,
+    pub qualified: bool,
+    pub clone: bool,
+    pub debug: bool,
+    pub eq: bool,
+    pub auto_convert: bool,
     pub short_type_name: syn::Ident,
     pub type_path: proc_macro2::TokenStream,
     pub functions: Vec<ParsedFunction>,
 }
 
 /// Parse type (impl block)
-/// - collect functions
+/// - collect functions (other than those marked `#[reflect(skip)]`)
+/// - collect functions included by signature in the options (trait methods with a default
+///   implementation, which do not appear in the impl block)
 /// - collect meta information about type
-pub fn parse_type_block(input: &ItemImpl) -> ParsedType {
+/// - fails if the implementing type is not a path (such as `MyType` or `Buffer<f64>`), or a
+///   function has an unsupported parameter (see `parse_function`) or an invalid `#[reflect(...)]`
+///   attribute
+/// - fails if the impl has generic or lifetime parameters (such as `impl<'a> Parser<'a>`), as
+///   reflected types are concrete and `'static`; an impl of an instantiation (such as
+///   `impl Parser<'static>`), with or without a where clause, is reflected as any other type
+pub fn parse_type_block(input: &ItemImpl, impl_options: &ImplOptions) -> Result<ParsedType, syn::Error> {
     let type_name = &input.self_ty;
+    check_impl_generics(input)?;
 
-    let (trait_id, type_id) = get_impl_info(&input);
+    let (trait_id, type_id) = get_impl_info(input);
+    let trait_path = input.trait_.as_ref().map(|(_, path, _)| path.clone());
 
-    let short_type_name = match type_name.as_ref() {
-        Type::Path(TypePath { path, .. }) if !path.segments.is_empty() => path.segments.last().unwrap().ident.clone(),
-        _ => panic!("Unsupported type in reflect_type"),
+    let (short_type_name, type_path) = match type_name.as_ref() {
+        Type::Path(type_path @ TypePath { path, .. }) if !path.segments.is_empty() =>
+            (short_name(path.segments.last().unwrap()), type_path.to_token_stream()),
+        _ => return Err(syn::Error::new_spanned(type_name,
+            "reflect_impl requires the implementing type to be a path, such as `MyType` or `Buffer<f64>`")),
     };
 
-    let type_path = if let Type::Path(type_path) = type_name.as_ref() {
-        type_path.to_token_stream()
-    } else {
-        panic!("Expected a viable type")
-    };
-
-    let functions = input.items.iter().filter_map(|item| {
+    let mut functions = Vec::new();
+    for item in input.items.iter() {
         if let ImplItem::Method(method) = item {
-            let function_type = determine_function_type(method);
-
-            let args = method.sig.inputs.iter()
-                .filter_map(|arg| if let FnArg::Typed(pat_type) = arg {
-                    if let Pat::Ident(pat_ident) = &*pat_type.pat {
-                        Some((pat_ident.ident.clone(), (*pat_type.ty).clone()))
-                    } else { None }
-                } else { None })
-                .collect();
-
-            let return_type = match &method.sig.output {
-                ReturnType::Default => syn::parse_quote!(()),
-                ReturnType::Type(_, ty) => (**ty).clone(),
-            };
-
-            Some(ParsedFunction {
-                name: method.sig.ident.clone(),
-                function_type,
-                args,
-                return_type,
-            })
-        } else {
-            None
+            let options = parse_reflect_attributes(&method.attrs)?;
+            if !options.skip {
+                let function_type = determine_function_type(&method.sig);
+                functions.push(parse_function(&method.sig, &options, function_type, Some(type_name))?);
+            }
         }
-    }).collect();
+    }
 
-    ParsedType {
+    for sig in impl_options.include.iter() {
+        functions.push(parse_function(sig, &ReflectAttributes::default(), determine_function_type(sig), Some(type_name))?);
+    }
+
+    Ok(ParsedType {
         type_name: type_id,
         trait_name: trait_id,
+        trait_path,
+        rename: impl_options.rename.clone(),
+        namespace: impl_options.namespace.clone(),
+        qualified: impl_options.qualified,
+        clone: impl_options.clone,
+        debug: impl_options.debug,
+        eq: impl_options.eq,
+        auto_convert: impl_options.auto_convert,
         short_type_name,
         type_path,
         functions,
+    })
+}
+
+/// Parse function signature
+/// - argument names and types
+/// - return type and how the value is returned
+/// - fails if a parameter is not a plain name (for example a destructured tuple `(a, b): (f64, f64)`)
+/// - fails if the function is async, other than a method taking `&self` with the `async` feature
+///
+/// # Arguments
+/// * `sig`: function signature
+/// * `options`: options given by `#[reflect(...)]` attributes
+/// * `function_type`: type of function (Constructor, Method, Static)
+/// * `self_type`: the implementing type (used to resolve `Self`), if within an impl block
+pub fn parse_function(sig: &Signature, options: &ReflectAttributes, function_type: FunctionType, self_type: Option<&Type>) -> Result<ParsedFunction, syn::Error> {
+    if sig.asyncness.is_some() {
+        if !matches!(function_type, FunctionType::AsyncMethod) {
+            return Err(syn::Error::new_spanned(sig, format!(
+                "reflected function `{}` is async, only async methods taking `&self` are supported", sig.ident)));
+        }
+        if !cfg!(feature = "async") {
+            return Err(syn::Error::new_spanned(sig, format!(
+                "reflected method `{}` is async, requiring the `async` feature of reflect", sig.ident)));
+        }
+    }
+
+    let mut args = Vec::new();
+    let mut defaults = Vec::new();
+    for arg in sig.inputs.iter() {
+        if let FnArg::Typed(pat_type) = arg {
+            defaults.push(parse_parameter_default(&pat_type.attrs)?);
+            match &*pat_type.pat {
+                Pat::Ident(pat_ident) => args.push((pat_ident.ident.clone(), (*pat_type.ty).clone())),
+                pat => return Err(syn::Error::new_spanned(pat, format!(
+                    "reflected function `{}` has unsupported parameter pattern `{}`, parameters must be plain names such as `x: T`",
+                    sig.ident, pat.to_token_stream()))),
+            }
+        }
+    }
+
+    let resolve = |ty: &Type| match self_type {
+        Some(self_ty) => resolve_self(ty, self_ty),
+        None => ty.clone(),
+    };
+
+    let (return_type, return_kind) = match &sig.output {
+        ReturnType::Default => (syn::parse_quote!(()), ReturnKind::Value),
+        ReturnType::Type(_, ty) if is_result_type(ty) =>
+            (resolve(result_ok_type(ty).unwrap()), ReturnKind::Result),
+        ReturnType::Type(_, ty) if option_some_type(ty).is_some() =>
+            (resolve(option_some_type(ty).unwrap()), ReturnKind::Option { none_as_error: options.none_as_error }),
+        ReturnType::Type(_, ty) => (resolve(ty), ReturnKind::Value),
+    };
+
+    Ok(ParsedFunction {
+        name: sig.ident.clone(),
+        reflected_name: options.rename.clone().unwrap_or_else(|| sig.ident.to_string()),
+        function_type,
+        args,
+        defaults,
+        return_type,
+        return_kind,
+        doc: options.doc.clone(),
+    })
+}
+
+// Check that the impl has no generic or lifetime parameters, which the generated registration
+// (outside of the impl) cannot bind
+fn check_impl_generics(item: &ItemImpl) -> Result<(), syn::Error> {
+    let type_name = match item.self_ty.as_ref() {
+        Type::Path(TypePath { path, .. }) => path.segments.last().map(|segment| segment.ident.to_string()),
+        _ => None
+    }.unwrap_or_else(|| "MyType".to_string());
+
+    match item.generics.params.first() {
+        None => Ok(()),
+        Some(GenericParam::Lifetime(_)) => Err(syn::Error::new_spanned(&item.generics, format!(
+            "reflect_impl does not support lifetime parameters, as reflected types must be 'static; reflect the type bound to 'static instead, such as `impl {}<'static>`",
+            type_name))),
+        Some(_) => Err(syn::Error::new_spanned(&item.generics, format!(
+            "reflect_impl does not support generic impls; reflect each instantiation of the type instead, such as `impl {}<f64>`",
+            type_name))),
     }
 }
 
@@ -97,4 +200,126 @@ fn get_impl_info(item: &ItemImpl) -> (Option<Ident>, Type) {
     let type_path = (*item.self_ty).clone();
 
     (trait_path, type_path)
-}
\ No newline at end of file
+}
+
+/// Short name of a type, used to name generated code
+/// - incorporates generic arguments so that instantiations of a generic type are distinct,
+///   for example `Buffer<f64>` becomes `BufferF64`
+fn short_name(segment: &PathSegment) -> Ident {
+    let mut name = segment.ident.to_string();
+
+    fn append(tokens: TokenStream, name: &mut String) {
+        for token in tokens {
+            match token {
+                TokenTree::Ident(ident) => name.push_str(&to_camel_case(&ident.to_string())),
+                TokenTree::Group(group) => append(group.stream(), name),
+                _ => (),
+            }
+        }
+    }
+    append(segment.arguments.to_token_stream(), &mut name);
+
+    Ident::new(&name, segment.ident.span())
+}
+
+/// Replace `Self` within a type by the implementing type
+/// - generated code lives outside of the impl block, where `Self` has no meaning
+/// - for example `Option<Self>` becomes `Option<Momentum>` in `impl Momentum`
+fn resolve_self(ty: &Type, self_ty: &Type) -> Type {
+    fn replace(tokens: TokenStream, self_ty: &Type) -> TokenStream {
+        tokens.into_iter().map(|token| match token {
+            TokenTree::Ident(ident) if ident == "Self" => self_ty.to_token_stream(),
+            TokenTree::Group(group) => {
+                let mut replaced = Group::new(group.delimiter(), replace(group.stream(), self_ty));
+                replaced.set_span(group.span());
+                TokenTree::Group(replaced).into()
+            }
+            other => other.into(),
+        }).collect()
+    }
+
+    syn::parse2(replace(ty.to_token_stream(), self_ty)).expect("failed to resolve Self in type")
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use syn::parse_quote;
+
+    #[test]
+    fn test_unsupported_parameter() {
+        let input: ItemImpl = parse_quote! {
+            impl Point {
+                fn new ((x, y): (f64, f64)) -> Self {
+                    Point { x, y }
+                }
+            }
+        };
+        let error = parse_type_block(&input, &ImplOptions::default()).err().expect("expected error");
+        assert_eq!(error.to_string(),
+            "reflected function `new` has unsupported parameter pattern `(x , y)`, parameters must be plain names such as `x: T`");
+    }
+
+    #[test]
+    fn test_unsupported_type() {
+        let input: ItemImpl = parse_quote! {
+            impl Trait for &'static str {
+                fn f (&self) -> f64 {
+                    0.0
+                }
+            }
+        };
+        let error = parse_type_block(&input, &ImplOptions::default()).err().expect("expected error");
+        assert!(error.to_string().starts_with("reflect_impl requires the implementing type to be a path"), "{}", error);
+    }
+
+    #[test]
+    fn test_unsupported_async() {
+        let input: ItemImpl = parse_quote! {
+            impl Loader {
+                async fn open (path: String) -> Self {
+                    Loader { path }
+                }
+            }
+        };
+        let error = parse_type_block(&input, &ImplOptions::default()).err().expect("expected error");
+        assert_eq!(error.to_string(), "reflected function `open` is async, only async methods taking `&self` are supported");
+    }
+
+    #[test]
+    fn test_unsupported_generics() {
+        let input: ItemImpl = parse_quote! {
+            impl<'a> Parser<'a> {
+                fn new (text: &'a str) -> Self {
+                    Parser { text }
+                }
+            }
+        };
+        let error = parse_type_block(&input, &ImplOptions::default()).err().expect("expected error");
+        assert_eq!(error.to_string(),
+            "reflect_impl does not support lifetime parameters, as reflected types must be 'static; reflect the type bound to 'static instead, such as `impl Parser<'static>`");
+
+        let input: ItemImpl = parse_quote! {
+            impl<T> Buffer<T> where T: Clone {
+                fn new (values: &[T]) -> Self {
+                    Buffer { values: values.to_vec() }
+                }
+            }
+        };
+        let error = parse_type_block(&input, &ImplOptions::default()).err().expect("expected error");
+        assert_eq!(error.to_string(),
+            "reflect_impl does not support generic impls; reflect each instantiation of the type instead, such as `impl Buffer<f64>`");
+
+        // a where clause without generic parameters is fine
+        let input: ItemImpl = parse_quote! {
+            impl Parser<'static> where Self: Send {
+                fn new (text: String) -> Self {
+                    Parser { text }
+                }
+            }
+        };
+        let parsed = parse_type_block(&input, &ImplOptions::default()).expect("failed to parse");
+        assert_eq!(parsed.short_type_name.to_string(), "ParserStatic");
+    }
+}
@@ -3,9 +3,6 @@
 //! -
 //!
 
-use quote::{quote, format_ident};
-use syn::Ident;
-
 
 /// Convert to camel-case
 pub fn to_camel_case(s: &str) -> String {
@@ -28,5 +25,5 @@ pub fn to_camel_case(s: &str) -> String {
 
 /// Convert identifier to camel-case
 pub fn ident_camel_case(s: &proc_macro2::Ident) -> String {
-    return to_camel_case(&s.to_string());
+    to_camel_case(&s.to_string())
 }
\ No newline at end of file